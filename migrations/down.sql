@@ -1,9 +1,25 @@
 DROP VIEW nfl.simulation_results_readable;
 
+DROP TABLE nfl.simulated_games;
+
+DROP TABLE nfl.simulation_queue;
+
 DROP TABLE nfl.simulation_results;
 
 DROP TABLE nfl.simulations;
 
+DROP TABLE nfl.franchises;
+
+DROP TABLE nfl.elo_ratings;
+
+DROP TABLE nfl.game_odds;
+
+ALTER TABLE nfl.games DROP COLUMN IF EXISTS neutral_site;
+
+ALTER TABLE nfl.games DROP COLUMN IF EXISTS home_touchdowns;
+
+ALTER TABLE nfl.games DROP COLUMN IF EXISTS away_touchdowns;
+
 DROP TYPE nfl.resultset;
 
 DROP TYPE nfl.gameresult;
@@ -1,12 +1,50 @@
 CREATE TYPE nfl.gameresult AS ENUM ('home win', 'away win', 'tie');
 
-CREATE TYPE nfl.resultset AS ENUM ('playoff seed', 'draft position');
+CREATE TYPE nfl.resultset AS ENUM ('playoff seed', 'draft position', 'win total', 'reached conference championship', 'reached super bowl', 'won super bowl');
+
+ALTER TYPE nfl.resultset ADD VALUE IF NOT EXISTS 'reached conference championship';
+
+ALTER TYPE nfl.resultset ADD VALUE IF NOT EXISTS 'reached super bowl';
+
+ALTER TYPE nfl.resultset ADD VALUE IF NOT EXISTS 'won super bowl';
+
+ALTER TABLE nfl.games ADD COLUMN IF NOT EXISTS neutral_site boolean NOT NULL DEFAULT false;
+
+ALTER TABLE nfl.games ADD COLUMN IF NOT EXISTS home_touchdowns int4;
+
+ALTER TABLE nfl.games ADD COLUMN IF NOT EXISTS away_touchdowns int4;
+
+CREATE TABLE IF NOT EXISTS nfl.franchises (
+    team_id int4 NOT NULL,
+    franchise_id int4 NOT NULL,
+    CONSTRAINT franchises_pkey PRIMARY KEY (team_id),
+    CONSTRAINT franchises_team_id_fkey FOREIGN KEY (team_id) REFERENCES nfl.teams(team_id) ON DELETE CASCADE ON UPDATE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS nfl.elo_ratings (
+    team_id int4 NOT NULL,
+    season int4 NOT NULL,
+    rating float8 NOT NULL,
+    CONSTRAINT elo_ratings_pkey PRIMARY KEY (team_id, season),
+    CONSTRAINT elo_ratings_team_id_fkey FOREIGN KEY (team_id) REFERENCES nfl.teams(team_id) ON DELETE CASCADE ON UPDATE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS nfl.game_odds (
+    game_id int4 NOT NULL,
+    home_spread float8,
+    home_moneyline int4,
+    away_moneyline int4,
+    CONSTRAINT game_odds_pkey PRIMARY KEY (game_id),
+    CONSTRAINT game_odds_game_id_fkey FOREIGN KEY (game_id) REFERENCES nfl.games(game_id) ON DELETE CASCADE ON UPDATE CASCADE
+);
 
 CREATE TABLE IF NOT EXISTS nfl.simulations (
     simulation_id serial4 NOT NULL,
     simulation_timestamp timestamptz NOT NULL DEFAULT NOW(),
 	season int4 NOT NULL,
     simulations_per_game_result bigint NOT NULL,
+    baseline_simulations bigint NOT NULL,
+    include_decided boolean NOT NULL DEFAULT false,
     CONSTRAINT simulations_pkey PRIMARY KEY (simulation_id)
 );
 
@@ -19,12 +57,43 @@ CREATE TABLE IF NOT EXISTS nfl.simulation_results (
 	result_set nfl.resultset,
 	team_rank smallint,
     simulations_with_rank bigint NOT NULL,
+    simulations bigint,
     CONSTRAINT simulation_results_pkey PRIMARY KEY (simulation_result_id),
     CONSTRAINT simulation_results_simulation_id_fkey FOREIGN KEY (simulation_id) REFERENCES nfl.simulations(simulation_id) ON DELETE CASCADE ON UPDATE CASCADE,
     CONSTRAINT simulation_results_game_id_fkey FOREIGN KEY (game_id) REFERENCES nfl.games(game_id) ON DELETE CASCADE ON UPDATE CASCADE,
     CONSTRAINT simulation_results_simulation_team_id_fkey FOREIGN KEY (simulation_team_id) REFERENCES nfl.teams(team_id) ON DELETE CASCADE ON UPDATE CASCADE
 );
 
+ALTER TABLE nfl.simulation_results ADD COLUMN IF NOT EXISTS simulations bigint;
+
+CREATE TABLE IF NOT EXISTS nfl.simulation_queue (
+    work_id bigserial NOT NULL,
+    simulation_id int4 NOT NULL,
+    game_id int4,
+    simulated_game_result nfl.gameresult,
+    claimed_by text,
+    claimed_at timestamptz,
+    completed_at timestamptz,
+    CONSTRAINT simulation_queue_pkey PRIMARY KEY (work_id),
+    CONSTRAINT simulation_queue_simulation_id_fkey FOREIGN KEY (simulation_id) REFERENCES nfl.simulations(simulation_id) ON DELETE CASCADE ON UPDATE CASCADE,
+    CONSTRAINT simulation_queue_game_id_fkey FOREIGN KEY (game_id) REFERENCES nfl.games(game_id) ON DELETE CASCADE ON UPDATE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS nfl.simulated_games (
+    simulated_game_id bigserial NOT NULL,
+    simulation_id int4 NOT NULL,
+    run_index int4 NOT NULL,
+    game_id int4 NOT NULL,
+    game_result nfl.gameresult NOT NULL,
+    home_score int4,
+    away_score int4,
+    is_simulated boolean NOT NULL,
+    CONSTRAINT simulated_games_pkey PRIMARY KEY (simulated_game_id),
+    CONSTRAINT simulated_games_run_game_key UNIQUE (simulation_id, run_index, game_id),
+    CONSTRAINT simulated_games_simulation_id_fkey FOREIGN KEY (simulation_id) REFERENCES nfl.simulations(simulation_id) ON DELETE CASCADE ON UPDATE CASCADE,
+    CONSTRAINT simulated_games_game_id_fkey FOREIGN KEY (game_id) REFERENCES nfl.games(game_id) ON DELETE CASCADE ON UPDATE CASCADE
+);
+
 CREATE VIEW nfl.simulation_results_readable 
 AS 
 	WITH
@@ -41,7 +110,7 @@ AS
 				sr.result_set AS result_set,
 				sr.team_rank AS team_rank,
 				sr.simulations_with_rank AS simulations_with_rank,
-				s.simulations_per_game_result AS simulations_run
+				COALESCE(sr.simulations, s.simulations_per_game_result) AS simulations_run
 			FROM nfl.simulation_results sr
 			LEFT JOIN nfl.simulations s
 			USING (simulation_id)
@@ -64,6 +133,9 @@ AS
 					WHEN result_set = 'playoff seed' AND team_rank <= 4 THEN 'division winner'
 					WHEN result_set = 'playoff seed' AND team_rank >= 5 THEN 'wildcard team'
 					WHEN result_set = 'draft position' AND team_rank = 1 THEN 'first pick'
+					WHEN result_set = 'reached conference championship' THEN 'conference finalist'
+					WHEN result_set = 'reached super bowl' THEN 'super bowl team'
+					WHEN result_set = 'won super bowl' THEN 'super bowl champion'
 					ELSE NULL
 				END AS result_condition,
 				CAST(simulations_with_rank AS float) / CAST(simulations_run AS float) AS probability
@@ -0,0 +1,326 @@
+use crate::{Error, Game, PoolType, Season, Team, PLAYOFF_GAME_ID};
+use rand::Rng;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::Arc;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum PlayoffRound {
+    WildCard,
+    Divisional,
+    ConferenceChampionship,
+    SuperBowl,
+}
+
+impl PlayoffRound {
+    pub const ALL: [PlayoffRound; 4] = [
+        PlayoffRound::WildCard,
+        PlayoffRound::Divisional,
+        PlayoffRound::ConferenceChampionship,
+        PlayoffRound::SuperBowl,
+    ];
+}
+
+impl fmt::Display for PlayoffRound {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PlayoffRound::WildCard => write!(f, "wild card"),
+            PlayoffRound::Divisional => write!(f, "divisional"),
+            PlayoffRound::ConferenceChampionship => write!(f, "conference championship"),
+            PlayoffRound::SuperBowl => write!(f, "super bowl"),
+        }
+    }
+}
+
+/// How many teams each conference sends to the playoffs, and how many of them skip the
+/// wild-card round. Every format has four division winners, seeded first.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PlayoffFormat {
+    /// Six teams and two byes per conference, used through the 2019 season.
+    SixTeams,
+    /// Seven teams and one bye per conference, used from the 2020 season.
+    SevenTeams,
+}
+
+impl PlayoffFormat {
+    pub fn for_season(season_year: i32) -> PlayoffFormat {
+        match season_year {
+            year if year <= 2019 => PlayoffFormat::SixTeams,
+            _ => PlayoffFormat::SevenTeams,
+        }
+    }
+
+    pub fn playoff_teams(&self) -> u8 {
+        match self {
+            PlayoffFormat::SixTeams => 6,
+            PlayoffFormat::SevenTeams => 7,
+        }
+    }
+
+    pub fn wildcards(&self) -> u8 {
+        self.playoff_teams() - 4
+    }
+
+    pub fn byes(&self) -> u8 {
+        match self {
+            PlayoffFormat::SixTeams => 2,
+            PlayoffFormat::SevenTeams => 1,
+        }
+    }
+
+    /// Wild-card round games as zero-based (higher, lower) seed indices: the best remaining
+    /// seed hosts the worst.
+    fn wild_card_games(&self) -> Vec<(usize, usize)> {
+        let byes: usize = usize::from(self.byes());
+        let last: usize = usize::from(self.playoff_teams()) - 1;
+        (byes..)
+            .zip((byes..=last).rev())
+            .take_while(|(higher_seed, lower_seed)| higher_seed < lower_seed)
+            .collect()
+    }
+}
+
+/// A game of a simulated postseason. Only the Super Bowl is at a neutral site, where
+/// `home_team_id` is just the first conference's champion.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct PlayoffMatchup {
+    pub round: PlayoffRound,
+    pub home_team_id: i32,
+    pub away_team_id: i32,
+    pub winner: i32,
+    pub neutral_site: bool,
+}
+
+/// One simulated postseason. A bye counts as a wild-card round win.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct PlayoffBracket {
+    pub round_winners: HashMap<PlayoffRound, Vec<i32>>,
+    /// Every game played, in order.
+    pub games: Vec<PlayoffMatchup>,
+    pub champion: Option<i32>,
+}
+
+impl Season {
+    /// Plays the conference brackets of the season's `PlayoffFormat` and the Super Bowl from
+    /// the current simulation's seeds. The divisional round is reseeded so the top seed hosts
+    /// the lowest remaining seed, and the better seed hosts every game before the Super Bowl,
+    /// which is played at a neutral site.
+    pub(crate) fn simulate_playoff_bracket(&mut self) -> Result<PlayoffBracket, Error> {
+        let mut bracket: PlayoffBracket = PlayoffBracket::default();
+        let mut conference_champions: Vec<i32> = Vec::new();
+        let playoff_format: PlayoffFormat = self.playoff_format();
+        let mut conferences: Vec<String> = self.conference_mapping.keys().cloned().collect();
+        conferences.sort();
+        for conference in conferences {
+            let seeds: Vec<i32> = self.conference_seeds(&conference)?;
+
+            let mut remaining: Vec<usize> = (0..usize::from(playoff_format.byes())).collect();
+            for (higher_seed, lower_seed) in playoff_format.wild_card_games() {
+                remaining.push(self.play_seeded_game(
+                    &mut bracket,
+                    PlayoffRound::WildCard,
+                    &seeds,
+                    higher_seed,
+                    lower_seed,
+                )?);
+            }
+            bracket.record_round(PlayoffRound::WildCard, &seeds, &remaining);
+
+            remaining.sort();
+            let mut divisional_winners: Vec<usize> = Vec::new();
+            for (higher_seed, lower_seed) in
+                [(remaining[0], remaining[3]), (remaining[1], remaining[2])]
+            {
+                divisional_winners.push(self.play_seeded_game(
+                    &mut bracket,
+                    PlayoffRound::Divisional,
+                    &seeds,
+                    higher_seed,
+                    lower_seed,
+                )?);
+            }
+            bracket.record_round(PlayoffRound::Divisional, &seeds, &divisional_winners);
+
+            divisional_winners.sort();
+            let champion: usize = self.play_seeded_game(
+                &mut bracket,
+                PlayoffRound::ConferenceChampionship,
+                &seeds,
+                divisional_winners[0],
+                divisional_winners[1],
+            )?;
+            bracket.record_round(PlayoffRound::ConferenceChampionship, &seeds, &[champion]);
+            conference_champions.push(seeds[champion]);
+        }
+
+        if let [first_team_id, second_team_id] = conference_champions[..] {
+            let first_team_probability: f64 =
+                (self.playoff_win_probability(first_team_id, second_team_id, true)? + 1.0
+                    - self.playoff_win_probability(second_team_id, first_team_id, true)?)
+                    / 2.0;
+            let champion: i32 = match self.rng.gen::<f64>() < first_team_probability {
+                true => first_team_id,
+                false => second_team_id,
+            };
+            bracket
+                .round_winners
+                .insert(PlayoffRound::SuperBowl, vec![champion]);
+            bracket.games.push(PlayoffMatchup {
+                round: PlayoffRound::SuperBowl,
+                home_team_id: first_team_id,
+                away_team_id: second_team_id,
+                winner: champion,
+                neutral_site: true,
+            });
+            bracket.champion = Some(champion);
+        }
+        Ok(bracket)
+    }
+
+    /// Adds the playoff teams to the current simulation's draft order after the non-playoff
+    /// teams: wild-card round losers first, then divisional and conference championship
+    /// losers, the Super Bowl loser and the champion. Teams eliminated in the same round are
+    /// ordered like non-playoff teams, worst record first.
+    pub(crate) fn evaluate_playoff_draft_order(
+        &mut self,
+        bracket: &PlayoffBracket,
+    ) -> Result<(), Error> {
+        let mut remaining: HashSet<i32> = self
+            .current_simulation_result
+            .playoff_seeding
+            .values()
+            .flatten()
+            .copied()
+            .collect();
+        let drafted: usize = self.current_simulation_result.draft_order.len();
+        let picks: usize = drafted + remaining.len();
+        let too_many = |_| Error::InvalidData(format!("{} picks don't fit a draft order", picks));
+        // Positions run up to `picks`, so it has to fit as well as the starting point.
+        u8::try_from(picks).map_err(too_many)?;
+        let mut draft_position: u8 = u8::try_from(drafted).map_err(too_many)?;
+        for round in PlayoffRound::ALL {
+            let winners: HashSet<i32> = match bracket.round_winners.get(&round) {
+                Some(winners) => winners.iter().copied().collect(),
+                None => HashSet::new(),
+            };
+            let losers: Vec<i32> = remaining.difference(&winners).copied().collect();
+            remaining = winners;
+            for team_id in self.evaluate_pool(losers, PoolType::DraftOrder)? {
+                draft_position += 1;
+                self.current_simulation_result
+                    .draft_order
+                    .insert(draft_position, team_id);
+            }
+        }
+        for team_id in remaining {
+            draft_position += 1;
+            self.current_simulation_result
+                .draft_order
+                .insert(draft_position, team_id);
+        }
+        Ok(())
+    }
+
+    fn conference_seeds(&self, conference: &str) -> Result<Vec<i32>, Error> {
+        (1..=self.playoff_format().playoff_teams())
+            .map(|seed| {
+                let teams = self.current_simulation_result.playoff_seeding.get(&seed);
+                let team_id: Option<&i32> = teams.and_then(|teams| {
+                    teams.iter().find(|team_id| {
+                        self.teams
+                            .get(team_id)
+                            .is_some_and(|team| team.conference == conference)
+                    })
+                });
+                match team_id {
+                    Some(team_id) => Ok(*team_id),
+                    None => Err(Error::InvalidData(format!(
+                        "{} has no playoff seed {}",
+                        conference, seed
+                    ))),
+                }
+            })
+            .collect()
+    }
+
+    /// Plays and records a `round` game, returning the index into `seeds` of the winner.
+    /// `home` must be the better seed.
+    fn play_seeded_game(
+        &mut self,
+        bracket: &mut PlayoffBracket,
+        round: PlayoffRound,
+        seeds: &[i32],
+        home: usize,
+        away: usize,
+    ) -> Result<usize, Error> {
+        let home_win_probability: f64 =
+            self.playoff_win_probability(seeds[home], seeds[away], false)?;
+        let winner: usize = match self.rng.gen::<f64>() < home_win_probability {
+            true => home,
+            false => away,
+        };
+        bracket.games.push(PlayoffMatchup {
+            round,
+            home_team_id: seeds[home],
+            away_team_id: seeds[away],
+            winner: seeds[winner],
+            neutral_site: false,
+        });
+        Ok(winner)
+    }
+
+    fn playoff_win_probability(
+        &self,
+        home_team_id: i32,
+        away_team_id: i32,
+        neutral_site: bool,
+    ) -> Result<f64, Error> {
+        let team = |team_id: i32| -> Result<&Arc<Team>, Error> {
+            self.teams
+                .get(&team_id)
+                .ok_or_else(|| Error::InvalidData(format!("unknown playoff team {}", team_id)))
+        };
+        let home_team: &Arc<Team> = team(home_team_id)?;
+        let away_team: &Arc<Team> = team(away_team_id)?;
+        let game: Game = Game {
+            game_id: PLAYOFF_GAME_ID,
+            season_year: self.season_year,
+            week: 0,
+            division_game: home_team.division == away_team.division,
+            conference_game: home_team.conference == away_team.conference,
+            home_team: home_team.clone(),
+            away_team: away_team.clone(),
+            game_result: None,
+            is_simulated: false,
+            home_score: None,
+            away_score: None,
+            neutral_site,
+            home_touchdowns: None,
+            away_touchdowns: None,
+        };
+        Ok(self.home_win_probability(&game))
+    }
+}
+
+impl PlayoffBracket {
+    /// Teams that hosted at least one game.
+    pub fn hosts(&self) -> HashSet<i32> {
+        self.games
+            .iter()
+            .filter(|game| !game.neutral_site)
+            .map(|game| game.home_team_id)
+            .collect()
+    }
+
+    fn record_round(&mut self, round: PlayoffRound, seeds: &[i32], winners: &[usize]) {
+        self.round_winners
+            .entry(round)
+            .or_default()
+            .extend(winners.iter().map(|winner| seeds[*winner]));
+    }
+}
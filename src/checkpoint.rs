@@ -0,0 +1,76 @@
+use crate::{Error, Game, Season, SimulationResultLookup, Team, TeamSimulationResults};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+
+/// What `Season::save_checkpoint` writes, as JSON. Results are a list of pairs because JSON
+/// object keys must be strings.
+#[derive(Deserialize, Serialize)]
+struct Checkpoint {
+    season_year: i32,
+    simulation_id: Option<i32>,
+    seed: Option<u64>,
+    /// Seed of the generator both the saved and the restored season continue with.
+    rng_seed: u64,
+    teams: Vec<Team>,
+    actual_games: Vec<Game>,
+    overall_results: Vec<(SimulationResultLookup, TeamSimulationResults)>,
+    division_playoff_counts: HashMap<String, HashMap<u8, i32>>,
+}
+
+impl Season {
+    /// Writes the teams, actual games, accumulated results and random state to `path`, so a
+    /// long run can be picked up after a restart or on another machine. A generator can't be
+    /// saved as is, so this one is reseeded from itself and the seed is saved; the season and
+    /// any copy restored with `load_checkpoint` then draw the same numbers. Configuration and
+    /// models are not saved.
+    pub fn save_checkpoint(&mut self, path: &str) -> Result<(), Error> {
+        let rng_seed: u64 = self.rng.next_u64();
+        self.rng = Box::new(StdRng::seed_from_u64(rng_seed));
+
+        let mut teams: Vec<Team> = self.teams.values().map(|team| Team::clone(team)).collect();
+        teams.sort_by_key(|team| team.team_id);
+        let mut actual_games: Vec<Game> = self.actual_games.values().cloned().collect();
+        actual_games.sort_by_key(|game| game.game_id);
+        let checkpoint: Checkpoint = Checkpoint {
+            season_year: self.season_year,
+            simulation_id: self.simulation_id,
+            seed: self.seed,
+            rng_seed,
+            teams,
+            actual_games,
+            overall_results: self
+                .overall_results
+                .iter()
+                .map(|(lookup, results)| (lookup.clone(), results.clone()))
+                .collect(),
+            division_playoff_counts: self.division_playoff_counts.clone(),
+        };
+
+        let mut writer: BufWriter<File> = BufWriter::new(File::create(path)?);
+        serde_json::to_writer(&mut writer, &checkpoint)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Rebuilds a season saved with `save_checkpoint`, with the default configuration.
+    pub fn load_checkpoint(path: &str) -> Result<Season, Error> {
+        let checkpoint: Checkpoint = serde_json::from_reader(BufReader::new(File::open(path)?))?;
+        let mut season: Season = Season::from_parts(checkpoint.teams, checkpoint.actual_games)?;
+        if season.season_year != checkpoint.season_year {
+            return Err(Error::InvalidData(format!(
+                "{} is for season {}, but its games are from {}",
+                path, checkpoint.season_year, season.season_year
+            )));
+        }
+        season.simulation_id = checkpoint.simulation_id;
+        season.seed = checkpoint.seed;
+        season.rng = Box::new(StdRng::seed_from_u64(checkpoint.rng_seed));
+        season.overall_results = checkpoint.overall_results.into_iter().collect();
+        season.division_playoff_counts = checkpoint.division_playoff_counts;
+        Ok(season)
+    }
+}
@@ -0,0 +1,338 @@
+use nfl_schedule_simulator::{Error, Game, GameResult, ProgressSink, Season, Standing, TeamOdds};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::widgets::{Bar, BarChart, BarGroup, Block, Gauge, Row, Table, TableState};
+use ratatui::{DefaultTerminal, Frame};
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::Duration;
+
+const SWING_ROWS: usize = 12;
+
+/// What the simulation thread tells the dashboard, through `DashboardProgress`.
+#[derive(Debug)]
+enum Update {
+    GameStarted {
+        index: usize,
+        total_games: usize,
+    },
+    Baseline(HashMap<i32, TeamOdds>),
+    /// Each team's playoff odds with one game's result pinned.
+    Scenario {
+        game_id: i32,
+        make_playoffs: HashMap<i32, f64>,
+    },
+    Finished,
+    /// The simulation stopped with an error; the dashboard closes and returns it.
+    Failed(Error),
+}
+
+#[derive(Clone, Debug)]
+struct DashboardProgress {
+    sender: Sender<Update>,
+}
+
+impl ProgressSink for DashboardProgress {
+    fn on_game_started(&self, _game: &Game, index: usize, total_games: usize) {
+        let _ = self.sender.send(Update::GameStarted { index, total_games });
+    }
+
+    fn on_scenario_done(
+        &self,
+        scenario: Option<(i32, &GameResult)>,
+        odds: &HashMap<i32, TeamOdds>,
+    ) {
+        let update: Update = match scenario {
+            None => Update::Baseline(odds.clone()),
+            Some((game_id, _)) => Update::Scenario {
+                game_id,
+                make_playoffs: odds
+                    .iter()
+                    .map(|(team_id, team_odds)| (*team_id, team_odds.make_playoffs))
+                    .collect(),
+            },
+        };
+        let _ = self.sender.send(update);
+    }
+}
+
+/// Terminal dashboard over a running simulation: standings by division with each team's
+/// odds, the selected team's seed distribution, and the games that swing its playoff odds
+/// most among the scenarios finished so far. Up and down (or k and j) select a team and q
+/// quits, even while the simulation is still running.
+pub struct Dashboard {
+    standings: Vec<Standing>,
+    games: HashMap<i32, Game>,
+    baseline: HashMap<i32, TeamOdds>,
+    scenarios: HashMap<i32, Vec<HashMap<i32, f64>>>,
+    progress: Option<(usize, usize)>,
+    finished: bool,
+    table_state: TableState,
+}
+
+impl Dashboard {
+    pub fn new(season: &Season) -> Result<Dashboard, Error> {
+        Ok(Dashboard {
+            standings: season.standings()?,
+            games: season.actual_games.clone(),
+            baseline: HashMap::new(),
+            scenarios: HashMap::new(),
+            progress: None,
+            finished: false,
+            table_state: TableState::default().with_selected(Some(0)),
+        })
+    }
+
+    /// Simulates `season` on another thread and shows its progress until the user quits.
+    pub fn run(mut self, season: Season, sims: u64, include_decided: bool) -> Result<(), Error> {
+        let (sender, receiver) = mpsc::channel::<Update>();
+        let progress: DashboardProgress = DashboardProgress {
+            sender: sender.clone(),
+        };
+        let mut season: Season = season.with_progress(progress);
+        thread::spawn(move || {
+            let update: Update = match season.simulate(sims, include_decided) {
+                Ok(_) => Update::Finished,
+                Err(e) => Update::Failed(e),
+            };
+            let _ = sender.send(update);
+        });
+
+        let mut terminal: DefaultTerminal = ratatui::init();
+        let result: Result<(), Error> = self.event_loop(&mut terminal, &receiver);
+        ratatui::restore();
+        result
+    }
+
+    fn event_loop(
+        &mut self,
+        terminal: &mut DefaultTerminal,
+        receiver: &Receiver<Update>,
+    ) -> Result<(), Error> {
+        loop {
+            while let Ok(update) = receiver.try_recv() {
+                match update {
+                    Update::Failed(e) => return Err(e),
+                    update => self.apply(update),
+                }
+            }
+            terminal.draw(|frame| self.draw(frame))?;
+            if !event::poll(Duration::from_millis(200))? {
+                continue;
+            }
+            if let Event::Key(key) = event::read()? {
+                if key.kind != KeyEventKind::Press {
+                    continue;
+                }
+                match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                    KeyCode::Down | KeyCode::Char('j') => self.select(1),
+                    KeyCode::Up | KeyCode::Char('k') => self.select(-1),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    fn apply(&mut self, update: Update) {
+        match update {
+            Update::GameStarted { index, total_games } => {
+                self.progress = Some((index, total_games))
+            }
+            Update::Baseline(odds) => self.baseline = odds,
+            Update::Scenario {
+                game_id,
+                make_playoffs,
+            } => self
+                .scenarios
+                .entry(game_id)
+                .or_default()
+                .push(make_playoffs),
+            Update::Finished => self.finished = true,
+            Update::Failed(_) => {}
+        }
+    }
+
+    fn select(&mut self, step: isize) {
+        let rows: isize = self.standings.len() as isize;
+        let selected: isize = self.table_state.selected().unwrap_or(0) as isize;
+        self.table_state
+            .select(Some((selected + step).rem_euclid(rows.max(1)) as usize));
+    }
+
+    fn selected_team(&self) -> Option<&Standing> {
+        self.standings.get(self.table_state.selected().unwrap_or(0))
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [header, body] =
+            Layout::vertical([Constraint::Length(3), Constraint::Min(0)]).areas(frame.area());
+        let [left, right] =
+            Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)])
+                .areas(body);
+        let [seeds, swings] =
+            Layout::vertical([Constraint::Length(12), Constraint::Min(0)]).areas(right);
+
+        self.draw_progress(frame, header);
+        self.draw_standings(frame, left);
+        self.draw_seeds(frame, seeds);
+        self.draw_swings(frame, swings);
+    }
+
+    fn draw_progress(&self, frame: &mut Frame, area: Rect) {
+        let (ratio, label): (f64, String) = match (self.finished, self.progress) {
+            (true, _) => (1.0, String::from("Done")),
+            (false, Some((index, total_games))) => (
+                (index - 1) as f64 / total_games.max(1) as f64,
+                format!("Game {} of {}", index, total_games),
+            ),
+            (false, None) => (0.0, String::from("Simulating current state")),
+        };
+        let gauge: Gauge = Gauge::default()
+            .block(Block::bordered().title(" Simulation (q to quit) "))
+            .ratio(ratio)
+            .label(label);
+        frame.render_widget(gauge, area);
+    }
+
+    fn draw_standings(&mut self, frame: &mut Frame, area: Rect) {
+        let rows: Vec<Row> = self
+            .standings
+            .iter()
+            .map(|standing| {
+                let (wins, losses, ties) = standing.overall_record;
+                let mut cells: Vec<String> = vec![
+                    standing.division.clone(),
+                    standing.abbreviation.clone(),
+                    format!("{}-{}-{}", wins, losses, ties),
+                ];
+                cells.extend(match self.baseline.get(&standing.team_id) {
+                    Some(odds) => vec![
+                        percent(odds.make_playoffs),
+                        percent(odds.win_division),
+                        percent(odds.first_round_bye),
+                        format!("{:.1}", odds.expected_wins),
+                    ],
+                    None => vec![String::from("-"); 4],
+                });
+                Row::new(cells)
+            })
+            .collect();
+        let table: Table = Table::new(
+            rows,
+            [
+                Constraint::Length(12),
+                Constraint::Length(5),
+                Constraint::Length(8),
+                Constraint::Length(9),
+                Constraint::Length(9),
+                Constraint::Length(7),
+                Constraint::Length(6),
+            ],
+        )
+        .header(
+            Row::new([
+                "Division", "Team", "Record", "Playoffs", "Division", "Bye", "Wins",
+            ])
+            .style(Style::new().add_modifier(Modifier::BOLD)),
+        )
+        .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+        .block(Block::bordered().title(" Standings and odds "));
+        frame.render_stateful_widget(table, area, &mut self.table_state);
+    }
+
+    fn draw_seeds(&self, frame: &mut Frame, area: Rect) {
+        let standing: &Standing = match self.selected_team() {
+            Some(standing) => standing,
+            None => return,
+        };
+        let odds: Option<&TeamOdds> = self.baseline.get(&standing.team_id);
+        let mut bars: Vec<Bar> = Vec::new();
+        for seed in 1..=7_u8 {
+            let chance: f64 =
+                odds.map_or(0.0, |odds| *odds.playoff_seeds.get(&seed).unwrap_or(&0.0));
+            bars.push(bar(seed.to_string(), chance));
+        }
+        bars.push(bar(
+            String::from("Out"),
+            odds.map_or(0.0, |odds| 1.0 - odds.make_playoffs),
+        ));
+        let chart: BarChart = BarChart::default()
+            .block(Block::bordered().title(format!(" {} seeds ", standing.abbreviation)))
+            .data(BarGroup::default().bars(&bars))
+            .bar_width(4)
+            .bar_gap(1)
+            .max(100);
+        frame.render_widget(chart, area);
+    }
+
+    fn draw_swings(&self, frame: &mut Frame, area: Rect) {
+        let standing: &Standing = match self.selected_team() {
+            Some(standing) => standing,
+            None => return,
+        };
+        let mut swings: Vec<(i32, f64, f64)> = self
+            .scenarios
+            .iter()
+            .filter_map(|(game_id, outcomes)| {
+                let chances: Vec<f64> = outcomes
+                    .iter()
+                    .filter_map(|make_playoffs| make_playoffs.get(&standing.team_id).copied())
+                    .collect();
+                let low: f64 = chances.iter().copied().reduce(f64::min)?;
+                let high: f64 = chances.iter().copied().reduce(f64::max)?;
+                Some((*game_id, low, high))
+            })
+            .collect();
+        swings.sort_by(|a, b| (b.2 - b.1).total_cmp(&(a.2 - a.1)).then(a.0.cmp(&b.0)));
+
+        let rows: Vec<Row> = swings
+            .iter()
+            .take(SWING_ROWS)
+            .map(|(game_id, low, high)| {
+                let matchup: String = match self.games.get(game_id) {
+                    Some(game) => format!(
+                        "Wk {} {} @ {}",
+                        game.week, game.away_team.abbreviation, game.home_team.abbreviation
+                    ),
+                    None => game_id.to_string(),
+                };
+                Row::new([
+                    matchup,
+                    format!("{} - {}", percent(*low), percent(*high)),
+                    percent(high - low),
+                ])
+            })
+            .collect();
+        let table: Table = Table::new(
+            rows,
+            [
+                Constraint::Length(16),
+                Constraint::Length(17),
+                Constraint::Length(8),
+            ],
+        )
+        .header(
+            Row::new(["Game", "Playoffs", "Swing"])
+                .style(Style::new().add_modifier(Modifier::BOLD)),
+        )
+        .block(Block::bordered().title(format!(
+            " Biggest swings for {} so far ",
+            standing.abbreviation
+        )));
+        frame.render_widget(table, area);
+    }
+}
+
+fn bar(label: String, chance: f64) -> Bar<'static> {
+    Bar::default()
+        .label(label.into())
+        .value((chance * 100.0).round() as u64)
+        .text_value(format!("{:.0}%", chance * 100.0))
+}
+
+fn percent(chance: f64) -> String {
+    format!("{:.1}%", chance * 100.0)
+}
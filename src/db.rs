@@ -0,0 +1,64 @@
+use crate::{execute_with_client, get_conn_string, run_query_with_client, Error};
+use postgres::types::ToSql;
+use postgres::{NoTls, Row};
+use r2d2::{Pool, PooledConnection};
+use r2d2_postgres::PostgresConnectionManager;
+use std::sync::OnceLock;
+
+/// Connection checked out of a `Db` pool. Dereferences to `postgres::Client`, so it can be
+/// passed to any of the `_with_client` functions.
+pub type PooledClient = PooledConnection<PostgresConnectionManager<NoTls>>;
+
+const DEFAULT_POOL_SIZE: u32 = 4;
+
+static SHARED_DB: OnceLock<Db> = OnceLock::new();
+
+/// Pool of Postgres connections. Cloning is cheap and shares the same pool.
+#[derive(Clone, Debug)]
+pub struct Db {
+    pool: Pool<PostgresConnectionManager<NoTls>>,
+}
+
+impl Db {
+    pub fn new(conn_string: &str, max_size: u32) -> Result<Db, Error> {
+        let manager: PostgresConnectionManager<NoTls> =
+            PostgresConnectionManager::new(conn_string.parse()?, NoTls);
+        let pool: Pool<PostgresConnectionManager<NoTls>> =
+            Pool::builder().max_size(max_size).build(manager)?;
+        Ok(Db { pool })
+    }
+
+    /// Pool for the `PG_*` environment variables.
+    pub fn from_env() -> Result<Db, Error> {
+        Db::new(&get_conn_string()?, DEFAULT_POOL_SIZE)
+    }
+
+    /// Process-wide pool used by `run_query`, `execute` and the functions without a
+    /// `_with_client` or `_with_db` suffix. Created from the environment on first use.
+    pub fn shared() -> Result<&'static Db, Error> {
+        if let Some(db) = SHARED_DB.get() {
+            return Ok(db);
+        }
+        let db: Db = Db::from_env()?;
+        Ok(SHARED_DB.get_or_init(|| db))
+    }
+
+    pub fn get(&self) -> Result<PooledClient, Error> {
+        Ok(self.pool.get()?)
+    }
+
+    pub fn run_query(&self, query: String) -> Result<Vec<Row>, Error> {
+        let mut client: PooledClient = self.get()?;
+        run_query_with_client(&mut client, query)
+    }
+
+    pub fn query(&self, query: &str, params: &[&(dyn ToSql + Sync)]) -> Result<Vec<Row>, Error> {
+        let mut client: PooledClient = self.get()?;
+        Ok(client.query(query, params)?)
+    }
+
+    pub fn execute(&self, statement: String) -> Result<(), Error> {
+        let mut client: PooledClient = self.get()?;
+        execute_with_client(&mut client, statement)
+    }
+}
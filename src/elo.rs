@@ -0,0 +1,208 @@
+#[cfg(feature = "postgres")]
+use crate::{connect, PooledClient};
+use crate::{Error, Game, GameResult, GameSimulator};
+#[cfg(feature = "postgres")]
+use postgres::{Client, Row};
+use std::collections::HashMap;
+use std::fs;
+
+#[derive(Clone, Debug)]
+pub struct EloModel {
+    pub ratings: HashMap<i32, f64>,
+    pub initial_rating: f64,
+    pub k_factor: f64,
+    /// Rating points added to the home team when computing win probabilities, except at a
+    /// neutral site.
+    pub home_field_advantage: f64,
+    /// Fraction of each rating's distance from `initial_rating` removed between seasons.
+    pub regression_to_mean: f64,
+    /// Update ratings after each simulated game, so later games in the same simulation see the
+    /// new ratings. Every simulation starts again from the loaded ratings.
+    pub update_in_simulation: bool,
+}
+
+impl Default for EloModel {
+    fn default() -> EloModel {
+        EloModel {
+            ratings: HashMap::new(),
+            initial_rating: 1505.0,
+            k_factor: 20.0,
+            home_field_advantage: 48.0,
+            regression_to_mean: 1.0 / 3.0,
+            update_in_simulation: true,
+        }
+    }
+}
+
+impl EloModel {
+    /// Starts every team at `initial_rating` and plays through `games` in order, regressing
+    /// ratings toward the mean whenever the season changes.
+    pub fn from_historical(games: &[Game]) -> EloModel {
+        let mut model: EloModel = EloModel::default();
+        model.update_from_games(games);
+        model
+    }
+
+    #[cfg(feature = "postgres")]
+    pub fn load_ratings(season_year: i32) -> Result<EloModel, Error> {
+        let mut client: PooledClient = connect()?;
+        EloModel::load_ratings_with_client(season_year, &mut client)
+    }
+
+    /// Preseason ratings for `season_year` from `nfl.elo_ratings`. Teams without a row start
+    /// at `initial_rating`.
+    #[cfg(feature = "postgres")]
+    pub fn load_ratings_with_client(
+        season_year: i32,
+        client: &mut Client,
+    ) -> Result<EloModel, Error> {
+        let rows: Vec<Row> = client.query(
+            "
+            SELECT
+                team_id,
+                rating
+            FROM nfl.elo_ratings
+            WHERE season=$1;
+        ",
+            &[&season_year],
+        )?;
+        let mut model: EloModel = EloModel::default();
+        for row in rows {
+            model.ratings.insert(row.try_get(0)?, row.try_get(1)?);
+        }
+        Ok(model)
+    }
+
+    /// Ratings from a file of `team_id,rating` lines. Blank lines, lines starting with `#`
+    /// and a `team_id,rating` header are skipped.
+    pub fn from_ratings_file(path: &str) -> Result<EloModel, Error> {
+        let contents: String = fs::read_to_string(path)?;
+        let mut model: EloModel = EloModel::default();
+        for line in contents.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') || line == "team_id,rating" {
+                continue;
+            }
+            let parsed: Option<(i32, f64)> = line.split_once(',').and_then(|(team_id, rating)| {
+                Some((team_id.trim().parse().ok()?, rating.trim().parse().ok()?))
+            });
+            match parsed {
+                Some((team_id, rating)) => {
+                    model.ratings.insert(team_id, rating);
+                }
+                None => {
+                    return Err(Error::InvalidData(format!(
+                        "invalid rating line in {}: {}",
+                        path, line
+                    )))
+                }
+            }
+        }
+        Ok(model)
+    }
+
+    pub fn update_from_games(&mut self, games: &[Game]) {
+        let mut ordered_games: Vec<&Game> = games.iter().collect();
+        ordered_games.sort_by_key(|game| (game.season_year, game.week, game.game_id));
+
+        let mut current_season: Option<i32> = None;
+        for game in ordered_games {
+            if game.has_tbd_team() || game.game_result.is_none() {
+                continue;
+            }
+            match current_season {
+                Some(season_year) if season_year != game.season_year => {
+                    self.regress_to_mean();
+                }
+                _ => {}
+            }
+            current_season = Some(game.season_year);
+            self.update(game);
+        }
+    }
+
+    /// Preseason adjustment: moves every rating `regression_to_mean` of the way back toward
+    /// `initial_rating`.
+    pub fn regress_to_mean(&mut self) {
+        for rating in self.ratings.values_mut() {
+            *rating += (self.initial_rating - *rating) * self.regression_to_mean;
+        }
+    }
+
+    pub fn rating(&self, team_id: i32) -> f64 {
+        *self.ratings.get(&team_id).unwrap_or(&self.initial_rating)
+    }
+
+    pub fn win_probability(&self, home_team_id: i32, away_team_id: i32) -> f64 {
+        self.win_probability_with_advantage(home_team_id, away_team_id, self.home_field_advantage)
+    }
+
+    fn win_probability_with_advantage(
+        &self,
+        home_team_id: i32,
+        away_team_id: i32,
+        home_field_advantage: f64,
+    ) -> f64 {
+        let rating_difference: f64 =
+            self.rating(home_team_id) + home_field_advantage - self.rating(away_team_id);
+        1.0 / (1.0 + 10_f64.powf(-rating_difference / 400.0))
+    }
+
+    fn game_home_field_advantage(&self, game: &Game) -> f64 {
+        match game.neutral_site {
+            true => 0.0,
+            false => self.home_field_advantage,
+        }
+    }
+
+    /// Applies a decided game. When scores are known, the change is scaled by the margin of
+    /// victory, damped for favorites so that ratings don't run away.
+    pub fn update(&mut self, game: &Game) {
+        let home_team_id: i32 = game.home_team.team_id;
+        let away_team_id: i32 = game.away_team.team_id;
+        let home_actual: f64 = match game.game_result {
+            Some(GameResult::HomeWin) => 1.0,
+            Some(GameResult::AwayWin) => 0.0,
+            Some(GameResult::Tie) => 0.5,
+            None => return,
+        };
+        let home_field_advantage: f64 = self.game_home_field_advantage(game);
+        let home_expected: f64 =
+            self.win_probability_with_advantage(home_team_id, away_team_id, home_field_advantage);
+
+        let margin_multiplier: f64 = match (game.home_score, game.away_score) {
+            (Some(home_score), Some(away_score)) => {
+                let margin: f64 = f64::from((home_score - away_score).abs().max(1));
+                let winner_rating_difference: f64 = match game.game_result {
+                    Some(GameResult::AwayWin) => {
+                        self.rating(away_team_id) - self.rating(home_team_id) - home_field_advantage
+                    }
+                    _ => {
+                        self.rating(home_team_id) + home_field_advantage - self.rating(away_team_id)
+                    }
+                };
+                (margin + 1.0).ln() * 2.2 / (winner_rating_difference * 0.001 + 2.2)
+            }
+            _ => 1.0,
+        };
+
+        let shift: f64 = self.k_factor * margin_multiplier * (home_actual - home_expected);
+        let home_rating: f64 = self.rating(home_team_id) + shift;
+        let away_rating: f64 = self.rating(away_team_id) - shift;
+        self.ratings.insert(home_team_id, home_rating);
+        self.ratings.insert(away_team_id, away_rating);
+    }
+}
+
+impl GameSimulator for EloModel {
+    fn win_prob(&self, game: &Game) -> f64 {
+        self.win_probability_with_advantage(
+            game.home_team.team_id,
+            game.away_team.team_id,
+            self.game_home_field_advantage(game),
+        )
+    }
+
+    fn includes_home_field(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,106 @@
+use std::fmt;
+use std::io;
+
+/// Errors from loading and storing seasons, results and model inputs.
+#[derive(Debug)]
+pub enum Error {
+    /// A required environment variable is not set.
+    MissingVariable(String),
+    #[cfg(feature = "postgres")]
+    Database(postgres::Error),
+    #[cfg(feature = "postgres")]
+    Pool(r2d2::Error),
+    #[cfg(feature = "sqlite")]
+    Sqlite(rusqlite::Error),
+    Io(io::Error),
+    /// Stored or file data that doesn't fit the season, such as an unknown game or a malformed
+    /// line.
+    InvalidData(String),
+    /// A call that the season's configuration or state doesn't allow.
+    InvalidArgument(String),
+    /// Odds from two runs that should agree differ by more than chance allows, one line per
+    /// team and outcome.
+    Inconsistent(Vec<String>),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::MissingVariable(key) => write!(f, "environment variable {} is not set", key),
+            #[cfg(feature = "postgres")]
+            Error::Database(e) => write!(f, "database error: {}", e),
+            #[cfg(feature = "postgres")]
+            Error::Pool(e) => write!(f, "connection pool error: {}", e),
+            #[cfg(feature = "sqlite")]
+            Error::Sqlite(e) => write!(f, "SQLite error: {}", e),
+            Error::Io(e) => write!(f, "I/O error: {}", e),
+            Error::InvalidData(message) => write!(f, "invalid data: {}", message),
+            Error::InvalidArgument(message) => write!(f, "invalid argument: {}", message),
+            Error::Inconsistent(discrepancies) => {
+                write!(f, "inconsistent results: {}", discrepancies.join("; "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            #[cfg(feature = "postgres")]
+            Error::Database(e) => Some(e),
+            #[cfg(feature = "postgres")]
+            Error::Pool(e) => Some(e),
+            #[cfg(feature = "sqlite")]
+            Error::Sqlite(e) => Some(e),
+            Error::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl From<postgres::Error> for Error {
+    fn from(e: postgres::Error) -> Error {
+        Error::Database(e)
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl From<r2d2::Error> for Error {
+    fn from(e: r2d2::Error) -> Error {
+        Error::Pool(e)
+    }
+}
+
+impl From<csv::Error> for Error {
+    fn from(e: csv::Error) -> Error {
+        let message: String = e.to_string();
+        match e.into_kind() {
+            csv::ErrorKind::Io(e) => Error::Io(e),
+            _ => Error::InvalidData(message),
+        }
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl From<rusqlite::Error> for Error {
+    fn from(e: rusqlite::Error) -> Error {
+        Error::Sqlite(e)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Error {
+        match e.io_error_kind() {
+            Some(kind) => Error::Io(io::Error::new(kind, e)),
+            None => Error::InvalidData(e.to_string()),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Error {
+        Error::Io(e)
+    }
+}
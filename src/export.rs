@@ -0,0 +1,286 @@
+use crate::{
+    standard_error, Error, PlayoffRound, SimulationReport, SimulationResultLookup, TeamOdds,
+    TeamSimulationResults,
+};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::fs::File;
+use std::hash::Hash;
+use std::io::{self, BufWriter, Write};
+
+/// Writes a report's results to files, for consumers without access to the database.
+pub struct ResultWriter<'a> {
+    report: &'a SimulationReport,
+}
+
+impl<'a> ResultWriter<'a> {
+    pub fn new(report: &'a SimulationReport) -> ResultWriter<'a> {
+        ResultWriter { report }
+    }
+
+    pub fn write_json_file(&self, path: &str) -> Result<(), Error> {
+        let mut writer: BufWriter<File> = BufWriter::new(File::create(path)?);
+        self.write_json(&mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// One JSON object with the report's metadata, a `teams` array of baseline odds per team,
+    /// and a `results` array with the raw counts of every team in every scenario. Scenario
+    /// fields are `null` for the baseline.
+    pub fn write_json<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let report: &SimulationReport = self.report;
+        write!(
+            writer,
+            "{{\"season\":{},\"simulation_id\":{},\"seed\":{},\"complete\":{},\"teams\":[",
+            report.season_year,
+            json_or_null(report.simulation_id),
+            json_or_null(report.seed),
+            report.complete,
+        )?;
+
+        let mut team_ids: Vec<i32> = report.teams.keys().copied().collect();
+        team_ids.sort();
+        let mut first: bool = true;
+        for team_id in team_ids.iter() {
+            let odds: TeamOdds = match report.team_odds(*team_id) {
+                Some(odds) => odds,
+                None => continue,
+            };
+            let baseline: Option<&TeamSimulationResults> = report
+                .results
+                .get(&SimulationResultLookup::new(team_id, None));
+            let playoff_opponents: HashMap<PlayoffRound, String> = baseline
+                .map(|results| {
+                    results
+                        .playoff_opponents
+                        .iter()
+                        .map(|(round, opponents)| {
+                            let opponents: HashMap<String, f64> = opponents
+                                .iter()
+                                .map(|(opponent_id, count)| {
+                                    (
+                                        self.abbreviation(*opponent_id),
+                                        f64::from(*count) / f64::from(results.simulations),
+                                    )
+                                })
+                                .collect();
+                            (*round, json_object(&opponents))
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            if !first {
+                write!(writer, ",")?;
+            }
+            first = false;
+            write!(
+                writer,
+                "{{\"team_id\":{},\"team\":{},\"simulations\":{},\"make_playoffs\":{},\
+                 \"make_playoffs_se\":{},\"win_division\":{},\"win_division_se\":{},\
+                 \"wildcard\":{},\"first_round_bye\":{},\
+                 \"playoff_seeds\":{},\
+                 \"draft_positions\":{},\"expected_draft_position\":{},\"expected_wins\":{},\"win_totals\":{},\
+                 \"playoff_rounds\":{},\"played_strength_of_schedule\":{},\
+                 \"remaining_strength_of_schedule\":{},\"coin_toss\":{},\
+                 \"host_playoff_game\":{},\"playoff_opponents\":{}}}",
+                team_id,
+                json_string(&self.abbreviation(*team_id)),
+                odds.simulations,
+                odds.make_playoffs,
+                odds.make_playoffs_se,
+                odds.win_division,
+                odds.win_division_se,
+                odds.wildcard,
+                odds.first_round_bye,
+                json_object(&odds.playoff_seeds),
+                json_object(&odds.draft_positions),
+                json_or_null(odds.expected_draft_position),
+                odds.expected_wins,
+                json_object(&odds.win_totals),
+                json_object(&odds.playoff_rounds),
+                json_or_null(odds.played_strength_of_schedule),
+                json_or_null(odds.remaining_strength_of_schedule),
+                odds.coin_toss,
+                odds.host_playoff_game,
+                json_object(&playoff_opponents),
+            )?;
+        }
+
+        write!(writer, "],\"results\":[")?;
+        for (i, lookup) in self.sorted_lookups().into_iter().enumerate() {
+            let results: &TeamSimulationResults = report.results.get(lookup).unwrap();
+            if i > 0 {
+                write!(writer, ",")?;
+            }
+            write!(
+                writer,
+                "{{\"team_id\":{},\"team\":{},\"game_id\":{},\"forced_result\":{},\
+                 \"simulations\":{},\"made_playoffs\":{},\"division_winner\":{},\
+                 \"wildcard_team\":{},\"total_wins\":{},\"playoff_seedings\":{},\
+                 \"draft_positions\":{},\"win_totals\":{},\"playoff_round_wins\":{}}}",
+                lookup.team_id,
+                json_string(&self.abbreviation(lookup.team_id)),
+                json_or_null(lookup.game_id),
+                json_or_null(
+                    lookup
+                        .game_result
+                        .as_ref()
+                        .map(|gr| json_string(&gr.to_string()))
+                ),
+                results.simulations,
+                results.made_playoffs,
+                results.division_winner,
+                results.wildcard_team,
+                results.total_wins,
+                json_object(&results.playoff_seedings),
+                json_object(&results.draft_positions),
+                json_object(&results.win_totals),
+                json_object(&results.playoff_round_wins),
+            )?;
+        }
+        writeln!(writer, "]}}")
+    }
+
+    pub fn write_csv_file(&self, path: &str) -> Result<(), Error> {
+        let mut writer: csv::Writer<File> = csv::Writer::from_path(path)?;
+        self.write_csv_records(&mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// One row per team, scenario and outcome, with columns `team`, `scenario_game_id`,
+    /// `forced_result`, `outcome`, `probability` and its Monte Carlo `standard_error`. The
+    /// scenario columns are empty for the baseline. Outcomes are `make playoffs`, `win division`, `wildcard`, `first-round bye`,
+    /// `playoff seed N`, `draft position N`, `N wins` and `win <round>` for each playoff round.
+    pub fn write_csv<W: Write>(&self, writer: W) -> Result<(), Error> {
+        let mut writer: csv::Writer<W> = csv::Writer::from_writer(writer);
+        self.write_csv_records(&mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    fn write_csv_records<W: Write>(&self, writer: &mut csv::Writer<W>) -> Result<(), Error> {
+        writer.write_record([
+            "team",
+            "scenario_game_id",
+            "forced_result",
+            "outcome",
+            "probability",
+            "standard_error",
+        ])?;
+        for lookup in self.sorted_lookups() {
+            let results: &TeamSimulationResults = self.report.results.get(lookup).unwrap();
+            let team: String = self.abbreviation(lookup.team_id);
+            let game_id: String = match lookup.game_id {
+                Some(game_id) => game_id.to_string(),
+                None => String::new(),
+            };
+            let forced_result: String = match &lookup.game_result {
+                Some(game_result) => game_result.to_string(),
+                None => String::new(),
+            };
+            let mut outcomes: Vec<(String, i32)> = vec![
+                (String::from("make playoffs"), results.made_playoffs),
+                (String::from("win division"), results.division_winner),
+                (String::from("wildcard"), results.wildcard_team),
+                (
+                    String::from("first-round bye"),
+                    *results.playoff_seedings.get(&1).unwrap_or(&0),
+                ),
+            ];
+            outcomes.extend(
+                sorted_counts(&results.playoff_seedings)
+                    .map(|(seed, count)| (format!("playoff seed {}", seed), count)),
+            );
+            outcomes.extend(
+                sorted_counts(&results.draft_positions)
+                    .map(|(position, count)| (format!("draft position {}", position), count)),
+            );
+            outcomes.extend(
+                sorted_counts(&results.win_totals)
+                    .map(|(wins, count)| (format!("{} wins", wins), count)),
+            );
+            outcomes.extend(PlayoffRound::ALL.iter().map(|round| {
+                let count: i32 = *results.playoff_round_wins.get(round).unwrap_or(&0);
+                (format!("win {}", round), count)
+            }));
+
+            for (outcome, count) in outcomes {
+                let probability: f64 = match results.simulations {
+                    0 => 0.0,
+                    s => f64::from(count) / f64::from(s),
+                };
+                writer.write_record([
+                    team.as_str(),
+                    game_id.as_str(),
+                    forced_result.as_str(),
+                    outcome.as_str(),
+                    probability.to_string().as_str(),
+                    standard_error(probability, results.simulations)
+                        .to_string()
+                        .as_str(),
+                ])?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Baseline first, then each scenario by game and result, with teams in id order.
+    fn sorted_lookups(&self) -> Vec<&SimulationResultLookup> {
+        let mut lookups: Vec<&SimulationResultLookup> = self.report.results.keys().collect();
+        lookups.sort_by_key(|lookup| {
+            (
+                lookup.game_id,
+                lookup.game_result.as_ref().map(|gr| gr.to_string()),
+                lookup.team_id,
+            )
+        });
+        lookups
+    }
+
+    fn abbreviation(&self, team_id: i32) -> String {
+        match self.report.teams.get(&team_id) {
+            Some(team) => team.abbreviation.clone(),
+            None => team_id.to_string(),
+        }
+    }
+}
+
+fn sorted_counts(counts: &HashMap<u8, i32>) -> impl Iterator<Item = (u8, i32)> {
+    let mut counts: Vec<(u8, i32)> = counts.iter().map(|(k, v)| (*k, *v)).collect();
+    counts.sort();
+    counts.into_iter()
+}
+
+fn json_or_null<T: ToString>(value: Option<T>) -> String {
+    match value {
+        Some(value) => value.to_string(),
+        None => String::from("null"),
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut escaped: String = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", u32::from(c))),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+/// Object with the map's keys as strings, in key order.
+fn json_object<K: Display + Ord + Hash, V: Display>(map: &HashMap<K, V>) -> String {
+    let mut entries: Vec<(&K, &V)> = map.iter().collect();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+    let fields: Vec<String> = entries
+        .into_iter()
+        .map(|(key, value)| format!("{}:{}", json_string(&key.to_string()), value))
+        .collect();
+    format!("{{{}}}", fields.join(","))
+}
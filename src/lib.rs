@@ -1,83 +1,424 @@
-use chrono;
-use postgres::{Client, NoTls, Row};
-use rand::Rng;
-use std::collections::{HashMap, HashSet};
+#[cfg(feature = "postgres")]
+use postgres::types::ToSql;
+#[cfg(feature = "postgres")]
+use postgres::{Client, Row};
+use rand::rngs::StdRng;
+use rand::{Rng, RngCore, SeedableRng};
+use rayon::prelude::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::convert::TryFrom;
+#[cfg(feature = "postgres")]
 use std::env::var;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Range;
+use std::str::FromStr;
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+use tracing::{debug, info_span};
+
+mod bracket;
+#[cfg(feature = "serde")]
+mod checkpoint;
+#[cfg(feature = "postgres")]
+mod db;
+mod elo;
+mod error;
+mod export;
+mod odds;
+mod progress;
+#[cfg(feature = "postgres")]
+mod queue;
+mod report;
+#[cfg(feature = "scenarios")]
+mod scenario;
+mod score;
+#[cfg(feature = "server")]
+pub mod server;
+mod sink;
+mod source;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+mod standings;
+
+pub use bracket::{PlayoffBracket, PlayoffFormat, PlayoffMatchup, PlayoffRound};
+#[cfg(feature = "postgres")]
+pub use db::{Db, PooledClient};
+pub use elo::EloModel;
+pub use error::Error;
+pub use export::ResultWriter;
+pub use odds::{moneyline_probability, spread_probability, MarketOdds};
+pub use progress::{ProgressSink, ProgressSinkClone, StdoutProgress, TracingProgress};
+#[cfg(feature = "postgres")]
+pub use queue::{queue_status, QueueStatus};
+pub use report::{
+    standard_error, BatchOdds, ConditionalOdds, ConditionalReport, DivisionStrength, GameLeverage,
+    LeverageTarget, ResultRecord, RootingInterest, SimulationReport, TeamOdds, CONFIDENCE_Z,
+};
+#[cfg(feature = "scenarios")]
+pub use scenario::{ForcedResult, ScenarioFile, TeamOutcome};
+pub use score::ScoreModel;
+#[cfg(feature = "postgres")]
+pub use sink::PostgresResultSink;
+pub use sink::{CsvResultSink, JsonResultSink, ResultSink, RunInfo, StdoutResultSink};
+pub use source::{CsvDataSource, DataSource, ScheduledGame};
+#[cfg(feature = "sqlite")]
+pub use sqlite::{SqliteDataSource, SqliteResultSink};
+pub use standings::{compute_standings, Standing, Standings, StandingsTheme, TeamSummary};
+
+/// Random source owned by a `Season`. Implemented for any cloneable `RngCore`, so seeded,
+/// stepped or scripted generators can be injected with `Season::with_rng`.
+pub trait SimulationRng: RngCore + Send + fmt::Debug {
+    fn box_clone(&self) -> Box<dyn SimulationRng>;
+}
+
+impl<R: RngCore + Clone + Send + fmt::Debug + 'static> SimulationRng for R {
+    fn box_clone(&self) -> Box<dyn SimulationRng> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn SimulationRng> {
+    fn clone(&self) -> Box<dyn SimulationRng> {
+        (**self).box_clone()
+    }
+}
+
+/// Win-probability model for simulated games, plugged in with `Season::with_game_simulator`.
+/// Any cloneable implementation works; `CoinFlip`, `HomeField`, `EloModel` and `MarketOdds`
+/// are provided.
+pub trait GameSimulator: GameSimulatorClone + Send + fmt::Debug {
+    /// Home team's chance of winning, given the game isn't a tie.
+    fn win_prob(&self, game: &Game) -> f64;
+
+    /// Whether `win_prob` already favors the home team, so that
+    /// `Season::home_win_probability` doesn't add a home-field advantage of its own on top.
+    fn includes_home_field(&self) -> bool {
+        false
+    }
+}
+
+pub trait GameSimulatorClone {
+    fn box_clone(&self) -> Box<dyn GameSimulator>;
+}
+
+impl<G: GameSimulator + Clone + 'static> GameSimulatorClone for G {
+    fn box_clone(&self) -> Box<dyn GameSimulator> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn GameSimulator> {
+    fn clone(&self) -> Box<dyn GameSimulator> {
+        (**self).box_clone()
+    }
+}
+
+/// Even odds for every game.
+#[derive(Clone, Debug, Default)]
+pub struct CoinFlip;
+
+impl GameSimulator for CoinFlip {
+    fn win_prob(&self, _game: &Game) -> f64 {
+        0.5
+    }
+}
+
+/// Fixed chance of the home team winning, used when no other model is configured.
+/// Neutral-site games are even, and games in `game_overrides` use their own probability.
+#[derive(Clone, Debug)]
+pub struct HomeField {
+    pub home_win_probability: f64,
+    /// Home-win probability by game id.
+    pub game_overrides: HashMap<i32, f64>,
+}
+
+impl Default for HomeField {
+    fn default() -> HomeField {
+        HomeField {
+            home_win_probability: DEFAULT_HOME_WIN_PROBABILITY,
+            game_overrides: HashMap::new(),
+        }
+    }
+}
+
+impl HomeField {
+    pub fn new(home_win_probability: f64) -> HomeField {
+        HomeField {
+            home_win_probability,
+            ..HomeField::default()
+        }
+    }
+
+    /// Plays `game_id` with even odds, as if it were at a neutral site.
+    pub fn with_neutral_site(mut self, game_id: i32) -> HomeField {
+        self.game_overrides.insert(game_id, 0.5);
+        self
+    }
+}
+
+impl GameSimulator for HomeField {
+    fn win_prob(&self, game: &Game) -> f64 {
+        match (self.game_overrides.get(&game.game_id), game.neutral_site) {
+            (Some(probability), _) => *probability,
+            (None, true) => 0.5,
+            (None, false) => self.home_win_probability,
+        }
+    }
+
+    fn includes_home_field(&self) -> bool {
+        true
+    }
+}
+
+pub const TBD_TEAM_ID: i32 = 0;
+pub const TBD_TEAM_ABBREVIATION: &str = "TBD";
+/// Game id of the one-off games built to price playoff matchups. Stored games can't use it,
+/// so it never picks up a per-game override or market line.
+pub const PLAYOFF_GAME_ID: i32 = i32::MIN;
+/// Most undecided games `SimulationMode::Exact` will enumerate, for 2^24 combinations.
+pub const MAX_EXACT_GAMES: usize = 24;
+/// Home teams' share of regular season wins historically, ignoring ties.
+pub const DEFAULT_HOME_WIN_PROBABILITY: f64 = 0.55;
+
+#[cfg(feature = "postgres")]
+const SIMULATED_GAMES_BATCH_SIZE: usize = 500;
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Team {
     pub team_id: i32,
     pub abbreviation: String,
     pub name: String,
     pub conference: String,
     pub division: String,
+    /// Stable identity across relocations and renames, from `nfl.franchises`.
+    pub franchise_id: Option<i32>,
 }
 
 impl Team {
-    pub fn new_from_db_row(row: Row) -> Team {
+    #[cfg(feature = "postgres")]
+    pub fn new_from_db_row(row: Row) -> Result<Team, Error> {
         let team: Team = Team {
-            team_id: row.get(0),
-            abbreviation: row.get(1),
-            name: row.get(2),
-            conference: row.get(3),
-            division: row.get(4),
+            team_id: row.try_get(0)?,
+            abbreviation: row.try_get(1)?,
+            name: row.try_get(2)?,
+            conference: row.try_get(3)?,
+            division: row.try_get(4)?,
+            franchise_id: row.try_get(5)?,
         };
-        team
+        Ok(team)
+    }
+
+    /// The team's franchise, or its own team id when it has no franchise mapping.
+    pub fn franchise_id(&self) -> i32 {
+        self.franchise_id.unwrap_or(self.team_id)
+    }
+
+    /// Placeholder for an opponent that is not known yet. It belongs to no conference or
+    /// division, and games involving it are left out of simulation, records and tiebreakers.
+    pub fn new_tbd(team_id: i32) -> Team {
+        Team {
+            team_id,
+            abbreviation: String::from(TBD_TEAM_ABBREVIATION),
+            name: String::from("To Be Determined"),
+            conference: String::new(),
+            division: String::new(),
+            franchise_id: None,
+        }
+    }
+
+    pub fn is_tbd(&self) -> bool {
+        self.abbreviation == TBD_TEAM_ABBREVIATION && self.conference.is_empty()
+    }
+}
+
+impl fmt::Display for Team {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.is_tbd() {
+            true => write!(f, "{} — {}", self.abbreviation, self.name),
+            false => write!(
+                f,
+                "{} — {} ({})",
+                self.abbreviation, self.name, self.division
+            ),
+        }
     }
 }
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum GameResult {
     HomeWin,
     AwayWin,
     Tie,
 }
 
+impl fmt::Display for GameResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GameResult::HomeWin => write!(f, "home win"),
+            GameResult::AwayWin => write!(f, "away win"),
+            GameResult::Tie => write!(f, "tie"),
+        }
+    }
+}
+
+impl FromStr for GameResult {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<GameResult, String> {
+        match s {
+            "home win" => Ok(GameResult::HomeWin),
+            "away win" => Ok(GameResult::AwayWin),
+            "tie" => Ok(GameResult::Tie),
+            _ => Err(format!("Unknown game result: {s}")),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum ResultSet {
+    PlayoffSeed,
+    DraftPosition,
+    WinTotal,
+    ReachedConferenceChampionship,
+    ReachedSuperBowl,
+    WonSuperBowl,
+}
+
+impl ResultSet {
+    /// Result sets stored from `TeamSimulationResults::playoff_round_wins`, each with a team
+    /// rank of 1.
+    pub const PLAYOFF_ROUNDS: [ResultSet; 3] = [
+        ResultSet::ReachedConferenceChampionship,
+        ResultSet::ReachedSuperBowl,
+        ResultSet::WonSuperBowl,
+    ];
+
+    /// The playoff round whose wins this result set counts.
+    pub fn playoff_round(&self) -> Option<PlayoffRound> {
+        match self {
+            ResultSet::ReachedConferenceChampionship => Some(PlayoffRound::Divisional),
+            ResultSet::ReachedSuperBowl => Some(PlayoffRound::ConferenceChampionship),
+            ResultSet::WonSuperBowl => Some(PlayoffRound::SuperBowl),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ResultSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ResultSet::PlayoffSeed => write!(f, "playoff seed"),
+            ResultSet::DraftPosition => write!(f, "draft position"),
+            ResultSet::WinTotal => write!(f, "win total"),
+            ResultSet::ReachedConferenceChampionship => {
+                write!(f, "reached conference championship")
+            }
+            ResultSet::ReachedSuperBowl => write!(f, "reached super bowl"),
+            ResultSet::WonSuperBowl => write!(f, "won super bowl"),
+        }
+    }
+}
+
+impl FromStr for ResultSet {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<ResultSet, String> {
+        match s {
+            "playoff seed" => Ok(ResultSet::PlayoffSeed),
+            "draft position" => Ok(ResultSet::DraftPosition),
+            "win total" => Ok(ResultSet::WinTotal),
+            "reached conference championship" => Ok(ResultSet::ReachedConferenceChampionship),
+            "reached super bowl" => Ok(ResultSet::ReachedSuperBowl),
+            "won super bowl" => Ok(ResultSet::WonSuperBowl),
+            _ => Err(format!("Unknown result set: {s}")),
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Game {
     pub game_id: i32,
     pub season_year: i32,
     pub week: i32,
     pub division_game: bool,
     pub conference_game: bool,
-    pub home_team: Team,
-    pub away_team: Team,
+    pub home_team: Arc<Team>,
+    pub away_team: Arc<Team>,
     pub game_result: Option<GameResult>,
     pub is_simulated: bool,
+    pub home_score: Option<i32>,
+    pub away_score: Option<i32>,
+    /// Played at a neutral site, so neither team gets home-field advantage.
+    pub neutral_site: bool,
+    /// Touchdowns scored, when the source records them. Simulated games never have them.
+    pub home_touchdowns: Option<i32>,
+    pub away_touchdowns: Option<i32>,
 }
 
 impl Game {
-    pub fn new_from_db_row(row: Row, teams: HashMap<i32, Team>) -> Game {
-        let game_id: i32 = row.get(0);
-        let season_year: i32 = row.get(1);
-        let week: i32 = row.get(2);
-        let home_team_id: i32 = row.get(3);
-        let away_team_id: i32 = row.get(4);
-        let home_score: Option<i32> = row.get(5);
-        let away_score: Option<i32> = row.get(6);
-
-        let home_team: Team = teams
-            .get(&home_team_id)
-            .expect("Team does not exist")
-            .clone();
-        let away_team: Team = teams
-            .get(&away_team_id)
-            .expect("Team does not exist")
-            .clone();
-
-        let division_game: bool = { home_team.division == away_team.division };
-        let conference_game: bool = { home_team.conference == away_team.conference };
-
-        let game_result: Option<GameResult> = {
-            if home_score.is_none() && away_score.is_none() {
-                None
-            } else if home_score.unwrap() > away_score.unwrap() {
-                Some(GameResult::HomeWin)
-            } else if home_score.unwrap() < away_score.unwrap() {
-                Some(GameResult::AwayWin)
-            } else {
-                Some(GameResult::Tie)
+    #[cfg(feature = "postgres")]
+    pub fn new_from_db_row(row: Row, teams: &HashMap<i32, Arc<Team>>) -> Result<Game, Error> {
+        Game::new_from_scheduled(ScheduledGame::new_from_db_row(row)?, teams)
+    }
+
+    /// Resolves a stored game's teams, sharing them with `teams`. Team ids missing from
+    /// `teams` become TBD.
+    pub fn new_from_scheduled(
+        scheduled_game: ScheduledGame,
+        teams: &HashMap<i32, Arc<Team>>,
+    ) -> Result<Game, Error> {
+        let ScheduledGame {
+            game_id,
+            season_year,
+            week,
+            home_team_id,
+            away_team_id,
+            home_score,
+            away_score,
+            neutral_site,
+            home_touchdowns,
+            away_touchdowns,
+        } = scheduled_game;
+
+        if game_id == PLAYOFF_GAME_ID {
+            return Err(Error::InvalidData(format!(
+                "game id {} is reserved for playoff matchups",
+                game_id
+            )));
+        }
+
+        let home_team: Arc<Team> = Game::team_or_tbd(home_team_id, teams);
+        let away_team: Arc<Team> = Game::team_or_tbd(away_team_id, teams);
+
+        if (home_team.is_tbd() || away_team.is_tbd())
+            && (home_score.is_some() || away_score.is_some())
+        {
+            return Err(Error::InvalidData(format!(
+                "game {} has a score but an undetermined team",
+                game_id
+            )));
+        }
+
+        let (division_game, conference_game) = Game::matchup_flags(&home_team, &away_team);
+
+        let game_result: Option<GameResult> = match (home_score, away_score) {
+            (None, None) => None,
+            (Some(home), Some(away)) if home > away => Some(GameResult::HomeWin),
+            (Some(home), Some(away)) if home < away => Some(GameResult::AwayWin),
+            (Some(_), Some(_)) => Some(GameResult::Tie),
+            _ => {
+                return Err(Error::InvalidData(format!(
+                    "game {} has only one team's score",
+                    game_id
+                )))
             }
         };
 
@@ -91,40 +432,384 @@ impl Game {
             away_team,
             game_result,
             is_simulated: false,
+            home_score,
+            away_score,
+            neutral_site,
+            home_touchdowns,
+            away_touchdowns,
         };
 
-        game
+        Ok(game)
+    }
+
+    fn team_or_tbd(team_id: Option<i32>, teams: &HashMap<i32, Arc<Team>>) -> Arc<Team> {
+        match team_id {
+            Some(id) => match teams.get(&id) {
+                Some(team) => Arc::clone(team),
+                None => Arc::new(Team::new_tbd(id)),
+            },
+            None => Arc::new(Team::new_tbd(TBD_TEAM_ID)),
+        }
+    }
+
+    fn matchup_flags(home_team: &Team, away_team: &Team) -> (bool, bool) {
+        if home_team.is_tbd() || away_team.is_tbd() {
+            return (false, false);
+        }
+        (
+            home_team.division == away_team.division,
+            home_team.conference == away_team.conference,
+        )
+    }
+
+    pub fn has_tbd_team(&self) -> bool {
+        self.home_team.is_tbd() || self.away_team.is_tbd()
+    }
+
+    /// Pins the game to `game_result`. A score or touchdown count that disagrees with it is
+    /// dropped so the tiebreakers don't credit points to the wrong team.
+    pub fn force_result(&mut self, game_result: GameResult) {
+        if self.game_result.as_ref() != Some(&game_result) {
+            self.home_score = None;
+            self.away_score = None;
+            self.home_touchdowns = None;
+            self.away_touchdowns = None;
+        }
+        self.game_result = Some(game_result);
     }
 
-    pub fn simulate_if_undecided(&mut self) {
-        if self.game_result.is_none() {
+    pub fn simulate_if_undecided(&mut self, rng: &mut dyn RngCore, home_win_probability: f64) {
+        if self.game_result.is_none() && !self.has_tbd_team() {
             let tie_likelihood: f64 = 0.003421;
 
-            let mut rng: rand::rngs::ThreadRng = rand::thread_rng();
             let tie_predictor: f64 = rng.gen();
             let win_predictor: f64 = rng.gen();
 
             if tie_predictor <= tie_likelihood {
                 self.game_result = Some(GameResult::Tie);
-            } else if win_predictor < 0.5 {
+            } else if win_predictor < home_win_probability {
                 self.game_result = Some(GameResult::HomeWin);
-            } else if win_predictor >= 0.5 {
+            } else {
                 self.game_result = Some(GameResult::AwayWin);
             };
 
             self.is_simulated = true;
         }
     }
+
+    /// `simulate_if_undecided`, then a final score for the result from `score_model`.
+    pub fn simulate_with_score_if_undecided(
+        &mut self,
+        rng: &mut dyn RngCore,
+        home_win_probability: f64,
+        score_model: &ScoreModel,
+    ) {
+        if self.game_result.is_none() && !self.has_tbd_team() {
+            self.simulate_if_undecided(rng, home_win_probability);
+            let game_result: &GameResult = self.game_result.as_ref().unwrap();
+            let (home_score, away_score): (i32, i32) =
+                score_model.draw_scores(rng, home_win_probability, game_result);
+            self.home_score = Some(home_score);
+            self.away_score = Some(away_score);
+        }
+    }
+
+    fn simulate_in_mode(
+        &mut self,
+        rng: &mut dyn RngCore,
+        home_win_probability: f64,
+        score_model: Option<&ScoreModel>,
+    ) {
+        match score_model {
+            Some(score_model) => {
+                self.simulate_with_score_if_undecided(rng, home_win_probability, score_model)
+            }
+            None => self.simulate_if_undecided(rng, home_win_probability),
+        }
+    }
+}
+
+impl fmt::Display for Game {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let home: &str = &self.home_team.abbreviation;
+        let away: &str = &self.away_team.abbreviation;
+        let outcome: String = match (&self.game_result, self.home_score, self.away_score) {
+            (None, _, _) => String::from("scheduled"),
+            (Some(GameResult::HomeWin), Some(home_score), Some(away_score)) => {
+                format!("{} {}-{}", home, home_score, away_score)
+            }
+            (Some(GameResult::AwayWin), Some(home_score), Some(away_score)) => {
+                format!("{} {}-{}", away, away_score, home_score)
+            }
+            (Some(GameResult::Tie), Some(home_score), Some(away_score)) => {
+                format!("tie {}-{}", home_score, away_score)
+            }
+            (Some(GameResult::HomeWin), _, _) => format!("{} win", home),
+            (Some(GameResult::AwayWin), _, _) => format!("{} win", away),
+            (Some(GameResult::Tie), _, _) => String::from("tie"),
+        };
+        match self.neutral_site {
+            true => write!(f, "Week {}: {} vs {} — {}", self.week, away, home, outcome)?,
+            false => write!(f, "Week {}: {} @ {} — {}", self.week, away, home, outcome)?,
+        }
+        if self.is_simulated {
+            write!(f, " (simulated)")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SimulationConfig {
+    /// Seed each game's win probability from the teams' current records (log5) instead of
+    /// treating both teams as equal.
+    pub record_based_probabilities: bool,
+    /// Weight, in games, given to the prior (`shrinkage_prior`, or the team's entry from
+    /// `Season::set_priors`) when computing a team's modeling percent. The prior's share is
+    /// `shrinkage_games / (shrinkage_games + games played)`, so it fades as the season goes
+    /// on. Zero leaves the modeling percent equal to the team's actual win percentage.
+    pub shrinkage_games: f64,
+    pub shrinkage_prior: f64,
+    /// Expected intra-division schedule, checked by `Season::validate_schedule_symmetry`.
+    pub division_schedule: DivisionSchedulePattern,
+    /// Common random numbers: simulation `i` of the baseline and of every pinned-game scenario
+    /// draws from the same random stream, so games that aren't pinned get the same outcomes
+    /// and differences between scenarios have lower variance.
+    pub common_random_numbers: bool,
+    pub seeding_detail: SeedingDetail,
+    pub tiebreakers: TiebreakChains,
+    /// Copies of the season that split each batch of simulations on the rayon thread pool.
+    /// Each copy draws from its own stream seeded from the season's generator, so a seeded
+    /// run is reproducible for a given worker count but differs from a serial run.
+    pub parallel_workers: u64,
+    /// Playoff field and byes. `None` uses the format of the season's year.
+    pub playoff_format: Option<PlayoffFormat>,
+    /// Batches with at most this many undecided games (capped at `MAX_EXACT_GAMES`) use
+    /// `SimulationMode::Exact`, as long as there are at least as many simulations as
+    /// combinations of results. Exact batches never produce ties. Zero always simulates.
+    pub exact_threshold: usize,
+    /// Game probabilities when no `game_simulator` or `elo_model` is set and
+    /// `record_based_probabilities` is off.
+    pub home_field: HomeField,
+    /// Ends each Monte Carlo batch early once its odds are precise enough, with the batch's
+    /// simulation count as the cap. `None` always runs the full count.
+    pub stopping_rule: Option<StoppingRule>,
+    /// Draws a final score for each game a Monte Carlo simulation plays out, for point-based
+    /// tiebreakers and margins. `None` only draws the result. `SimulationMode::Exact`
+    /// never draws scores.
+    pub score_model: Option<ScoreModel>,
 }
 
+/// Adaptive stopping for Monte Carlo batches: simulations run in steps of `check_every` until
+/// the standard error of every team of interest's playoff odds, `sqrt(p(1 - p) / n)`, is at
+/// most `max_standard_error`. Blowout scenarios stop after the first step while close races
+/// run up to the cap.
 #[derive(Clone, Debug)]
+pub struct StoppingRule {
+    pub max_standard_error: f64,
+    /// Teams whose odds must reach the target; every team when empty.
+    pub team_ids: Vec<i32>,
+    /// Simulations between checks, and so the fewest a batch runs.
+    pub check_every: u64,
+}
+
+impl StoppingRule {
+    pub fn new(max_standard_error: f64) -> StoppingRule {
+        StoppingRule {
+            max_standard_error,
+            team_ids: Vec::new(),
+            check_every: 1000,
+        }
+    }
+
+    pub fn with_team_ids(mut self, team_ids: Vec<i32>) -> StoppingRule {
+        self.team_ids = team_ids;
+        self
+    }
+
+    pub fn with_check_every(mut self, check_every: u64) -> StoppingRule {
+        self.check_every = check_every;
+        self
+    }
+}
+
+impl Default for SimulationConfig {
+    fn default() -> SimulationConfig {
+        SimulationConfig {
+            record_based_probabilities: false,
+            shrinkage_games: 0.0,
+            shrinkage_prior: 0.5,
+            division_schedule: DivisionSchedulePattern::HomeAndHome(1),
+            common_random_numbers: false,
+            seeding_detail: SeedingDetail::FullSeeds,
+            tiebreakers: TiebreakChains::default(),
+            parallel_workers: 1,
+            playoff_format: None,
+            exact_threshold: 12,
+            home_field: HomeField::default(),
+            stopping_rule: None,
+            score_model: None,
+        }
+    }
+}
+
+impl SimulationConfig {
+    /// Win percentage used only to seed game probabilities, blended toward the prior while
+    /// few games have been played. Official records and tiebreakers use
+    /// `Season::calculate_percent_from_tuple`, which is never shrunk.
+    pub fn modeling_percent(&self, record: (u16, u16, u16)) -> f64 {
+        self.modeling_percent_with_prior(record, self.shrinkage_prior)
+    }
+
+    pub fn modeling_percent_with_prior(&self, record: (u16, u16, u16), prior: f64) -> f64 {
+        let (wins, losses, ties) = record;
+        let games: f64 = f64::from(wins) + f64::from(losses) + f64::from(ties);
+        let weighted_wins: f64 = f64::from(wins) + f64::from(ties) / 2.0;
+        match games + self.shrinkage_games {
+            total if total > 0.0 => (weighted_wins + self.shrinkage_games * prior) / total,
+            _ => prior,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum DivisionSchedulePattern {
+    /// Each team hosts every division opponent this many times.
+    HomeAndHome(u8),
+    /// Each pair of division opponents meets this many times, at either venue.
+    Meetings(u8),
+}
+
+/// How a batch of simulations plays out the undecided games.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SimulationMode {
+    MonteCarlo,
+    /// Every combination of home and away wins, each counted in proportion to its probability
+    /// and rounded so the counts add up to the batch's simulations. Only used when the batch
+    /// has at least one simulation per combination. Ties are excluded: they aren't enumerated
+    /// and their chance is spread over the two results.
+    Exact,
+}
+
+/// How much of the playoff seeding each simulation works out.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SeedingDetail {
+    /// Division winners and wildcard berths only. Seeds 1-4 are never assigned, so playoff
+    /// seed counts only cover the wildcard seeds and the finish-ahead matrix is not tracked.
+    DivisionAndBerth,
+    FullSeeds,
+}
+
+/// A single tiebreaking step, run by `TeamPool::apply_tiebreak_rule`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum TiebreakRule {
+    HeadToHead,
+    /// Applies only when one club beat, or lost to, every other tied club.
+    HeadToHeadSweep,
+    DivisionPercent,
+    ConferencePercent,
+    /// Won-lost-tied percentage in common games, with the minimum number of common games.
+    CommonGames(u8),
+    StrengthOfVictory,
+    StrengthOfSchedule,
+    /// Net points in games against common opponents.
+    NetPointsCommonGames,
+    NetPointsConferenceGames,
+    NetPoints,
+    /// Net touchdowns in all games. Skipped when a tied club has a game without touchdown
+    /// counts, which includes every simulated game.
+    NetTouchdowns,
+    CoinToss,
+}
+
+/// Tiebreaker orders used after clubs are ranked by overall won-lost-tied percentage. The
+/// defaults follow the NFL procedures, as documented on `TeamPool::evaluate_wildcard`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TiebreakChains {
+    /// Clubs in the same division, including division ties within the wildcard race. Starts
+    /// again from the first step whenever some of three or more tied clubs are eliminated.
+    pub division: Vec<TiebreakRule>,
+    /// Wildcard and division winner seeding, each step only while three or more clubs
+    /// remain tied.
+    pub three_or_more_clubs: Vec<TiebreakRule>,
+    /// Wildcard and division winner seeding once two clubs remain tied.
+    pub two_clubs: Vec<TiebreakRule>,
+}
+
+impl Default for TiebreakChains {
+    fn default() -> TiebreakChains {
+        TiebreakChains {
+            division: vec![
+                TiebreakRule::HeadToHead,
+                TiebreakRule::DivisionPercent,
+                TiebreakRule::CommonGames(0),
+                TiebreakRule::ConferencePercent,
+                TiebreakRule::StrengthOfVictory,
+                TiebreakRule::StrengthOfSchedule,
+                TiebreakRule::NetPointsCommonGames,
+                TiebreakRule::NetPoints,
+                TiebreakRule::NetTouchdowns,
+                TiebreakRule::CoinToss,
+            ],
+            three_or_more_clubs: vec![
+                TiebreakRule::HeadToHeadSweep,
+                TiebreakRule::ConferencePercent,
+                TiebreakRule::CommonGames(4),
+                TiebreakRule::StrengthOfVictory,
+                TiebreakRule::StrengthOfSchedule,
+                TiebreakRule::NetPointsConferenceGames,
+                TiebreakRule::NetPoints,
+                TiebreakRule::NetTouchdowns,
+                TiebreakRule::CoinToss,
+            ],
+            two_clubs: vec![
+                TiebreakRule::HeadToHead,
+                TiebreakRule::ConferencePercent,
+                TiebreakRule::CommonGames(4),
+                TiebreakRule::StrengthOfVictory,
+                TiebreakRule::StrengthOfSchedule,
+                TiebreakRule::NetPointsConferenceGames,
+                TiebreakRule::NetPoints,
+                TiebreakRule::NetTouchdowns,
+                TiebreakRule::CoinToss,
+            ],
+        }
+    }
+}
+
+/// A division pairing whose game count differs from the expected pattern. For
+/// `HomeAndHome`, `team_id` is the host; for `Meetings`, it is the lower team id.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScheduleIssue {
+    pub team_id: i32,
+    pub opponent_id: i32,
+    pub expected: u8,
+    pub found: u8,
+}
+
+impl ScheduleIssue {
+    pub fn is_missing(&self) -> bool {
+        self.found < self.expected
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct TeamRecord {
-    pub overall_record: (u8, u8, u8),
+    pub overall_record: (u16, u16, u16),
     pub overall_percent: u16,
-    pub conference_record: (u8, u8, u8),
+    pub conference_record: (u16, u16, u16),
     pub conference_percent: u16,
-    pub division_record: (u8, u8, u8),
+    pub division_record: (u16, u16, u16),
     pub division_percent: u16,
+    /// Points from the games with a score, overall and in conference games.
+    pub points_for: i32,
+    pub points_against: i32,
+    pub conference_points_for: i32,
+    pub conference_points_against: i32,
+    /// Decided games without a score, such as simulated ones, missing from the point totals.
+    pub unscored_games: u16,
 }
 
 impl TeamRecord {
@@ -136,17 +821,46 @@ impl TeamRecord {
             conference_percent: 0,
             division_record: (0, 0, 0),
             division_percent: 0,
+            points_for: 0,
+            points_against: 0,
+            conference_points_for: 0,
+            conference_points_against: 0,
+            unscored_games: 0,
         }
     }
+
+    pub fn point_differential(&self) -> i32 {
+        self.points_for - self.points_against
+    }
+
+    pub fn conference_point_differential(&self) -> i32 {
+        self.conference_points_for - self.conference_points_against
+    }
+
+    /// Whether every decided game had a score, so the point totals are complete.
+    pub fn has_all_scores(&self) -> bool {
+        self.unscored_games == 0
+    }
+
+    fn update_percentages(&mut self) {
+        self.overall_percent = Season::calculate_percent_from_tuple(self.overall_record);
+        self.conference_percent = Season::calculate_percent_from_tuple(self.conference_record);
+        self.division_percent = Season::calculate_percent_from_tuple(self.division_record);
+    }
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct CurrentSimulationResult {
     pub team_records: HashMap<i32, TeamRecord>,
     pub playoff_seeding: HashMap<u8, HashSet<i32>>,
     pub division_winners: HashSet<i32>,
     pub wildcard_teams: HashSet<i32>,
     pub draft_order: HashMap<u8, i32>,
+    pub random_ties: Vec<TieGroup>,
+    pub tiebreaks: Vec<TiebreakDecision>,
+    pub playoff_bracket: Option<PlayoffBracket>,
+    pub strengths_of_schedule: HashMap<i32, StrengthOfSchedule>,
 }
 
 impl CurrentSimulationResult {
@@ -157,6 +871,10 @@ impl CurrentSimulationResult {
             division_winners: HashSet::new(),
             wildcard_teams: HashSet::new(),
             draft_order: HashMap::new(),
+            random_ties: Vec::new(),
+            tiebreaks: Vec::new(),
+            playoff_bracket: None,
+            strengths_of_schedule: HashMap::new(),
         };
 
         for i in 1..8 {
@@ -165,9 +883,119 @@ impl CurrentSimulationResult {
 
         result
     }
+
+    /// Teams counted as both a division winner and a wildcard, which no valid seeding allows.
+    pub fn seeding_violations(&self) -> Vec<i32> {
+        let mut team_ids: Vec<i32> = self
+            .division_winners
+            .intersection(&self.wildcard_teams)
+            .copied()
+            .collect();
+        team_ids.sort();
+        team_ids
+    }
+
+    /// Teams in a division, wildcard or seeding tie that went to a coin toss. Draft order
+    /// coin tosses don't count.
+    pub fn coin_toss_teams(&self) -> HashSet<i32> {
+        self.random_ties
+            .iter()
+            .filter(|tie_group| tie_group.pool_type != PoolType::DraftOrder)
+            .flat_map(|tie_group| tie_group.teams.iter().copied())
+            .collect()
+    }
+
+    /// Whether a coin toss decided any division title, wildcard spot or seed.
+    pub fn decided_by_coin_toss(&self) -> bool {
+        self.random_ties
+            .iter()
+            .any(|tie_group| tie_group.pool_type != PoolType::DraftOrder)
+    }
+
+    /// Orders `team_ids` by final finish: playoff teams by seed, then non-playoff teams by
+    /// draft position, latest pick first. Teams with neither come last, in the order given.
+    pub fn finish_order(&self, team_ids: &[i32]) -> Vec<i32> {
+        let mut finish_keys: HashMap<i32, (u8, u8)> = HashMap::new();
+        for (seed, teams) in self.playoff_seeding.iter() {
+            for team_id in teams.iter() {
+                finish_keys.insert(*team_id, (0, *seed));
+            }
+        }
+        for (draft_position, team_id) in self.draft_order.iter() {
+            finish_keys
+                .entry(*team_id)
+                .or_insert((1, u8::MAX - draft_position));
+        }
+
+        let mut order: Vec<i32> = team_ids.to_vec();
+        order.sort_by_key(|team_id| match finish_keys.get(team_id) {
+            Some(finish_key) => *finish_key,
+            None => (2, 0),
+        });
+        order
+    }
+}
+
+/// A team's opponents' combined record at the end of one simulation, split between the games
+/// already decided when the simulation started and the games it simulated. The strength of
+/// schedule tiebreaker uses the two together.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct StrengthOfSchedule {
+    pub played: (u16, u16, u16),
+    pub remaining: (u16, u16, u16),
+}
+
+impl StrengthOfSchedule {
+    pub fn overall_record(&self) -> (u16, u16, u16) {
+        (
+            self.played.0 + self.remaining.0,
+            self.played.1 + self.remaining.1,
+            self.played.2 + self.remaining.2,
+        )
+    }
+
+    /// Combined win percentage in thousandths, as compared by the tiebreaker.
+    pub fn overall_percent(&self) -> u16 {
+        Season::calculate_percent_from_tuple(self.overall_record())
+    }
+
+    /// Opponents' win percentage in the decided games; `None` when there were none.
+    pub fn played_pct(&self) -> Option<f64> {
+        win_pct(self.played)
+    }
+
+    /// Opponents' win percentage in the simulated games; `None` when there were none.
+    pub fn remaining_pct(&self) -> Option<f64> {
+        win_pct(self.remaining)
+    }
+}
+
+fn win_pct(record: (u16, u16, u16)) -> Option<f64> {
+    let (wins, losses, ties) = record;
+    match wins + losses + ties {
+        0 => None,
+        games => Some((f64::from(wins) + f64::from(ties) / 2.0) / f64::from(games)),
+    }
+}
+
+/// Games whose results changed in a `Season::refresh`, and the run that followed.
+#[derive(Clone, Debug)]
+pub struct Refresh {
+    pub changed_game_ids: Vec<i32>,
+    pub report: SimulationReport,
+}
+
+/// A stored run being resumed. `completed` holds the scenarios already in the database,
+/// `None` for the baseline, and `results` their counts.
+#[cfg_attr(not(feature = "postgres"), allow(dead_code))]
+struct StoredRun {
+    completed: HashSet<Option<(i32, GameResult)>>,
+    results: HashMap<SimulationResultLookup, TeamSimulationResults>,
 }
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct SimulationResultLookup {
     pub game_id: Option<i32>,
     pub game_result: Option<GameResult>,
@@ -181,277 +1009,688 @@ impl SimulationResultLookup {
     ) -> SimulationResultLookup {
         match simulation_game {
             Some(sg) => SimulationResultLookup {
-                game_id: Some(sg.0.clone()),
+                game_id: Some(sg.0),
                 game_result: Some(sg.1.clone()),
-                team_id: team_id.clone(),
+                team_id: *team_id,
             },
             None => SimulationResultLookup {
                 game_id: None,
                 game_result: None,
-                team_id: team_id.clone(),
+                team_id: *team_id,
             },
         }
     }
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct TeamSimulationResults {
+    pub simulations: i32,
     pub made_playoffs: i32,
     pub playoff_seedings: HashMap<u8, i32>,
     pub division_winner: i32,
     pub wildcard_team: i32,
     pub draft_positions: HashMap<u8, i32>,
+    /// Simulations in which this team finished ahead of each other team in its conference,
+    /// keyed by the other team's id. Only counted for the baseline (no fixed game result).
+    pub finished_ahead: HashMap<i32, i32>,
+    pub total_wins: i32,
+    /// Simulations ending with each number of wins. Ties count as neither wins nor losses.
+    pub win_totals: HashMap<u8, i32>,
+    /// Sum of the weeks in which the team clinched a playoff spot, over `clinches` baseline
+    /// simulations.
+    pub total_clinch_weeks: i32,
+    pub clinches: i32,
+    /// Simulations in which the team won each playoff round, counting the top seed's bye.
+    pub playoff_round_wins: HashMap<PlayoffRound, i32>,
+    /// Sums of `StrengthOfSchedule::played_pct` and `remaining_pct` over the
+    /// `played_sos_simulations` and `remaining_sos_simulations` in which they were defined.
+    pub total_played_sos: f64,
+    pub played_sos_simulations: i32,
+    pub total_remaining_sos: f64,
+    pub remaining_sos_simulations: i32,
+    /// Simulations in which a coin toss settled one of the team's division, wildcard or seeding
+    /// ties; see `CurrentSimulationResult::coin_toss_teams`.
+    pub coin_tosses: i32,
+    /// Simulations in which the team hosted at least one playoff game.
+    pub hosted_playoff_games: i32,
+    /// Simulations in which the team played each opponent, by round and opponent id. Only
+    /// counted for the baseline.
+    pub playoff_opponents: HashMap<PlayoffRound, HashMap<i32, i32>>,
 }
 
 impl TeamSimulationResults {
     fn new() -> TeamSimulationResults {
         let mut results = TeamSimulationResults {
+            simulations: 0,
             made_playoffs: 0,
             playoff_seedings: HashMap::new(),
             division_winner: 0,
             wildcard_team: 0,
             draft_positions: HashMap::new(),
+            finished_ahead: HashMap::new(),
+            total_wins: 0,
+            win_totals: HashMap::new(),
+            total_clinch_weeks: 0,
+            clinches: 0,
+            playoff_round_wins: HashMap::new(),
+            total_played_sos: 0.0,
+            played_sos_simulations: 0,
+            total_remaining_sos: 0.0,
+            remaining_sos_simulations: 0,
+            coin_tosses: 0,
+            hosted_playoff_games: 0,
+            playoff_opponents: HashMap::new(),
         };
 
         for i in 1..8 {
             results.playoff_seedings.insert(i, 0);
         }
-        for i in 1..19 {
+        for i in 1..33 {
             results.draft_positions.insert(i, 0);
         }
+        for round in PlayoffRound::ALL {
+            results.playoff_round_wins.insert(round, 0);
+        }
 
         results
     }
-}
-
-#[derive(Clone, Debug)]
-pub enum PoolType {
-    Division,
-    Wildcard,
-    DraftOrder,
-    DivisionWinnerSeeding,
-}
-
-#[derive(Clone, Debug)]
-pub struct TeamPool {
-    pub pool_type: PoolType,
-    pub teams: HashSet<i32>,
-    pub conference_mapping: HashMap<String, Vec<i32>>,
-    pub division_mapping: HashMap<String, Vec<i32>>,
-    pub tied_teams: HashSet<i32>,
-    pub winner: Option<i32>,
-    pub ranking: Option<Vec<i32>>,
-    pub team_records: HashMap<i32, TeamRecord>,
-    pub games: HashMap<i32, Game>,
-}
 
-impl TeamPool {
-    pub fn new(source_vec: Vec<i32>, pool_type: PoolType, season: &Season) -> TeamPool {
-        TeamPool {
-            pool_type,
-            teams: HashSet::from_iter(source_vec.clone()),
-            conference_mapping: season.conference_mapping.clone(),
-            division_mapping: season.division_mapping.clone(),
-            tied_teams: HashSet::from_iter(source_vec.clone()),
-            winner: None,
-            ranking: None,
-            team_records: season.current_simulation_result.team_records.clone(),
-            games: season.current_simulation_games.clone(),
+    /// Standard error of the playoff odds as a binomial proportion; zero before any
+    /// simulations.
+    pub fn playoff_standard_error(&self) -> f64 {
+        match self.simulations {
+            0 => 0.0,
+            simulations => standard_error(
+                f64::from(self.made_playoffs) / f64::from(simulations),
+                simulations,
+            ),
         }
     }
 
-    pub fn evaluate(&mut self) {
-        match self.pool_type {
-            PoolType::Division => self.evaluate_division(),
-            PoolType::Wildcard => self.evaluate_wildcard(),
-            PoolType::DraftOrder => self.evaluate_draft_order(),
-            PoolType::DivisionWinnerSeeding => self.evaluate_division_winner_seeding(),
-        }
+    /// Simulations in which the team played in its conference championship game.
+    pub fn reached_conference_championship(&self) -> i32 {
+        self.round_wins(PlayoffRound::Divisional)
     }
 
-    fn evaluate_division(&mut self) {
-        self.break_by_percent("overall");
-        self.break_by_percent("division");
-        self.break_by_head_to_head();
-        self.break_by_common_games(0);
-        self.break_by_percent("conference");
-        self.break_by_strength_of_victory();
-        self.break_by_strength_of_schedule();
-        self.break_by_random();
-        self.winner = Some(self.tied_teams.iter().next().unwrap().clone());
+    /// Simulations in which the team played in the Super Bowl.
+    pub fn reached_super_bowl(&self) -> i32 {
+        self.round_wins(PlayoffRound::ConferenceChampionship)
     }
 
-    fn evaluate_wildcard(&mut self) {
-        self.ranking = Some(Vec::new());
-        for _ in 0..3 {
-            self.break_by_percent("overall");
-            if self.tied_teams.len() > 2 {
-                self.break_wildcard_division_ties();
-            }
-            if self.tied_teams.len() > 2 {
-                self.break_by_head_to_head_sweep();
-            }
-            if self.tied_teams.len() > 2 {
-                self.break_by_percent("conference");
-            }
-            if self.tied_teams.len() > 2 {
-                self.break_by_common_games(4);
-            }
-            if self.tied_teams.len() > 2 {
-                self.break_by_strength_of_victory();
-            }
-            if self.tied_teams.len() > 2 {
-                self.break_by_strength_of_schedule();
-            }
-            if self.tied_teams.len() > 2 {
-                self.pick_two_random();
-            }
+    pub fn won_super_bowl(&self) -> i32 {
+        self.round_wins(PlayoffRound::SuperBowl)
+    }
 
-            self.break_by_head_to_head();
-            self.break_by_percent("conference");
-            self.break_by_common_games(4);
-            self.break_by_strength_of_victory();
-            self.break_by_strength_of_schedule();
-            self.break_by_random();
+    fn round_wins(&self, round: PlayoffRound) -> i32 {
+        *self.playoff_round_wins.get(&round).unwrap_or(&0)
+    }
 
-            let top_team = self.tied_teams.iter().next().unwrap().clone();
-            self.ranking.as_mut().unwrap().push(top_team);
-            self.tied_teams = self.teams.clone();
-            for team_id in self.ranking.as_ref().unwrap() {
-                self.tied_teams.remove(team_id);
+    fn add(&mut self, other: &TeamSimulationResults) {
+        self.simulations += other.simulations;
+        self.made_playoffs += other.made_playoffs;
+        self.division_winner += other.division_winner;
+        self.wildcard_team += other.wildcard_team;
+        self.total_wins += other.total_wins;
+        self.total_clinch_weeks += other.total_clinch_weeks;
+        self.clinches += other.clinches;
+        self.total_played_sos += other.total_played_sos;
+        self.played_sos_simulations += other.played_sos_simulations;
+        self.total_remaining_sos += other.total_remaining_sos;
+        self.remaining_sos_simulations += other.remaining_sos_simulations;
+        self.coin_tosses += other.coin_tosses;
+        self.hosted_playoff_games += other.hosted_playoff_games;
+        for (round, opponents) in other.playoff_opponents.iter() {
+            let counts: &mut HashMap<i32, i32> = self.playoff_opponents.entry(*round).or_default();
+            for (opponent_id, count) in opponents.iter() {
+                *counts.entry(*opponent_id).or_insert(0) += count;
             }
         }
-    }
-
-    fn evaluate_draft_order(&mut self) {
-        self.ranking = Some(Vec::new());
-        for _ in 0..18 {
-            self.break_by_percent("overall");
-            self.break_by_strength_of_schedule();
-            self.break_by_random();
+        for (seed, count) in other.playoff_seedings.iter() {
+            *self.playoff_seedings.entry(*seed).or_insert(0) += count;
+        }
+        for (draft_position, count) in other.draft_positions.iter() {
+            *self.draft_positions.entry(*draft_position).or_insert(0) += count;
+        }
+        for (wins, count) in other.win_totals.iter() {
+            *self.win_totals.entry(*wins).or_insert(0) += count;
+        }
+        for (team_id, count) in other.finished_ahead.iter() {
+            *self.finished_ahead.entry(*team_id).or_insert(0) += count;
+        }
+        for (round, count) in other.playoff_round_wins.iter() {
+            *self.playoff_round_wins.entry(*round).or_insert(0) += count;
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum PoolType {
+    Division,
+    Wildcard,
+    DraftOrder,
+    DivisionWinnerSeeding,
+}
+
+/// Which won-lost-tied percentage `TeamPool::break_by_percent` compares.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum PercentType {
+    Overall,
+    Division,
+    Conference,
+}
+
+/// Which games `TeamPool::break_by_net_points` totals.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum NetPointsGames {
+    /// Games against opponents every tied club played.
+    Common,
+    Conference,
+    Overall,
+}
+
+/// A step of `TeamPool`'s tiebreaking, as recorded in `TiebreakDecision`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum TiebreakStep {
+    OverallPercent,
+    /// Ties within each division broken first, keeping the top club per division.
+    DivisionTiebreaker,
+    Rule(TiebreakRule),
+}
+
+impl fmt::Display for TiebreakStep {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TiebreakStep::OverallPercent => write!(f, "overall percentage"),
+            TiebreakStep::DivisionTiebreaker => write!(f, "division tiebreaker"),
+            TiebreakStep::Rule(TiebreakRule::HeadToHead) => write!(f, "head-to-head"),
+            TiebreakStep::Rule(TiebreakRule::HeadToHeadSweep) => write!(f, "head-to-head sweep"),
+            TiebreakStep::Rule(TiebreakRule::DivisionPercent) => write!(f, "division percentage"),
+            TiebreakStep::Rule(TiebreakRule::ConferencePercent) => {
+                write!(f, "conference percentage")
+            }
+            TiebreakStep::Rule(TiebreakRule::CommonGames(_)) => write!(f, "common games"),
+            TiebreakStep::Rule(TiebreakRule::StrengthOfVictory) => write!(f, "strength of victory"),
+            TiebreakStep::Rule(TiebreakRule::StrengthOfSchedule) => {
+                write!(f, "strength of schedule")
+            }
+            TiebreakStep::Rule(TiebreakRule::NetPointsCommonGames) => {
+                write!(f, "net points in common games")
+            }
+            TiebreakStep::Rule(TiebreakRule::NetPointsConferenceGames) => {
+                write!(f, "net points in conference games")
+            }
+            TiebreakStep::Rule(TiebreakRule::NetPoints) => write!(f, "net points"),
+            TiebreakStep::Rule(TiebreakRule::NetTouchdowns) => write!(f, "net touchdowns"),
+            TiebreakStep::Rule(TiebreakRule::CoinToss) => write!(f, "coin toss"),
+        }
+    }
+}
+
+/// A tiebreaking step that separated clubs, in the order `TeamPool` applied it.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct TiebreakDecision {
+    pub pool_type: PoolType,
+    /// Place being decided, from 1: the wildcard spot or seed, or the draft slot counted from
+    /// the latest pick. Always 1 for a division.
+    pub place: usize,
+    pub step: TiebreakStep,
+    /// Team ids tied before the step, sorted.
+    pub tied_teams: Vec<i32>,
+    /// Team ids still tied after it, sorted. A single team won the tie.
+    pub remaining_teams: Vec<i32>,
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct TieGroup {
+    pub pool_type: PoolType,
+    /// Place being decided, from 1, as in `TiebreakDecision`.
+    pub place: usize,
+    pub teams: Vec<i32>,
+}
+
+/// Each team's games and opponents, so that tiebreakers can go through the tied teams' games
+/// instead of the whole league's. Only depends on who plays whom, so one index serves every
+/// simulation of a batch. Games with a TBD team are left out.
+#[derive(Clone, Debug, Default)]
+pub struct ScheduleIndex {
+    /// Game ids by team, in id order.
+    pub team_games: HashMap<i32, Vec<i32>>,
+    pub opponents: HashMap<i32, HashSet<i32>>,
+}
 
-            let top_team = self.tied_teams.iter().next().unwrap().clone();
-            self.ranking.as_mut().unwrap().push(top_team);
-            self.tied_teams = self.teams.clone();
-            for team_id in self.ranking.as_ref().unwrap() {
-                self.tied_teams.remove(team_id);
+impl ScheduleIndex {
+    pub fn new(games: &HashMap<i32, Game>) -> ScheduleIndex {
+        let mut index: ScheduleIndex = ScheduleIndex::default();
+        let mut games: Vec<&Game> = games.values().filter(|game| !game.has_tbd_team()).collect();
+        games.sort_by_key(|game| game.game_id);
+        for game in games {
+            let home_team_id: i32 = game.home_team.team_id;
+            let away_team_id: i32 = game.away_team.team_id;
+            for (team_id, opponent_id) in
+                [(home_team_id, away_team_id), (away_team_id, home_team_id)]
+            {
+                index
+                    .team_games
+                    .entry(team_id)
+                    .or_default()
+                    .push(game.game_id);
+                index
+                    .opponents
+                    .entry(team_id)
+                    .or_default()
+                    .insert(opponent_id);
             }
         }
+        index
+    }
+}
+
+/// What a `TeamPool` reads from its season: the current simulation's games and records and
+/// the league's structure. Borrowed rather than copied, since a pool is built for every
+/// tiebreak of every simulation.
+#[derive(Clone, Copy, Debug)]
+pub struct PoolContext<'a> {
+    pub conference_mapping: &'a HashMap<String, Vec<i32>>,
+    pub division_mapping: &'a HashMap<String, Vec<i32>>,
+    pub team_records: &'a HashMap<i32, TeamRecord>,
+    /// Looked up through `schedule`, so games with a TBD team are skipped.
+    pub games: &'a HashMap<i32, Game>,
+    pub schedule: &'a ScheduleIndex,
+    /// Worked out once per simulation, before any pool is evaluated.
+    pub strengths_of_schedule: &'a HashMap<i32, StrengthOfSchedule>,
+    pub tiebreakers: &'a TiebreakChains,
+    pub playoff_format: PlayoffFormat,
+}
+
+#[derive(Clone, Debug)]
+pub struct TeamPool<'a> {
+    pub pool_type: PoolType,
+    pub teams: HashSet<i32>,
+    pub tied_teams: HashSet<i32>,
+    pub winner: Option<i32>,
+    pub ranking: Option<Vec<i32>>,
+    pub random_ties: Vec<TieGroup>,
+    /// Every step that narrowed a tie during `evaluate`.
+    pub trace: Vec<TiebreakDecision>,
+    pub context: PoolContext<'a>,
+}
+
+impl<'a> TeamPool<'a> {
+    pub fn new(
+        source_vec: Vec<i32>,
+        pool_type: PoolType,
+        context: PoolContext<'a>,
+    ) -> TeamPool<'a> {
+        let teams: HashSet<i32> = HashSet::from_iter(source_vec);
+        TeamPool {
+            pool_type,
+            tied_teams: teams.clone(),
+            teams,
+            winner: None,
+            ranking: None,
+            random_ties: Vec::new(),
+            trace: Vec::new(),
+            context,
+        }
+    }
 
-        self.ranking.as_mut().unwrap().reverse();
+    fn team_games(&self, team_id: i32) -> impl Iterator<Item = &'a Game> {
+        let games: &'a HashMap<i32, Game> = self.context.games;
+        let schedule: &'a ScheduleIndex = self.context.schedule;
+        schedule
+            .team_games
+            .get(&team_id)
+            .into_iter()
+            .flatten()
+            .map(move |game_id| games.get(game_id).unwrap())
     }
 
-    fn evaluate_division_winner_seeding(&mut self) {
-        self.ranking = Some(Vec::new());
-        for _ in 0..4 {
-            self.break_by_percent("overall");
-            if self.tied_teams.len() > 2 {
-                self.break_by_head_to_head_sweep();
+    /// Games between two of `team_ids`, each once.
+    fn games_among(&self, team_ids: &[i32]) -> Vec<&'a Game> {
+        team_ids
+            .iter()
+            .flat_map(|team_id| {
+                self.team_games(*team_id).filter(move |game| {
+                    game.home_team.team_id == *team_id && team_ids.contains(&game.away_team.team_id)
+                })
+            })
+            .collect()
+    }
+
+    pub fn evaluate(&mut self, rng: &mut dyn RngCore) -> Result<(), Error> {
+        match self.pool_type {
+            PoolType::Division => self.evaluate_division(rng),
+            PoolType::Wildcard => self.evaluate_wildcard(rng),
+            PoolType::DraftOrder => self.evaluate_draft_order(rng),
+            PoolType::DivisionWinnerSeeding => self.evaluate_division_winner_seeding(rng),
+        }
+    }
+
+    fn evaluate_division(&mut self, rng: &mut dyn RngCore) -> Result<(), Error> {
+        self.apply_step(TiebreakStep::OverallPercent, rng)?;
+        self.break_division_ties(rng)?;
+        self.settle_by_coin_toss(rng)?;
+        self.winner = Some(self.top_team()?);
+        Ok(())
+    }
+
+    /// Runs the configured division steps. Whenever a step eliminates some of three or more
+    /// tied clubs, the clubs still tied go back to the first step.
+    fn break_division_ties(&mut self, rng: &mut dyn RngCore) -> Result<(), Error> {
+        let rules: &'a [TiebreakRule] = &self.context.tiebreakers.division;
+        self.break_ties_with_restarts(rules, 1, rng)
+    }
+
+    /// Runs the configured three-or-more-club steps while more than two clubs are tied, then
+    /// the two-club steps. Each goes back to its first step whenever clubs are eliminated, so
+    /// three clubs left from four start the three-club steps again and two clubs start the
+    /// two-club steps.
+    fn break_remaining_ties(&mut self, rng: &mut dyn RngCore) -> Result<(), Error> {
+        let tiebreakers: &'a TiebreakChains = self.context.tiebreakers;
+        self.break_ties_with_restarts(&tiebreakers.three_or_more_clubs, 2, rng)?;
+        self.break_ties_with_restarts(&tiebreakers.two_clubs, 1, rng)
+    }
+
+    /// Applies `rules` in order while more than `remaining` clubs are tied, starting over from
+    /// the first rule after any step that narrows the tie.
+    fn break_ties_with_restarts(
+        &mut self,
+        rules: &[TiebreakRule],
+        remaining: usize,
+        rng: &mut dyn RngCore,
+    ) -> Result<(), Error> {
+        let mut step: usize = 0;
+        while step < rules.len() && self.tied_teams.len() > remaining {
+            let tied_count: usize = self.tied_teams.len();
+            self.apply_tiebreak_rule(rules[step], rng)?;
+            step = match self.tied_teams.len() < tied_count {
+                true => 0,
+                false => step + 1,
+            };
+        }
+        Ok(())
+    }
+
+    pub fn apply_tiebreak_rule(
+        &mut self,
+        rule: TiebreakRule,
+        rng: &mut dyn RngCore,
+    ) -> Result<(), Error> {
+        self.apply_step(TiebreakStep::Rule(rule), rng)
+    }
+
+    /// Runs `step` on the tied clubs, adding it to `trace` if it separated any of them.
+    fn apply_step(&mut self, step: TiebreakStep, rng: &mut dyn RngCore) -> Result<(), Error> {
+        let tied_teams: Option<Vec<i32>> = match self.tied_teams.len() {
+            tt if tt > 1 => Some(sorted_ids(&self.tied_teams)),
+            _ => None,
+        };
+        match step {
+            TiebreakStep::OverallPercent => self.break_by_percent(PercentType::Overall),
+            TiebreakStep::DivisionTiebreaker => self.break_wildcard_division_ties(rng)?,
+            TiebreakStep::Rule(TiebreakRule::HeadToHead) => self.break_by_head_to_head(),
+            TiebreakStep::Rule(TiebreakRule::HeadToHeadSweep) => self.break_by_head_to_head_sweep(),
+            TiebreakStep::Rule(TiebreakRule::DivisionPercent) => {
+                self.break_by_percent(PercentType::Division)
+            }
+            TiebreakStep::Rule(TiebreakRule::ConferencePercent) => {
+                self.break_by_percent(PercentType::Conference)
+            }
+            TiebreakStep::Rule(TiebreakRule::CommonGames(min_games)) => {
+                self.break_by_common_games(min_games)
+            }
+            TiebreakStep::Rule(TiebreakRule::StrengthOfVictory) => {
+                self.break_by_strength_of_victory()
             }
-            if self.tied_teams.len() > 2 {
-                self.break_by_percent("conference");
+            TiebreakStep::Rule(TiebreakRule::StrengthOfSchedule) => {
+                self.break_by_strength_of_schedule()
             }
-            if self.tied_teams.len() > 2 {
-                self.break_by_common_games(4);
+            TiebreakStep::Rule(TiebreakRule::NetPointsCommonGames) => {
+                self.break_by_net_points(NetPointsGames::Common)
             }
-            if self.tied_teams.len() > 2 {
-                self.break_by_strength_of_victory();
+            TiebreakStep::Rule(TiebreakRule::NetPointsConferenceGames) => {
+                self.break_by_net_points(NetPointsGames::Conference)
             }
-            if self.tied_teams.len() > 2 {
-                self.break_by_strength_of_schedule();
+            TiebreakStep::Rule(TiebreakRule::NetPoints) => {
+                self.break_by_net_points(NetPointsGames::Overall)
             }
-            if self.tied_teams.len() > 2 {
-                self.pick_two_random();
+            TiebreakStep::Rule(TiebreakRule::NetTouchdowns) => self.break_by_net_touchdowns(),
+            TiebreakStep::Rule(TiebreakRule::CoinToss) => self.break_by_random(rng),
+        }
+        if let Some(tied_teams) = tied_teams {
+            if self.tied_teams.len() < tied_teams.len() {
+                self.trace.push(TiebreakDecision {
+                    pool_type: self.pool_type.clone(),
+                    place: self.ranking.as_ref().map_or(0, |ranking| ranking.len()) + 1,
+                    step,
+                    tied_teams,
+                    remaining_teams: sorted_ids(&self.tied_teams),
+                });
             }
+        }
+        Ok(())
+    }
+
+    /// NFL wildcard procedure, run once per wildcard spot. The clubs left over start again from
+    /// the top for the next spot. Ties within a division are always broken first, using the
+    /// division procedure (`break_wildcard_division_ties`). Steps after that come from
+    /// `tiebreakers`, whose defaults are:
+    ///
+    /// Three or more clubs, each step only while three or more remain tied. When a step
+    /// eliminates a club and three or more are still tied, they start again from step 1:
+    /// 1. Division tiebreaker, keeping the top club per division: `break_wildcard_division_ties`
+    /// 2. Head-to-head sweep: `break_by_head_to_head_sweep`
+    /// 3. Won-lost-tied percentage in conference games: `break_by_percent(PercentType::Conference)`
+    /// 4. Won-lost-tied percentage in common games, minimum of four: `break_by_common_games(4)`
+    /// 5. Strength of victory: `break_by_strength_of_victory`
+    /// 6. Strength of schedule: `break_by_strength_of_schedule`
+    /// 7. Points rankings (steps 7-8): not implemented
+    /// 9. Net points in conference games: `break_by_net_points(NetPointsGames::Conference)`
+    /// 10. Net points in all games: `break_by_net_points(NetPointsGames::Overall)`
+    /// 11. Net touchdowns in all games: `break_by_net_touchdowns`
+    /// 12. Coin toss: `break_by_random`
+    ///
+    /// Two clubs, including when a three-club step leaves two:
+    /// 1. Head-to-head: `break_by_head_to_head`
+    /// 2. Won-lost-tied percentage in conference games: `break_by_percent(PercentType::Conference)`
+    /// 3. Won-lost-tied percentage in common games, minimum of four: `break_by_common_games(4)`
+    /// 4. Strength of victory: `break_by_strength_of_victory`
+    /// 5. Strength of schedule: `break_by_strength_of_schedule`
+    /// 6. Points rankings (steps 6-7): not implemented
+    /// 8. Net points in conference games: `break_by_net_points(NetPointsGames::Conference)`
+    /// 9. Net points in all games: `break_by_net_points(NetPointsGames::Overall)`
+    /// 10. Net touchdowns in all games: `break_by_net_touchdowns`
+    /// 11. Coin toss: `break_by_random`
+    ///
+    /// Net touchdowns only decide anything when every game of the tied clubs has touchdown
+    /// counts from the source, so in practice only in standings of decided games. Simulated
+    /// games have none, and the step passes the tie on to the coin toss.
+    fn evaluate_wildcard(&mut self, rng: &mut dyn RngCore) -> Result<(), Error> {
+        self.ranking = Some(Vec::new());
+        for _ in 0..self.context.playoff_format.wildcards() {
+            self.apply_step(TiebreakStep::OverallPercent, rng)?;
+            self.apply_step(TiebreakStep::DivisionTiebreaker, rng)?;
+            self.break_remaining_ties(rng)?;
+            self.settle_by_coin_toss(rng)?;
+            self.rank_top_team()?;
+        }
+        Ok(())
+    }
 
-            self.break_by_head_to_head();
-            self.break_by_percent("conference");
-            self.break_by_common_games(4);
-            self.break_by_strength_of_victory();
-            self.break_by_strength_of_schedule();
-            self.break_by_random();
+    /// Non-playoff clubs, ranked from the latest pick to the earliest and then reversed: the
+    /// better record picks later, then the tougher strength of schedule. Remaining ties use
+    /// the division tiebreakers for clubs in one division, the wildcard tiebreakers for clubs
+    /// in one conference, and otherwise head-to-head, common games (minimum of four) and
+    /// strength of victory. The club that wins a tiebreaker picks later.
+    fn evaluate_draft_order(&mut self, rng: &mut dyn RngCore) -> Result<(), Error> {
+        self.ranking = Some(Vec::new());
+        for _ in 0..self.teams.len() {
+            self.apply_step(TiebreakStep::OverallPercent, rng)?;
+            self.apply_tiebreak_rule(TiebreakRule::StrengthOfSchedule, rng)?;
+            self.break_draft_order_ties(rng)?;
+            self.settle_by_coin_toss(rng)?;
+            self.rank_top_team()?;
+        }
 
-            let top_team = self.tied_teams.iter().next().unwrap().clone();
-            self.ranking.as_mut().unwrap().push(top_team);
-            self.tied_teams = self.teams.clone();
-            for team_id in self.ranking.as_ref().unwrap() {
-                self.tied_teams.remove(team_id);
-            }
+        if let Some(ranking) = self.ranking.as_mut() {
+            ranking.reverse();
+        }
+        Ok(())
+    }
+
+    fn break_draft_order_ties(&mut self, rng: &mut dyn RngCore) -> Result<(), Error> {
+        if self.tied_teams.len() < 2 {
+            return Ok(());
+        }
+        let divisions: HashSet<Option<String>> = self
+            .tied_teams
+            .iter()
+            .map(|team_id| self.get_team_division(team_id))
+            .collect();
+        let conferences: HashSet<Option<&String>> = self
+            .tied_teams
+            .iter()
+            .map(|team_id| {
+                self.context
+                    .conference_mapping
+                    .iter()
+                    .find(|(_, team_ids)| team_ids.contains(team_id))
+                    .map(|(conference, _)| conference)
+            })
+            .collect();
+
+        if divisions.len() == 1 {
+            self.break_division_ties(rng)
+        } else if conferences.len() == 1 {
+            self.apply_step(TiebreakStep::DivisionTiebreaker, rng)?;
+            self.break_remaining_ties(rng)
+        } else {
+            self.apply_tiebreak_rule(TiebreakRule::HeadToHead, rng)?;
+            self.apply_tiebreak_rule(TiebreakRule::CommonGames(4), rng)?;
+            self.apply_tiebreak_rule(TiebreakRule::StrengthOfVictory, rng)
+        }
+    }
+
+    /// Same procedure as `evaluate_wildcard`, without the division step since every club
+    /// in the pool won its division.
+    fn evaluate_division_winner_seeding(&mut self, rng: &mut dyn RngCore) -> Result<(), Error> {
+        self.ranking = Some(Vec::new());
+        for _ in 0..4 {
+            self.apply_step(TiebreakStep::OverallPercent, rng)?;
+            self.break_remaining_ties(rng)?;
+            self.settle_by_coin_toss(rng)?;
+            self.rank_top_team()?;
         }
+        Ok(())
     }
 
+    /// The club left once tiebreaking has settled the current place. Errors when no club
+    /// is left, as when a conference has fewer clubs than places to fill.
+    fn top_team(&self) -> Result<i32, Error> {
+        match self.tied_teams.iter().next() {
+            Some(team_id) => Ok(*team_id),
+            None => Err(Error::InvalidData(format!(
+                "no team left for place {} of a {:?} pool of {} teams",
+                self.ranking.as_ref().map_or(0, |ranking| ranking.len()) + 1,
+                self.pool_type,
+                self.teams.len()
+            ))),
+        }
+    }
+
+    /// Adds `top_team` to `ranking` and puts the clubs not yet ranked back in the tie for
+    /// the next place.
+    fn rank_top_team(&mut self) -> Result<(), Error> {
+        let top_team: i32 = self.top_team()?;
+        let ranking: &mut Vec<i32> = self.ranking.get_or_insert_with(Vec::new);
+        ranking.push(top_team);
+        self.tied_teams = self
+            .teams
+            .iter()
+            .filter(|team_id| !ranking.contains(team_id))
+            .copied()
+            .collect();
+        Ok(())
+    }
+
+    /// Applies only when one club beat each of the others in every meeting, which leaves that
+    /// club, or lost to each of them in every meeting, which eliminates it. Clubs that didn't
+    /// play each other can't sweep or be swept.
     fn break_by_head_to_head_sweep(&mut self) {
         match self.tied_teams.len() {
             tt if tt > 1 => {
-                let mut records: HashMap<i32, (u8, u8, u8)> = HashMap::new();
-                for team_id in &self.tied_teams {
-                    records.insert(team_id.clone(), (0, 0, 0));
-                }
-                for (_, game) in self.games.iter() {
-                    if self.tied_teams.contains(&game.home_team.team_id)
-                        && self.tied_teams.contains(&game.away_team.team_id)
-                    {
-                        match game.game_result {
-                            Some(GameResult::HomeWin) => {
-                                records.get_mut(&game.home_team.team_id).unwrap().0 += 1;
-                                records.get_mut(&game.away_team.team_id).unwrap().1 += 1;
-                            }
-                            Some(GameResult::AwayWin) => {
-                                records.get_mut(&game.home_team.team_id).unwrap().1 += 1;
-                                records.get_mut(&game.away_team.team_id).unwrap().0 += 1;
+                let tied_teams: Vec<i32> = sorted_ids(&self.tied_teams);
+                let matrix: HashMap<(i32, i32), (u16, u16, u16)> =
+                    head_to_head_matrix(self.games_among(&tied_teams).into_iter(), &tied_teams);
+                let sweeps = |team_id: i32, beat: bool| -> bool {
+                    tied_teams
+                        .iter()
+                        .filter(|opponent_id| **opponent_id != team_id)
+                        .all(|opponent_id| {
+                            let (wins, losses, ties) = matrix[&(team_id, *opponent_id)];
+                            match beat {
+                                true => wins > 0 && losses == 0 && ties == 0,
+                                false => losses > 0 && wins == 0 && ties == 0,
                             }
-                            Some(GameResult::Tie) => {
-                                records.get_mut(&game.home_team.team_id).unwrap().2 += 1;
-                                records.get_mut(&game.away_team.team_id).unwrap().2 += 1;
-                            }
-                            None => panic!("Game has no result"),
-                        }
-                    }
-                }
-                let mut sweeper: Option<i32> = None;
-                let mut swept: HashSet<i32> = HashSet::new();
-                for (team_id, record) in records {
-                    if record.1 == 0 && record.2 == 0 {
-                        sweeper = Some(team_id);
-                    } else if record.0 == 0 && record.2 == 0 {
-                        swept.insert(team_id);
-                    }
-                }
+                        })
+                };
 
-                let mut new_tied_teams: HashSet<i32> = HashSet::new();
-                match sweeper {
-                    Some(team_id) => {
-                        new_tied_teams.insert(team_id);
+                let sweeper: Option<i32> = tied_teams.iter().copied().find(|t| sweeps(*t, true));
+                let swept: Option<i32> = tied_teams.iter().copied().find(|t| sweeps(*t, false));
+                match (sweeper, swept) {
+                    (Some(team_id), _) => {
+                        self.tied_teams = HashSet::from([team_id]);
                     }
-                    None => {
-                        for team_id in self.tied_teams.iter() {
-                            if !swept.contains(team_id) {
-                                new_tied_teams.insert(team_id.clone());
-                            }
-                        }
+                    (None, Some(team_id)) => {
+                        self.tied_teams.remove(&team_id);
                     }
+                    (None, None) => {}
                 }
             }
             _ => {}
         }
     }
 
-    fn break_wildcard_division_ties(&mut self) {
+    fn tied_head_to_head_records(&self) -> HashMap<i32, (u16, u16, u16)> {
+        let tied_teams: Vec<i32> = Vec::from_iter(self.tied_teams.clone());
+        let mut records: HashMap<i32, (u16, u16, u16)> = HashMap::new();
+        for team_id in &tied_teams {
+            records.insert(*team_id, (0, 0, 0));
+        }
+        for ((team_id, _), (wins, losses, ties)) in
+            head_to_head_matrix(self.games_among(&tied_teams).into_iter(), &tied_teams)
+        {
+            let record = records.get_mut(&team_id).unwrap();
+            record.0 += wins;
+            record.1 += losses;
+            record.2 += ties;
+        }
+        records
+    }
+
+    fn break_wildcard_division_ties(&mut self, rng: &mut dyn RngCore) -> Result<(), Error> {
         match self.tied_teams.len() {
             tt if tt > 1 => {
-                let mut tied_team_divisions: HashMap<String, HashSet<i32>> = HashMap::new();
+                let mut tied_team_divisions: BTreeMap<String, HashSet<i32>> = BTreeMap::new();
                 for team_id in self.tied_teams.iter() {
-                    let team_division = self.get_team_division(team_id).unwrap();
-                    if tied_team_divisions.contains_key(&team_division) {
-                        tied_team_divisions
-                            .get_mut(&team_division)
-                            .unwrap()
-                            .insert(team_id.clone());
-                    } else {
-                        let mut new_set: HashSet<i32> = HashSet::new();
-                        new_set.insert(team_id.clone());
-                        tied_team_divisions.insert(team_division, new_set);
-                    }
+                    let team_division: String = match self.get_team_division(team_id) {
+                        Some(team_division) => team_division,
+                        None => {
+                            return Err(Error::InvalidData(format!(
+                                "team {} is in no division",
+                                team_id
+                            )))
+                        }
+                    };
+                    tied_team_divisions
+                        .entry(team_division)
+                        .or_default()
+                        .insert(*team_id);
                 }
 
                 let mut division_winners: HashSet<i32> = HashSet::new();
@@ -461,14 +1700,14 @@ impl TeamPool {
                         division_pool.pool_type = PoolType::Division;
                         division_pool.teams = teams.clone();
                         division_pool.tied_teams = teams.clone();
-                        division_pool.evaluate();
-                        division_winners.insert(division_pool.winner.unwrap());
-                    } else if teams.len() == 1 {
-                        for team in teams.iter() {
-                            division_winners.insert(team.clone());
-                        }
+                        division_pool.random_ties = Vec::new();
+                        division_pool.trace = Vec::new();
+                        division_pool.evaluate(rng)?;
+                        division_winners.extend(division_pool.winner);
+                        self.random_ties.append(&mut division_pool.random_ties);
+                        self.trace.append(&mut division_pool.trace);
                     } else {
-                        panic!("Division has no teams associated")
+                        division_winners.extend(teams.iter().copied());
                     }
                 }
 
@@ -476,11 +1715,12 @@ impl TeamPool {
             }
             _ => {}
         }
+        Ok(())
     }
 
     fn get_team_division(&self, team_id: &i32) -> Option<String> {
         let mut team_division: Option<String> = None;
-        for (division, teams) in self.division_mapping.iter() {
+        for (division, teams) in self.context.division_mapping.iter() {
             if teams.contains(team_id) {
                 team_division = Some(division.clone());
                 break;
@@ -489,33 +1729,27 @@ impl TeamPool {
         team_division
     }
 
-    fn break_by_percent(&mut self, percent_type: &str) {
+    fn break_by_percent(&mut self, percent_type: PercentType) {
         match self.tied_teams.len() {
             tt if tt > 1 => {
                 let mut working_vec: Vec<(i32, u16)> = Vec::new();
                 for team_id in self.tied_teams.iter() {
-                    let percent = match percent_type {
-                        t if t == "overall" => {
-                            self.team_records.get(team_id).unwrap().overall_percent
-                        }
-                        t if t == "division" => {
-                            self.team_records.get(team_id).unwrap().division_percent
-                        }
-                        t if t == "conference" => {
-                            self.team_records.get(team_id).unwrap().conference_percent
-                        }
-                        t => panic!("Invalid percent type {}", t),
+                    let record: &TeamRecord = self.context.team_records.get(team_id).unwrap();
+                    let percent: u16 = match percent_type {
+                        PercentType::Overall => record.overall_percent,
+                        PercentType::Division => record.division_percent,
+                        PercentType::Conference => record.conference_percent,
                     };
-                    working_vec.push((team_id.clone(), percent.clone()));
+                    working_vec.push((*team_id, percent));
                 }
                 working_vec.sort_by_key(|t| t.1);
                 working_vec.reverse();
 
-                let max_pct = working_vec.get(0).unwrap().1;
+                let max_pct = working_vec.first().unwrap().1;
                 self.tied_teams = HashSet::new();
                 for (team_id, percent) in &working_vec {
                     if percent == &max_pct {
-                        self.tied_teams.insert(team_id.clone());
+                        self.tied_teams.insert(*team_id);
                     } else {
                         break;
                     }
@@ -528,46 +1762,19 @@ impl TeamPool {
     fn break_by_head_to_head(&mut self) {
         match self.tied_teams.len() {
             tt if tt > 1 => {
-                let mut records: HashMap<i32, (u8, u8, u8)> = HashMap::new();
-                for team_id in &self.tied_teams {
-                    records.insert(team_id.clone(), (0, 0, 0));
-                }
-                for (_, game) in self.games.iter() {
-                    if self.tied_teams.contains(&game.home_team.team_id)
-                        && self.tied_teams.contains(&game.away_team.team_id)
-                    {
-                        match game.game_result {
-                            Some(GameResult::HomeWin) => {
-                                records.get_mut(&game.home_team.team_id).unwrap().0 += 1;
-                                records.get_mut(&game.away_team.team_id).unwrap().1 += 1;
-                            }
-                            Some(GameResult::AwayWin) => {
-                                records.get_mut(&game.home_team.team_id).unwrap().1 += 1;
-                                records.get_mut(&game.away_team.team_id).unwrap().0 += 1;
-                            }
-                            Some(GameResult::Tie) => {
-                                records.get_mut(&game.home_team.team_id).unwrap().2 += 1;
-                                records.get_mut(&game.away_team.team_id).unwrap().2 += 1;
-                            }
-                            None => panic!("Game has no result"),
-                        }
-                    }
-                }
+                let records: HashMap<i32, (u16, u16, u16)> = self.tied_head_to_head_records();
                 let mut working_vec: Vec<(i32, u16)> = Vec::new();
                 for (team_id, record) in records {
-                    working_vec.push((
-                        team_id.clone(),
-                        Season::calculate_percent_from_tuple(record),
-                    ));
+                    working_vec.push((team_id, Season::calculate_percent_from_tuple(record)));
                 }
                 working_vec.sort_by_key(|t| t.1);
                 working_vec.reverse();
 
                 self.tied_teams = HashSet::new();
-                let max_pct = working_vec.get(0).unwrap().1;
+                let max_pct = working_vec.first().unwrap().1;
                 for (team_id, pct) in working_vec {
                     if pct == max_pct {
-                        self.tied_teams.insert(team_id.clone());
+                        self.tied_teams.insert(team_id);
                     } else {
                         break;
                     }
@@ -577,108 +1784,188 @@ impl TeamPool {
         }
     }
 
+    /// Opponents every one of `team_ids` played.
+    fn common_opponents(&self, team_ids: &[i32]) -> HashSet<i32> {
+        let no_opponents: HashSet<i32> = HashSet::new();
+        let mut team_opponents = team_ids.iter().map(|team_id| {
+            self.context
+                .schedule
+                .opponents
+                .get(team_id)
+                .unwrap_or(&no_opponents)
+        });
+        team_opponents
+            .next()
+            .map(|set| {
+                team_opponents.fold(set.clone(), |set1, set2| {
+                    set1.intersection(set2).cloned().collect()
+                })
+            })
+            .unwrap_or_default()
+    }
+
     fn break_by_common_games(&mut self, min_games: u8) {
         match self.tied_teams.len() {
             tt if tt > 1 => {
-                let mut records: HashMap<i32, (u8, u8, u8)> = HashMap::new();
+                let mut records: HashMap<i32, (u16, u16, u16)> = HashMap::new();
                 for team_id in &self.tied_teams {
-                    records.insert(team_id.clone(), (0, 0, 0));
+                    records.insert(*team_id, (0, 0, 0));
                 }
 
-                let mut team_opponents: HashMap<i32, HashSet<i32>> = HashMap::new();
-                for team_id in &self.tied_teams {
-                    team_opponents.insert(team_id.clone(), HashSet::new());
-                }
+                let tied_teams: Vec<i32> = sorted_ids(&self.tied_teams);
+                let common_opponents: HashSet<i32> = self.common_opponents(&tied_teams);
 
-                for (_, game) in self.games.iter() {
-                    if self.tied_teams.contains(&game.home_team.team_id) {
-                        team_opponents
-                            .get_mut(&game.home_team.team_id)
-                            .unwrap()
-                            .insert(game.away_team.team_id.clone());
-                    } else if self.tied_teams.contains(&game.away_team.team_id) {
-                        team_opponents
-                            .get_mut(&game.away_team.team_id)
-                            .unwrap()
-                            .insert(game.home_team.team_id.clone());
+                let mut total_common_games = 0;
+                for team_id in tied_teams.iter() {
+                    for game in self.team_games(*team_id) {
+                        let is_home: bool = game.home_team.team_id == *team_id;
+                        let opponent_id: i32 = match is_home {
+                            true => game.away_team.team_id,
+                            false => game.home_team.team_id,
+                        };
+                        if !common_opponents.contains(&opponent_id) || game.game_result.is_none() {
+                            continue;
+                        }
+                        total_common_games += 1;
+                        let record: &mut (u16, u16, u16) = records.get_mut(team_id).unwrap();
+                        match (&game.game_result, is_home) {
+                            (Some(GameResult::Tie), _) => record.2 += 1,
+                            (Some(GameResult::HomeWin), true)
+                            | (Some(GameResult::AwayWin), false) => record.0 += 1,
+                            (Some(_), _) => record.1 += 1,
+                            (None, _) => {}
+                        }
                     }
                 }
 
-                let mut set_vec: Vec<HashSet<i32>> = Vec::new();
-                for (_, team_opponents_set) in team_opponents.iter() {
-                    set_vec.push(team_opponents_set.clone());
+                let enough_common_games: bool = total_common_games > 0
+                    && records
+                        .values()
+                        .all(|(wins, losses, ties)| wins + losses + ties >= u16::from(min_games));
+                if enough_common_games {
+                    let mut working_vec: Vec<(i32, u16)> = Vec::new();
+                    for (team_id, record) in records {
+                        working_vec.push((team_id, Season::calculate_percent_from_tuple(record)));
+                    }
+                    working_vec.sort_by_key(|t| t.1);
+                    working_vec.reverse();
+
+                    self.tied_teams = HashSet::new();
+                    let max_pct = working_vec.first().unwrap().1;
+                    for (team_id, pct) in working_vec {
+                        if pct == max_pct {
+                            self.tied_teams.insert(team_id);
+                        } else {
+                            break;
+                        }
+                    }
                 }
-                let mut iter = team_opponents.iter();
-                let common_opponents = iter
-                    .next()
-                    .map(|(_, set)| {
-                        iter.fold(set.clone(), |set1, (_, set2)| {
-                            set1.intersection(&set2).cloned().collect()
-                        })
-                    })
-                    .unwrap();
+            }
+            _ => {}
+        }
+    }
 
-                let mut total_common_games = 0;
-                for (_, game) in self.games.iter() {
-                    if self.tied_teams.contains(&game.home_team.team_id)
-                        && common_opponents.contains(&game.away_team.team_id)
-                    {
-                        total_common_games += 1;
-                        match game.game_result {
-                            Some(GameResult::HomeWin) => {
-                                records.get_mut(&game.home_team.team_id).unwrap().0 += 1;
-                            }
-                            Some(GameResult::AwayWin) => {
-                                records.get_mut(&game.home_team.team_id).unwrap().1 += 1;
-                            }
-                            Some(GameResult::Tie) => {
-                                records.get_mut(&game.home_team.team_id).unwrap().2 += 1;
-                            }
-                            None => panic!("Game has no result"),
+    /// Keeps the clubs with the most net points in `games_type` games, leaving out undecided
+    /// games. Skipped unless every game counted has a score, as when games are simulated
+    /// without a `ScoreModel`.
+    fn break_by_net_points(&mut self, games_type: NetPointsGames) {
+        match self.tied_teams.len() {
+            tt if tt > 1 => {
+                let mut net_points: Vec<(i32, i32)> = Vec::new();
+                match games_type {
+                    NetPointsGames::Common => {
+                        let tied_teams: Vec<i32> = sorted_ids(&self.tied_teams);
+                        let common_opponents: HashSet<i32> = self.common_opponents(&tied_teams);
+                        if common_opponents.is_empty() {
+                            return;
                         }
-                    } else if common_opponents.contains(&game.home_team.team_id)
-                        && self.tied_teams.contains(&game.away_team.team_id)
-                    {
-                        total_common_games += 1;
-                        match game.game_result {
-                            Some(GameResult::HomeWin) => {
-                                records.get_mut(&game.away_team.team_id).unwrap().1 += 1;
+                        for team_id in tied_teams.iter() {
+                            let mut net: i32 = 0;
+                            for game in self.team_games(*team_id) {
+                                let is_home: bool = game.home_team.team_id == *team_id;
+                                let opponent_id: i32 = match is_home {
+                                    true => game.away_team.team_id,
+                                    false => game.home_team.team_id,
+                                };
+                                if !common_opponents.contains(&opponent_id)
+                                    || game.game_result.is_none()
+                                {
+                                    continue;
+                                }
+                                let (home_score, away_score): (i32, i32) =
+                                    match game.home_score.zip(game.away_score) {
+                                        Some(scores) => scores,
+                                        None => return,
+                                    };
+                                net += match is_home {
+                                    true => home_score - away_score,
+                                    false => away_score - home_score,
+                                };
                             }
-                            Some(GameResult::AwayWin) => {
-                                records.get_mut(&game.away_team.team_id).unwrap().0 += 1;
-                            }
-                            Some(GameResult::Tie) => {
-                                records.get_mut(&game.away_team.team_id).unwrap().2 += 1;
+                            net_points.push((*team_id, net));
+                        }
+                    }
+                    NetPointsGames::Conference | NetPointsGames::Overall => {
+                        for team_id in self.tied_teams.iter() {
+                            let record: &TeamRecord =
+                                self.context.team_records.get(team_id).unwrap();
+                            if !record.has_all_scores() {
+                                return;
                             }
-                            None => panic!("Game has no result"),
+                            let net: i32 = match games_type {
+                                NetPointsGames::Conference => {
+                                    record.conference_point_differential()
+                                }
+                                _ => record.point_differential(),
+                            };
+                            net_points.push((*team_id, net));
                         }
                     }
                 }
 
-                match total_common_games {
-                    tcg if tcg > min_games => {
-                        let mut working_vec: Vec<(i32, u16)> = Vec::new();
-                        for (team_id, record) in records {
-                            working_vec.push((
-                                team_id.clone(),
-                                Season::calculate_percent_from_tuple(record),
-                            ));
-                        }
-                        working_vec.sort_by_key(|t| t.1);
-                        working_vec.reverse();
-
-                        self.tied_teams = HashSet::new();
-                        let max_pct = working_vec.get(0).unwrap().1;
-                        for (team_id, pct) in working_vec {
-                            if pct == max_pct {
-                                self.tied_teams.insert(team_id.clone());
-                            } else {
-                                break;
-                            }
+                let max_net: i32 = net_points.iter().map(|t| t.1).max().unwrap();
+                self.tied_teams = net_points
+                    .into_iter()
+                    .filter(|(_, net)| *net == max_net)
+                    .map(|(team_id, _)| team_id)
+                    .collect();
+            }
+            _ => {}
+        }
+    }
+
+    /// Keeps the tied clubs with the most touchdowns scored less touchdowns allowed, over all
+    /// their decided games. Leaves the tie alone when any of those games has no touchdown
+    /// counts.
+    fn break_by_net_touchdowns(&mut self) {
+        match self.tied_teams.len() {
+            tt if tt > 1 => {
+                let mut net_touchdowns: Vec<(i32, i32)> = Vec::new();
+                for team_id in sorted_ids(&self.tied_teams) {
+                    let mut net: i32 = 0;
+                    for game in self.team_games(team_id) {
+                        if game.game_result.is_none() {
+                            continue;
                         }
+                        let (home_touchdowns, away_touchdowns): (i32, i32) =
+                            match game.home_touchdowns.zip(game.away_touchdowns) {
+                                Some(touchdowns) => touchdowns,
+                                None => return,
+                            };
+                        net += match game.home_team.team_id == team_id {
+                            true => home_touchdowns - away_touchdowns,
+                            false => away_touchdowns - home_touchdowns,
+                        };
                     }
-                    _ => {}
+                    net_touchdowns.push((team_id, net));
                 }
+
+                let max_net: i32 = net_touchdowns.iter().map(|t| t.1).max().unwrap();
+                self.tied_teams = net_touchdowns
+                    .into_iter()
+                    .filter(|(_, net)| *net == max_net)
+                    .map(|(team_id, _)| team_id)
+                    .collect();
             }
             _ => {}
         }
@@ -689,35 +1976,31 @@ impl TeamPool {
             tt if tt > 1 => {
                 let mut defeated_teams: HashMap<i32, Vec<i32>> = HashMap::new();
                 for team_id in self.tied_teams.iter() {
-                    defeated_teams.insert(team_id.clone(), Vec::new());
+                    defeated_teams.insert(*team_id, Vec::new());
                 }
-                for (_, game) in self.games.iter() {
-                    let home_team = &game.home_team.team_id;
-                    let away_team = &game.away_team.team_id;
-                    if self.tied_teams.contains(home_team)
-                        && game.game_result.as_ref().unwrap() == &GameResult::HomeWin
-                    {
-                        defeated_teams
-                            .get_mut(home_team)
-                            .unwrap()
-                            .push(away_team.clone());
-                    }
-                    if self.tied_teams.contains(away_team)
-                        && game.game_result.as_ref().unwrap() == &GameResult::AwayWin
-                    {
-                        defeated_teams
-                            .get_mut(away_team)
-                            .unwrap()
-                            .push(home_team.clone());
+                for team_id in self.tied_teams.iter() {
+                    for game in self.team_games(*team_id) {
+                        let home_team = &game.home_team.team_id;
+                        let away_team = &game.away_team.team_id;
+                        match (game.game_result.as_ref(), home_team == team_id) {
+                            (Some(GameResult::HomeWin), true) => {
+                                defeated_teams.get_mut(team_id).unwrap().push(*away_team)
+                            }
+                            (Some(GameResult::AwayWin), false) => {
+                                defeated_teams.get_mut(team_id).unwrap().push(*home_team)
+                            }
+                            _ => {}
+                        }
                     }
                 }
 
                 let mut strengths_of_victory: Vec<(i32, u16)> = Vec::new();
                 for team_id in &self.tied_teams {
-                    let mut overall_defeated_team_record: (u8, u8, u8) = (0, 0, 0);
+                    let mut overall_defeated_team_record: (u16, u16, u16) = (0, 0, 0);
 
                     for defeated_team_id in defeated_teams.get(team_id).unwrap().iter() {
                         let record = self
+                            .context
                             .team_records
                             .get(defeated_team_id)
                             .unwrap()
@@ -729,17 +2012,17 @@ impl TeamPool {
 
                     let defeated_team_win_percentage: u16 =
                         Season::calculate_percent_from_tuple(overall_defeated_team_record);
-                    strengths_of_victory.push((team_id.clone(), defeated_team_win_percentage));
+                    strengths_of_victory.push((*team_id, defeated_team_win_percentage));
                 }
 
                 strengths_of_victory.sort_by_key(|t| t.1);
                 strengths_of_victory.reverse();
 
                 self.tied_teams = HashSet::new();
-                let max_pct = strengths_of_victory.get(0).unwrap().1;
+                let max_pct = strengths_of_victory.first().unwrap().1;
                 for (team_id, pct) in strengths_of_victory {
                     if pct == max_pct {
-                        self.tied_teams.insert(team_id.clone());
+                        self.tied_teams.insert(team_id);
                     } else {
                         break;
                     }
@@ -752,51 +2035,25 @@ impl TeamPool {
     fn break_by_strength_of_schedule(&mut self) {
         match self.tied_teams.len() {
             tt if tt > 1 => {
-                let mut opponents: HashMap<i32, Vec<i32>> = HashMap::new();
-                for team_id in self.tied_teams.iter() {
-                    opponents.insert(team_id.clone(), Vec::new());
-                }
-                for (_, game) in self.games.iter() {
-                    let home_team = &game.home_team.team_id;
-                    let away_team = &game.away_team.team_id;
-                    if self.tied_teams.contains(home_team) {
-                        opponents
-                            .get_mut(home_team)
-                            .unwrap()
-                            .push(away_team.clone());
-                    }
-                    if self.tied_teams.contains(away_team) {
-                        opponents
-                            .get_mut(away_team)
-                            .unwrap()
-                            .push(home_team.clone());
-                    }
-                }
-
                 let mut strengths_of_schedule: Vec<(i32, u16)> = Vec::new();
                 for team_id in &self.tied_teams {
-                    let mut overall_opponent_record: (u8, u8, u8) = (0, 0, 0);
-
-                    for opponent_id in opponents.get(team_id).unwrap().iter() {
-                        let record = self.team_records.get(opponent_id).unwrap().overall_record;
-                        overall_opponent_record.0 += record.0;
-                        overall_opponent_record.1 += record.1;
-                        overall_opponent_record.2 += record.2;
-                    }
-
-                    let opponent_win_percentage: u16 =
-                        Season::calculate_percent_from_tuple(overall_opponent_record);
-                    strengths_of_schedule.push((team_id.clone(), opponent_win_percentage));
+                    let opponent_win_percentage: u16 = self
+                        .context
+                        .strengths_of_schedule
+                        .get(team_id)
+                        .unwrap()
+                        .overall_percent();
+                    strengths_of_schedule.push((*team_id, opponent_win_percentage));
                 }
 
                 strengths_of_schedule.sort_by_key(|t| t.1);
                 strengths_of_schedule.reverse();
 
                 self.tied_teams = HashSet::new();
-                let max_pct = strengths_of_schedule.get(0).unwrap().1;
+                let max_pct = strengths_of_schedule.first().unwrap().1;
                 for (team_id, pct) in strengths_of_schedule {
                     if pct == max_pct {
-                        self.tied_teams.insert(team_id.clone());
+                        self.tied_teams.insert(team_id);
                     } else {
                         break;
                     }
@@ -806,36 +2063,45 @@ impl TeamPool {
         }
     }
 
-    fn break_by_random(&mut self) {
-        let tied_teams_vec: Vec<i32> = Vec::from_iter(self.tied_teams.clone());
-        let mut rng: rand::rngs::ThreadRng = rand::thread_rng();
-        let index = rng.gen_range(0..tied_teams_vec.len());
-        let winner = tied_teams_vec.get(index).unwrap().clone();
-        self.tied_teams = HashSet::new();
-        self.tied_teams.insert(winner);
+    fn record_random_tie(&mut self) {
+        if self.tied_teams.len() > 1 {
+            let mut teams: Vec<i32> = Vec::from_iter(self.tied_teams.clone());
+            teams.sort();
+            self.random_ties.push(TieGroup {
+                pool_type: self.pool_type.clone(),
+                place: self.ranking.as_ref().map_or(0, |ranking| ranking.len()) + 1,
+                teams,
+            });
+        }
     }
 
-    fn pick_two_random(&mut self) {
-        let mut tied_teams_vec: Vec<i32> = Vec::from_iter(self.tied_teams.clone());
-        let mut rng: rand::rngs::ThreadRng = rand::thread_rng();
-        let index = rng.gen_range(0..tied_teams_vec.len());
-        let winner1 = tied_teams_vec.get(index).unwrap().clone();
-
-        tied_teams_vec.retain(|team_id| team_id != &winner1);
-        let mut rng: rand::rngs::ThreadRng = rand::thread_rng();
-        let index = rng.gen_range(0..tied_teams_vec.len());
-        let winner2 = tied_teams_vec.get(index).unwrap().clone();
+    /// The coin toss that ends every place's tiebreaking, whether or not the configured
+    /// chains end with one, so a tie they leave is settled at random and recorded in
+    /// `random_ties` instead of by set order.
+    fn settle_by_coin_toss(&mut self, rng: &mut dyn RngCore) -> Result<(), Error> {
+        self.apply_tiebreak_rule(TiebreakRule::CoinToss, rng)
+    }
 
-        self.tied_teams = HashSet::new();
-        self.tied_teams.insert(winner1);
-        self.tied_teams.insert(winner2);
+    fn break_by_random(&mut self, rng: &mut dyn RngCore) {
+        match self.tied_teams.len() {
+            tt if tt > 1 => {
+                self.record_random_tie();
+                let mut tied_teams_vec: Vec<i32> = Vec::from_iter(self.tied_teams.clone());
+                tied_teams_vec.sort();
+                let index = rng.gen_range(0..tied_teams_vec.len());
+                let winner = *tied_teams_vec.get(index).unwrap();
+                self.tied_teams = HashSet::new();
+                self.tied_teams.insert(winner);
+            }
+            _ => {}
+        }
     }
 }
 
 #[derive(Clone, Debug)]
 pub struct Season {
     pub season_year: i32,
-    pub teams: HashMap<i32, Team>,
+    pub teams: HashMap<i32, Arc<Team>>,
     pub conference_mapping: HashMap<String, Vec<i32>>,
     pub division_mapping: HashMap<String, Vec<i32>>,
     pub actual_games: HashMap<i32, Game>,
@@ -845,11 +2111,136 @@ pub struct Season {
     pub current_simulation_games: HashMap<i32, Game>,
     pub current_simulation_result: CurrentSimulationResult,
     pub overall_results: HashMap<SimulationResultLookup, TeamSimulationResults>,
+    pub rng: Box<dyn SimulationRng>,
+    pub seed: Option<u64>,
+    /// Added to the home team's chance of winning under models without a home edge of their
+    /// own; see `home_win_probability`. Simulating with it set and a model that has a home
+    /// edge is an error.
+    pub home_field_advantage: f64,
+    /// Home-field advantage by home team id, used instead of `home_field_advantage` and
+    /// `config.home_field`'s fixed probability. Simulating with it set and a model that has a
+    /// home edge is an error.
+    pub venue_home_advantage: HashMap<i32, f64>,
+    pub config: SimulationConfig,
+    pub modeling_percents: HashMap<i32, f64>,
+    pub elo_model: Option<EloModel>,
+    pub playoff_streaks: HashMap<i32, i32>,
+    pub priors: HashMap<i32, f64>,
+    /// Baseline simulations by division and number of that division's playoff teams.
+    pub division_playoff_counts: HashMap<String, HashMap<u8, i32>>,
+    /// Base seed of the shared per-simulation streams while `config.common_random_numbers`
+    /// is on.
+    pub common_random_seed: Option<u64>,
+    /// Records from decided games, computed once per batch of simulations so that each run only
+    /// adds its simulated games. `None` outside a batch, where records are computed in full.
+    pub base_records: Option<HashMap<i32, TeamRecord>>,
+    /// Games each run of the current batch visits, in order, from `simulation_game_ids`. Set
+    /// once per batch along with a fresh copy of the base games in `current_simulation_games`,
+    /// so that each run only resets and re-simulates these games instead of cloning them all.
+    /// `None` outside a batch, where each run starts from its own copy.
+    pub batch_game_ids: Option<Vec<i32>>,
+    /// Index of the base games' schedule for the tiebreakers, set once per batch. `None`
+    /// outside a batch, where each tiebreak builds its own.
+    pub schedule_index: Option<ScheduleIndex>,
+    /// Memo for `current_record`, keyed by a fingerprint of `actual_games`.
+    pub current_records: RefCell<Option<(u64, HashMap<i32, TeamRecord>)>>,
+    pub game_simulator: Option<Box<dyn GameSimulator>>,
+    /// Counts of the last run of the baseline and every scenario, which `refresh` repeats.
+    pub last_run: Option<RunInfo>,
+    /// Pool used to load and store results. `None` uses `Db::shared`.
+    #[cfg(feature = "postgres")]
+    pub db: Option<Db>,
+    pub progress: Box<dyn ProgressSink>,
 }
 
 impl Season {
-    pub fn new_from_year(season_year: i32) -> Season {
-        let mut season: Season = Season {
+    #[cfg(feature = "postgres")]
+    pub fn new_from_year(season_year: i32) -> Result<Season, Error> {
+        let mut client: PooledClient = connect()?;
+        Season::new_from_year_with_client(season_year, &mut client)
+    }
+
+    /// Loads the season through `db` and keeps the pool for storing and loading results.
+    #[cfg(feature = "postgres")]
+    pub fn new_from_year_with_db(season_year: i32, db: &Db) -> Result<Season, Error> {
+        let mut client: PooledClient = db.get()?;
+        let mut season: Season = Season::new_from_year_with_client(season_year, &mut client)?;
+        season.db = Some(db.clone());
+        Ok(season)
+    }
+
+    #[cfg(feature = "postgres")]
+    pub fn new_from_year_with_client(
+        season_year: i32,
+        client: &mut Client,
+    ) -> Result<Season, Error> {
+        Season::new_from_source(season_year, client)
+    }
+
+    /// Loads the season's teams and schedule from any `DataSource`.
+    pub fn new_from_source(season_year: i32, source: &mut dyn DataSource) -> Result<Season, Error> {
+        let _span = info_span!("load_season", season_year).entered();
+        let mut season: Season = Season::empty(season_year);
+        season.load_teams(source)?;
+        season.load_conference_division_mapping();
+        season.load_games(source)?;
+        season.validate_game_counts()?;
+        debug!(
+            teams = season.teams.len(),
+            games = season.actual_games.len(),
+            "loaded season"
+        );
+        Ok(season)
+    }
+
+    /// Builds a season from teams and regular-season games already in memory. Every game must
+    /// be from the same season, and its teams must be in `teams` unless they are TBD.
+    pub fn from_parts(teams: Vec<Team>, games: Vec<Game>) -> Result<Season, Error> {
+        let season_year: i32 = match games.first() {
+            Some(game) => game.season_year,
+            None => {
+                return Err(Error::InvalidArgument(String::from(
+                    "a season needs at least one game",
+                )))
+            }
+        };
+        let mut season: Season = Season::empty(season_year);
+        for team in teams {
+            season.teams.insert(team.team_id, Arc::new(team));
+        }
+        season.load_conference_division_mapping();
+
+        for mut game in games {
+            if game.season_year != season_year {
+                return Err(Error::InvalidArgument(format!(
+                    "game {} is from season {}, not {}",
+                    game.game_id, game.season_year, season_year
+                )));
+            }
+            for team in [&game.home_team, &game.away_team] {
+                if !team.is_tbd() && season.teams.get(&team.team_id) != Some(team) {
+                    return Err(Error::InvalidArgument(format!(
+                        "game {} has team {}, which doesn't match any of the season's teams",
+                        game.game_id, team.team_id
+                    )));
+                }
+            }
+            // Share the season's copy of each team rather than the game's own.
+            if let Some(team) = season.teams.get(&game.home_team.team_id) {
+                game.home_team = Arc::clone(team);
+            }
+            if let Some(team) = season.teams.get(&game.away_team.team_id) {
+                game.away_team = Arc::clone(team);
+            }
+            season.actual_games.insert(game.game_id, game);
+        }
+        season.current_simulation_base_games = season.actual_games.clone();
+        season.validate_game_counts()?;
+        Ok(season)
+    }
+
+    fn empty(season_year: i32) -> Season {
+        Season {
             season_year,
             teams: HashMap::new(),
             conference_mapping: HashMap::new(),
@@ -861,576 +2252,2662 @@ impl Season {
             current_simulation_games: HashMap::new(),
             current_simulation_result: CurrentSimulationResult::new(),
             overall_results: HashMap::new(),
-        };
+            rng: Box::new(StdRng::from_entropy()),
+            seed: None,
+            home_field_advantage: 0.0,
+            venue_home_advantage: HashMap::new(),
+            config: SimulationConfig::default(),
+            modeling_percents: HashMap::new(),
+            elo_model: None,
+            playoff_streaks: HashMap::new(),
+            priors: HashMap::new(),
+            division_playoff_counts: HashMap::new(),
+            common_random_seed: None,
+            base_records: None,
+            batch_game_ids: None,
+            schedule_index: None,
+            current_records: RefCell::new(None),
+            game_simulator: None,
+            last_run: None,
+            #[cfg(feature = "postgres")]
+            db: None,
+            progress: Box::new(TracingProgress),
+        }
+    }
 
-        season.load_teams();
-        season.load_conference_division_mapping();
-        season.load_games();
-        season
+    #[cfg(feature = "postgres")]
+    pub fn run_all_game_simulations(
+        &mut self,
+        sims: u64,
+        include_decided: bool,
+    ) -> Result<SimulationReport, Error> {
+        self.run_all_game_simulations_with_counts(sims, sims, include_decided)
     }
 
-    pub fn run_all_game_simulations(&mut self, sims: u64, include_decided: bool) {
-        self.set_simulation_id(sims.clone());
+    /// Simulates like `simulate_with_counts` under a new `simulation_id`, inserting each
+    /// scenario's results as soon as it finishes so that an interrupted run can be finished
+    /// with `resume`.
+    #[cfg(feature = "postgres")]
+    pub fn run_all_game_simulations_with_counts(
+        &mut self,
+        baseline_sims: u64,
+        scenario_sims: u64,
+        include_decided: bool,
+    ) -> Result<SimulationReport, Error> {
+        let mut sink: PostgresResultSink = PostgresResultSink::new(self.db()?);
+        self.simulate_into(
+            baseline_sims,
+            scenario_sims,
+            include_decided,
+            &mut [&mut sink],
+        )
+    }
 
-        println!("\n{} - Simulating current season state...", now(),);
-        self.simulate_current_state(sims);
+    /// Simulates like `simulate_with_counts`, sending each scenario's results to every sink as
+    /// soon as it finishes. The first id a sink assigns becomes the `simulation_id`. Sinks
+    /// need `SeedingDetail::FullSeeds` to record division winners.
+    pub fn simulate_into(
+        &mut self,
+        baseline_sims: u64,
+        scenario_sims: u64,
+        include_decided: bool,
+        sinks: &mut [&mut dyn ResultSink],
+    ) -> Result<SimulationReport, Error> {
+        self.simulate_all_scenarios(
+            baseline_sims,
+            scenario_sims,
+            include_decided,
+            None,
+            None,
+            sinks,
+        )
+    }
 
-        let games = self.actual_games.clone();
-        let total_games = games.len();
-        let mut i: u32 = 1;
-        for (game_id, _) in games.iter() {
-            println!(
-                "\n{} - Processing game {} of {} (id: {})...",
-                now(),
-                i,
-                total_games,
-                game_id
-            );
-            i += 1;
-            let actual_game: Game = self.actual_games.get(game_id).unwrap().clone();
+    /// Finishes a stored run that stopped partway, such as after a crash. Scenarios with
+    /// results in `nfl.simulation_results` are kept and the rest are simulated with the run's
+    /// stored counts, each stored as soon as it finishes. A kept baseline has no
+    /// `division_playoff_counts`.
+    #[cfg(feature = "postgres")]
+    pub fn resume(&mut self, simulation_id: i32) -> Result<SimulationReport, Error> {
+        let mut client: PooledClient = self.db()?.get()?;
+        let stored_report: SimulationReport =
+            self.load_results_with_client(simulation_id, &mut client)?;
+        let rows: Vec<Row> = client.query(
+            "
+            SELECT
+                simulations_per_game_result,
+                baseline_simulations,
+                include_decided
+            FROM nfl.simulations
+            WHERE simulation_id=$1;
+        ",
+            &[&simulation_id],
+        )?;
+        drop(client);
+        let row: &Row = match rows.first() {
+            Some(row) => row,
+            None => {
+                return Err(Error::InvalidArgument(format!(
+                    "simulation {simulation_id} does not exist"
+                )))
+            }
+        };
+        let scenario_sims: u64 = count_from_db(row.try_get(0)?)?.unsigned_abs().into();
+        let baseline_sims: u64 = count_from_db(row.try_get(1)?)?.unsigned_abs().into();
+        let include_decided: bool = row.try_get(2)?;
 
-            let mut simulate_scenarios = || {
-                println!("{} - Simulating home win...", now());
-                self.simulate_for_game(game_id.clone(), GameResult::HomeWin, sims);
+        let stored_run: StoredRun = StoredRun {
+            completed: stored_report
+                .results
+                .keys()
+                .map(|lookup| lookup.game_id.zip(lookup.game_result.clone()))
+                .collect(),
+            results: stored_report.results,
+        };
+        let mut sink: PostgresResultSink = PostgresResultSink::resuming(self.db()?, simulation_id);
+        self.simulate_all_scenarios(
+            baseline_sims,
+            scenario_sims,
+            include_decided,
+            None,
+            Some(&stored_run),
+            &mut [&mut sink],
+        )
+    }
 
-                println!("{} - Simulating away win...", now());
-                self.simulate_for_game(game_id.clone(), GameResult::AwayWin, sims);
-
-                println!("{} - Simulating tie...", now());
-                self.simulate_for_game(game_id.clone(), GameResult::Tie, sims);
-            };
-            match actual_game.game_result {
-                Some(_) => match include_decided {
-                    true => simulate_scenarios(),
-                    false => {}
-                },
-                None => {
-                    simulate_scenarios();
-                }
+    /// Live updating for game days: re-reads the games table with `update_results` and, when
+    /// any result changed, runs the baseline and the scenarios again with the last run's
+    /// counts. Games that just went final drop out of the scenarios unless the last run
+    /// included decided games, so each refresh has less to simulate than the one before. A
+    /// stored run is stored again under a new simulation id. Returns `None` when no result
+    /// changed.
+    #[cfg(feature = "postgres")]
+    pub fn refresh(&mut self) -> Result<Option<Refresh>, Error> {
+        let run: RunInfo = match &self.last_run {
+            Some(run) => run.clone(),
+            None => {
+                return Err(Error::InvalidArgument(String::from(
+                    "refresh repeats the last run, and the season has not been simulated",
+                )))
             }
+        };
+        let mut client: PooledClient = self.db()?.get()?;
+        let changed_game_ids: Vec<i32> = self.update_results(&mut *client)?;
+        drop(client);
+        if changed_game_ids.is_empty() {
+            return Ok(None);
         }
-        self.insert_results();
+        let report: SimulationReport = match self.simulation_id {
+            Some(_) => self.run_all_game_simulations_with_counts(
+                run.baseline_sims,
+                run.scenario_sims,
+                run.include_decided,
+            )?,
+            None => self.simulate_with_counts(
+                run.baseline_sims,
+                run.scenario_sims,
+                run.include_decided,
+            )?,
+        };
+        Ok(Some(Refresh {
+            changed_game_ids,
+            report,
+        }))
     }
 
-    pub fn simulate_current_state(&mut self, sims: u64) {
-        for (team_id, _) in self.teams.iter() {
-            let new_lookup = SimulationResultLookup {
-                game_id: None,
-                game_result: None,
-                team_id: team_id.clone(),
+    /// Re-reads the schedule from `source` and takes the results and scores of games whose
+    /// result differs from `actual_games`, such as games that went final since the season was
+    /// loaded. Returns those games' ids in order.
+    pub fn update_results(&mut self, source: &mut dyn DataSource) -> Result<Vec<i32>, Error> {
+        let mut changed_game_ids: Vec<i32> = Vec::new();
+        for scheduled_game in source.fetch_games(self.season_year)? {
+            let game: Game = Game::new_from_scheduled(scheduled_game, &self.teams)?;
+            let actual_game: &mut Game = match self.actual_games.get_mut(&game.game_id) {
+                Some(actual_game) => actual_game,
+                None => continue,
             };
-            self.overall_results
-                .insert(new_lookup, TeamSimulationResults::new());
+            if actual_game.game_result != game.game_result {
+                actual_game.game_result = game.game_result;
+                actual_game.home_score = game.home_score;
+                actual_game.away_score = game.away_score;
+                actual_game.home_touchdowns = game.home_touchdowns;
+                actual_game.away_touchdowns = game.away_touchdowns;
+                changed_game_ids.push(game.game_id);
+            }
         }
-        for _ in 0..sims {
-            self.run_simulation(true);
+        changed_game_ids.sort();
+        if !changed_game_ids.is_empty() {
+            debug!(games = ?changed_game_ids, "updated results");
+            self.current_simulation_base_games = self.actual_games.clone();
         }
+        Ok(changed_game_ids)
     }
 
-    pub fn simulate_for_game(&mut self, game_id: i32, game_result: GameResult, sims: u64) {
-        self.current_simulation_game = Some((game_id.clone(), game_result.clone()));
-        self.current_simulation_base_games = self.actual_games.clone();
-        self.current_simulation_base_games
-            .get_mut(&game_id)
-            .unwrap()
-            .game_result = Some(game_result.clone());
+    pub fn simulate(
+        &mut self,
+        sims: u64,
+        include_decided: bool,
+    ) -> Result<SimulationReport, Error> {
+        self.simulate_with_counts(sims, sims, include_decided)
+    }
 
-        for (team_id, _) in self.teams.iter() {
-            let new_lookup = SimulationResultLookup {
-                game_id: Some(game_id.clone()),
-                game_result: Some(game_result.clone()),
-                team_id: team_id.clone(),
-            };
-            self.overall_results
-                .insert(new_lookup, TeamSimulationResults::new());
-        }
+    /// Runs `baseline_sims` simulations of the current state and `scenario_sims` for each
+    /// pinned game result. Odds are normalized by each lookup's own simulation count.
+    pub fn simulate_with_counts(
+        &mut self,
+        baseline_sims: u64,
+        scenario_sims: u64,
+        include_decided: bool,
+    ) -> Result<SimulationReport, Error> {
+        self.simulate_all_scenarios(
+            baseline_sims,
+            scenario_sims,
+            include_decided,
+            None,
+            None,
+            &mut [],
+        )
+    }
 
-        for _ in 0..sims {
-            self.run_simulation(true);
+    /// Sends a snapshot after the baseline and after each game's scenarios finish, followed by
+    /// the complete report. A disconnected receiver does not stop the simulation.
+    pub fn simulate_streaming(
+        &mut self,
+        sims: u64,
+        include_decided: bool,
+        sender: &Sender<SimulationReport>,
+    ) -> Result<SimulationReport, Error> {
+        self.simulate_all_scenarios(sims, sims, include_decided, Some(sender), None, &mut [])
+    }
+
+    /// Home team's chance of winning, given the game isn't a tie. A `game_simulator` supplies
+    /// the base probability when set. Otherwise it comes from the `elo_model`, or, with
+    /// `config.record_based_probabilities`, the log5 of the two teams' `modeling_percents`, or
+    /// else `config.home_field`'s fixed home-win probability.
+    ///
+    /// Home field is counted once. A `game_simulator` or `elo_model` that includes it, such
+    /// as `HomeField`, `EloModel` or `MarketOdds`, is used as it is, and simulating refuses to
+    /// start if `venue_home_advantage` or `home_field_advantage` is also set. Otherwise the
+    /// home team's entry in `venue_home_advantage`, or else the global
+    /// `home_field_advantage`, is added, and neither at a neutral site.
+    ///
+    /// With no model set, `config.home_field` applies. From highest precedence down: a game's
+    /// own override in `config.home_field`, then the home team's `venue_home_advantage` entry
+    /// added to even odds, then `config.home_field`'s fixed probability. The global
+    /// `home_field_advantage` isn't added under `config.home_field`, which has a home edge of
+    /// its own.
+    pub fn home_win_probability(&self, game: &Game) -> f64 {
+        let home_team_id: i32 = game.home_team.team_id;
+        let home_field_advantage: f64 = match (
+            game.neutral_site,
+            self.venue_home_advantage.get(&home_team_id),
+        ) {
+            (true, _) => 0.0,
+            (false, Some(venue_advantage)) => *venue_advantage,
+            (false, None) => self.home_field_advantage,
+        };
+        let (base_probability, includes_home_field): (f64, bool) = match (
+            &self.game_simulator,
+            &self.elo_model,
+            self.config.record_based_probabilities,
+        ) {
+            (Some(game_simulator), _, _) => (
+                game_simulator.win_prob(game),
+                game_simulator.includes_home_field(),
+            ),
+            (None, Some(elo_model), _) => {
+                (elo_model.win_prob(game), elo_model.includes_home_field())
+            }
+            (None, None, true) => {
+                let prior: f64 = self.config.shrinkage_prior;
+                let home_percent: f64 = *self
+                    .modeling_percents
+                    .get(&game.home_team.team_id)
+                    .unwrap_or(&prior);
+                let away_percent: f64 = *self
+                    .modeling_percents
+                    .get(&game.away_team.team_id)
+                    .unwrap_or(&prior);
+                (log5(home_percent, away_percent), false)
+            }
+            (None, None, false) => {
+                let home_field: &HomeField = &self.config.home_field;
+                match (
+                    home_field.game_overrides.contains_key(&game.game_id),
+                    self.venue_home_advantage.contains_key(&home_team_id),
+                ) {
+                    (false, true) => (0.5, false),
+                    _ => (home_field.win_prob(game), home_field.includes_home_field()),
+                }
+            }
+        };
+        match includes_home_field {
+            true => base_probability,
+            false => (base_probability + home_field_advantage).clamp(0.0, 1.0),
         }
     }
 
-    pub fn run_simulation(&mut self, increment: bool) {
-        self.current_simulation_result = CurrentSimulationResult::new();
-        self.current_simulation_games = self.current_simulation_base_games.clone();
-        for game_item in self.current_simulation_games.iter_mut() {
-            let game: &mut Game = game_item.1;
-            game.simulate_if_undecided();
+    /// Errors when `venue_home_advantage` or `home_field_advantage` is set alongside a
+    /// `game_simulator` or `elo_model` that already includes home field, which would leave
+    /// them ignored.
+    fn check_home_field_settings(&self) -> Result<(), Error> {
+        let model_with_home_field: Option<&str> = match (&self.game_simulator, &self.elo_model) {
+            (Some(game_simulator), _) if game_simulator.includes_home_field() => {
+                Some("the game simulator")
+            }
+            (None, Some(_)) => Some("the Elo model"),
+            _ => None,
+        };
+        let home_field_set: bool =
+            !self.venue_home_advantage.is_empty() || self.home_field_advantage != 0.0;
+        match (model_with_home_field, home_field_set) {
+            (Some(model), true) => Err(Error::InvalidArgument(format!(
+                "{} already includes home field, so venue_home_advantage and \
+                 home_field_advantage can't be set",
+                model
+            ))),
+            _ => Ok(()),
         }
-        self.evaluate_simulation_results(increment);
     }
 
-    fn evaluate_simulation_results(&mut self, increment: bool) {
-        self.populate_records();
-        self.calculate_percentages();
-        self.evaluate_divisions();
-        self.evaluate_division_winner_playoff_seedings();
-        self.evaluate_wildcards();
-        self.evaluate_draft_order();
-        match increment {
-            true => self.increment_overall_results(),
-            false => {}
+    /// Records among `team_ids` from the current simulation's games, or from the base games
+    /// when no simulation has run. Each entry is the first team's record against the second;
+    /// pairs that never played are 0-0-0 and undecided games are not counted.
+    pub fn head_to_head_matrix(&self, team_ids: &[i32]) -> HashMap<(i32, i32), (u16, u16, u16)> {
+        let games: &HashMap<i32, Game> = match self.current_simulation_games.is_empty() {
+            true => &self.current_simulation_base_games,
+            false => &self.current_simulation_games,
         };
+        head_to_head_matrix(games.values(), team_ids)
     }
 
-    fn populate_records(&mut self) {
-        for (team_id, _) in self.teams.iter() {
-            self.current_simulation_result
-                .team_records
-                .insert(team_id.clone(), TeamRecord::new());
-        }
-        for (_, game) in self.current_simulation_games.iter() {
-            let (winning_team, losing_team): (Option<i32>, Option<i32>) = {
-                if game.game_result == Some(GameResult::HomeWin) {
-                    (
-                        Some(game.home_team.team_id.clone()),
-                        Some(game.away_team.team_id.clone()),
-                    )
-                } else if game.game_result == Some(GameResult::AwayWin) {
-                    (
-                        Some(game.away_team.team_id.clone()),
-                        Some(game.home_team.team_id.clone()),
-                    )
-                } else if game.game_result == Some(GameResult::Tie) {
-                    (None, None)
-                } else {
-                    panic!("Game not simulated yet");
-                }
+    /// Checks every intra-division pairing against `config.division_schedule` and returns the
+    /// pairings with missing or extra games, sorted by team ids.
+    pub fn validate_schedule_symmetry(&self) -> Vec<ScheduleIssue> {
+        let mut found_games: HashMap<(i32, i32), u8> = HashMap::new();
+        for game in self.actual_games.values() {
+            if game.has_tbd_team() || game.home_team.division != game.away_team.division {
+                continue;
+            }
+            let home_team_id: i32 = game.home_team.team_id;
+            let away_team_id: i32 = game.away_team.team_id;
+            let pairing: (i32, i32) = match self.config.division_schedule {
+                DivisionSchedulePattern::HomeAndHome(_) => (home_team_id, away_team_id),
+                DivisionSchedulePattern::Meetings(_) => (
+                    home_team_id.min(away_team_id),
+                    home_team_id.max(away_team_id),
+                ),
             };
+            *found_games.entry(pairing).or_insert(0) += 1;
+        }
 
-            match winning_team {
-                Some(team_id) => {
-                    let record = self
-                        .current_simulation_result
-                        .team_records
-                        .get_mut(&team_id)
-                        .unwrap();
-                    record.overall_record.0 += 1;
-                    if game.conference_game {
-                        record.conference_record.0 += 1;
-                    }
-                    if game.division_game {
-                        record.division_record.0 += 1;
-                    }
-                }
-                None => {
-                    let team_id = game.home_team.team_id;
-                    let record = self
-                        .current_simulation_result
-                        .team_records
-                        .get_mut(&team_id)
-                        .unwrap();
-                    record.overall_record.2 += 1;
-                    if game.conference_game {
-                        record.conference_record.2 += 1;
-                    }
-                    if game.division_game {
-                        record.division_record.2 += 1;
-                    }
-                }
-            };
-            match losing_team {
-                Some(team_id) => {
-                    let record = self
-                        .current_simulation_result
-                        .team_records
-                        .get_mut(&team_id)
-                        .unwrap();
-                    record.overall_record.1 += 1;
-                    if game.conference_game {
-                        record.conference_record.1 += 1;
-                    }
-                    if game.division_game {
-                        record.division_record.1 += 1;
-                    }
-                }
-                None => {
-                    let team_id = game.away_team.team_id;
-                    let record = self
-                        .current_simulation_result
-                        .team_records
-                        .get_mut(&team_id)
-                        .unwrap();
-                    record.overall_record.2 += 1;
-                    if game.conference_game {
-                        record.conference_record.2 += 1;
-                    }
-                    if game.division_game {
-                        record.division_record.2 += 1;
+        let mut issues: Vec<ScheduleIssue> = Vec::new();
+        for (_, team_ids) in self.division_mapping.iter() {
+            for team_id in team_ids {
+                for opponent_id in team_ids {
+                    let expected: u8 = match self.config.division_schedule {
+                        _ if team_id == opponent_id => continue,
+                        DivisionSchedulePattern::HomeAndHome(games) => games,
+                        DivisionSchedulePattern::Meetings(_) if team_id > opponent_id => continue,
+                        DivisionSchedulePattern::Meetings(games) => games,
+                    };
+                    let found: u8 = *found_games.get(&(*team_id, *opponent_id)).unwrap_or(&0);
+                    if found != expected {
+                        issues.push(ScheduleIssue {
+                            team_id: *team_id,
+                            opponent_id: *opponent_id,
+                            expected,
+                            found,
+                        });
                     }
                 }
-            };
+            }
         }
+
+        issues.sort_by_key(|issue| (issue.team_id, issue.opponent_id));
+        issues
     }
 
-    fn calculate_percentages(&mut self) {
-        for (_, record) in self.current_simulation_result.team_records.iter_mut() {
-            record.overall_percent = Self::calculate_percent_from_tuple(record.overall_record);
-            record.conference_percent =
-                Self::calculate_percent_from_tuple(record.conference_record);
-            record.division_percent = Self::calculate_percent_from_tuple(record.division_record);
+    /// Recomputes `modeling_percents` from the decided games in `actual_games`.
+    pub fn refresh_modeling_percents(&mut self) {
+        let mut records: HashMap<i32, (u16, u16, u16)> = self
+            .teams
+            .keys()
+            .map(|team_id| (*team_id, (0, 0, 0)))
+            .collect();
+        for game in self.actual_games.values() {
+            if game.has_tbd_team() {
+                continue;
+            }
+            let (home_result, away_result) = match game.game_result {
+                Some(GameResult::HomeWin) => ((1, 0, 0), (0, 1, 0)),
+                Some(GameResult::AwayWin) => ((0, 1, 0), (1, 0, 0)),
+                Some(GameResult::Tie) => ((0, 0, 1), (0, 0, 1)),
+                None => continue,
+            };
+            for (team_id, (wins, losses, ties)) in [
+                (game.home_team.team_id, home_result),
+                (game.away_team.team_id, away_result),
+            ] {
+                let record = records.entry(team_id).or_insert((0, 0, 0));
+                record.0 += wins;
+                record.1 += losses;
+                record.2 += ties;
+            }
         }
-    }
 
-    pub fn calculate_percent_from_tuple(record_tuple: (u8, u8, u8)) -> u16 {
-        let (wins, losses, ties) = record_tuple;
-        let wins: u32 = u32::from(wins);
-        let losses: u32 = u32::from(losses);
-        let ties: u32 = u32::from(ties);
-        let computed_wins: u32 = (wins * 1000) + ((ties * 1000) / 2);
+        self.modeling_percents = records
+            .into_iter()
+            .map(|(team_id, record)| {
+                let modeling_percent: f64 = match self.priors.get(&team_id) {
+                    Some(prior) => self.config.modeling_percent_with_prior(record, *prior),
+                    None => self.config.modeling_percent(record),
+                };
+                (team_id, modeling_percent)
+            })
+            .collect();
+    }
 
-        let total_games = wins + losses + ties;
-        let win_percent: u16;
-        match total_games {
-            tg if tg != 0 => {
-                win_percent = u16::try_from(computed_wins / (wins + losses + ties)).unwrap();
+    /// Games in `week` ranked by the average playoff leverage of their two teams, most
+    /// flex-worthy first. Uses the scenarios from the last simulation; games without scenario
+    /// results are left out.
+    pub fn flex_candidates(&self, week: i32) -> Vec<(i32, f64)> {
+        let report: SimulationReport = self.report();
+        let mut candidates: Vec<(i32, f64)> = Vec::new();
+        for game in self.actual_games.values() {
+            if game.week != week || game.has_tbd_team() {
+                continue;
             }
-            _ => {
-                win_percent = 0;
+            let home_leverage = report.playoff_leverage(game.game_id, game.home_team.team_id);
+            let away_leverage = report.playoff_leverage(game.game_id, game.away_team.team_id);
+            if let (Some(home_leverage), Some(away_leverage)) = (home_leverage, away_leverage) {
+                candidates.push((game.game_id, (home_leverage + away_leverage) / 2.0));
             }
         }
-
-        win_percent
+        candidates.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        candidates
     }
 
-    fn evaluate_divisions(&mut self) {
-        for (_, team_ids) in self.division_mapping.iter() {
-            let mut team_pool: TeamPool = TeamPool::new(team_ids.clone(), PoolType::Division, self);
-            team_pool.evaluate();
-            self.current_simulation_result
-                .division_winners
-                .insert(team_pool.winner.unwrap());
+    /// For each other game in `week`, which side `team_id` should root for to improve its
+    /// playoff odds, from the last simulation's scenarios. Games that swing the odds the most
+    /// come first; games without scenario results are left out.
+    pub fn rooting_guide(&self, team_id: i32, week: i32) -> Vec<RootingInterest> {
+        let report: SimulationReport = self.report();
+        let mut guide: Vec<RootingInterest> = Vec::new();
+        for game in self.actual_games.values() {
+            if game.week != week
+                || game.has_tbd_team()
+                || game.home_team.team_id == team_id
+                || game.away_team.team_id == team_id
+            {
+                continue;
+            }
+            let (home_win, away_win) = match (
+                report.scenario_odds(team_id, game.game_id, GameResult::HomeWin),
+                report.scenario_odds(team_id, game.game_id, GameResult::AwayWin),
+            ) {
+                (Some(home_win), Some(away_win)) => (home_win, away_win),
+                _ => continue,
+            };
+            let root_for: Option<i32> = match home_win.make_playoffs - away_win.make_playoffs {
+                difference if difference > 0.0 => Some(game.home_team.team_id),
+                difference if difference < 0.0 => Some(game.away_team.team_id),
+                _ => None,
+            };
+            guide.push(RootingInterest {
+                game_id: game.game_id,
+                root_for,
+                make_playoffs_home_win: home_win.make_playoffs,
+                make_playoffs_away_win: away_win.make_playoffs,
+            });
         }
+        guide.sort_by(|a, b| {
+            let a_swing: f64 = (a.make_playoffs_home_win - a.make_playoffs_away_win).abs();
+            let b_swing: f64 = (b.make_playoffs_home_win - b.make_playoffs_away_win).abs();
+            b_swing.total_cmp(&a_swing).then(a.game_id.cmp(&b.game_id))
+        });
+        guide
     }
 
-    fn evaluate_division_winner_playoff_seedings(&mut self) {
-        for (_, team_ids) in self.conference_mapping.iter() {
-            let mut division_winners: Vec<i32> = Vec::new();
-            for team_id in team_ids {
-                if self
-                    .current_simulation_result
-                    .division_winners
-                    .contains(team_id)
-                {
-                    division_winners.push(team_id.clone());
-                }
+    /// A team's record from decided `actual_games`. Records for every team are computed
+    /// together and reused until a game's teams or result change.
+    pub fn current_record(&self, team_id: i32) -> Result<TeamRecord, Error> {
+        let fingerprint: u64 = self.actual_games_fingerprint();
+        let mut current_records = self.current_records.borrow_mut();
+        let is_stale: bool = match current_records.as_ref() {
+            Some((cached_fingerprint, _)) => *cached_fingerprint != fingerprint,
+            None => true,
+        };
+        if is_stale {
+            let mut team_records: HashMap<i32, TeamRecord> = self.empty_records();
+            for game in self.actual_games.values() {
+                record_game(&mut team_records, game);
             }
-            let mut team_pool =
-                TeamPool::new(division_winners, PoolType::DivisionWinnerSeeding, self);
-            team_pool.evaluate();
-            let mut playoff_seed = 1;
-            for team_id in team_pool.ranking.unwrap() {
-                self.current_simulation_result
-                    .playoff_seeding
-                    .get_mut(&playoff_seed)
-                    .unwrap()
-                    .insert(team_id);
-                playoff_seed += 1;
+            for record in team_records.values_mut() {
+                record.update_percentages();
             }
+            *current_records = Some((fingerprint, team_records));
+        }
+        match current_records
+            .as_ref()
+            .and_then(|(_, team_records)| team_records.get(&team_id))
+        {
+            Some(record) => Ok(record.clone()),
+            None => Err(Error::InvalidArgument(format!(
+                "team {team_id} is not in the {} season",
+                self.season_year
+            ))),
+        }
+    }
+
+    fn actual_games_fingerprint(&self) -> u64 {
+        let mut games: Vec<&Game> = self.actual_games.values().collect();
+        games.sort_by_key(|game| game.game_id);
+        let mut hasher: DefaultHasher = DefaultHasher::new();
+        for game in games {
+            (
+                game.game_id,
+                game.home_team.team_id,
+                game.away_team.team_id,
+                &game.game_result,
+                game.division_game,
+                game.conference_game,
+            )
+                .hash(&mut hasher);
         }
+        hasher.finish()
     }
 
-    fn evaluate_wildcards(&mut self) {
-        for (_, team_ids) in self.conference_mapping.iter() {
-            let mut team_ids_without_division_winners = team_ids.clone();
+    #[cfg(feature = "postgres")]
+    fn db(&self) -> Result<Db, Error> {
+        match &self.db {
+            Some(db) => Ok(db.clone()),
+            None => Ok(Db::shared()?.clone()),
+        }
+    }
 
-            team_ids_without_division_winners.retain(|team_id| {
-                !self
-                    .current_simulation_result
-                    .division_winners
-                    .contains(team_id)
-            });
+    /// Regular-season games per team, from the schedule.
+    pub fn games_per_team(&self) -> u16 {
+        let (game_counts, _) = self.scheduled_game_counts();
+        game_counts.into_values().max().unwrap_or(0)
+    }
 
-            let mut team_pool: TeamPool = TeamPool::new(
-                team_ids_without_division_winners.clone(),
-                PoolType::Wildcard,
-                self,
-            );
-            team_pool.evaluate();
-            let mut playoff_seed = 5;
-            for team_id in team_pool.ranking.unwrap() {
-                self.current_simulation_result
-                    .wildcard_teams
-                    .insert(team_id);
-                self.current_simulation_result
-                    .playoff_seeding
-                    .get_mut(&playoff_seed)
-                    .unwrap()
-                    .insert(team_id);
-                playoff_seed += 1;
+    /// Games scheduled for each team, and the number of game slots held by TBD teams.
+    fn scheduled_game_counts(&self) -> (HashMap<i32, u16>, u16) {
+        let mut game_counts: HashMap<i32, u16> =
+            self.teams.keys().map(|team_id| (*team_id, 0)).collect();
+        let mut tbd_slots: u16 = 0;
+        for game in self.actual_games.values() {
+            for team in [&game.home_team, &game.away_team] {
+                match game_counts.get_mut(&team.team_id) {
+                    Some(count) if !team.is_tbd() => *count += 1,
+                    _ => tbd_slots += 1,
+                }
             }
         }
+        (game_counts, tbd_slots)
     }
 
-    fn evaluate_draft_order(&mut self) {
-        let mut teams: HashSet<i32> = self.teams.keys().cloned().collect();
-        for (_, teams_set) in self.current_simulation_result.playoff_seeding.iter() {
-            for team_id in teams_set.iter() {
-                teams.remove(team_id);
-            }
+    /// Every team must play as many games as the team with the most. Teams short of that are
+    /// allowed only when TBD slots make up exactly the missing games.
+    fn validate_game_counts(&self) -> Result<(), Error> {
+        let (game_counts, tbd_slots) = self.scheduled_game_counts();
+        let games_per_team: u16 = game_counts.values().copied().max().unwrap_or(0);
+        let mut short_teams: Vec<(&i32, &u16)> = game_counts
+            .iter()
+            .filter(|(_, count)| **count < games_per_team)
+            .collect();
+        short_teams.sort();
+        let missing_games: u16 = short_teams
+            .iter()
+            .map(|(_, count)| games_per_team - **count)
+            .sum();
+        match missing_games == tbd_slots {
+            true => Ok(()),
+            false => Err(Error::InvalidData(format!(
+                "teams should play {} games, but {} are missing and {} have a TBD team: {}",
+                games_per_team,
+                missing_games,
+                tbd_slots,
+                short_teams
+                    .iter()
+                    .map(|(team_id, count)| format!(
+                        "{} plays {}",
+                        self.teams[team_id].abbreviation, count
+                    ))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ))),
         }
+    }
 
-        let mut team_pool: TeamPool =
-            TeamPool::new(Vec::from_iter(teams), PoolType::DraftOrder, self);
-        team_pool.evaluate();
-        let mut draft_position = 1;
-        for team_id in team_pool.ranking.unwrap() {
-            self.current_simulation_result
-                .draft_order
-                .insert(draft_position, team_id);
-            draft_position += 1;
+    pub fn playoff_format(&self) -> PlayoffFormat {
+        self.config
+            .playoff_format
+            .unwrap_or(PlayoffFormat::for_season(self.season_year))
+    }
+
+    /// Teams ordered by team id, for iteration that has to be repeatable.
+    pub fn teams_sorted(&self) -> Vec<&Team> {
+        let mut teams: Vec<&Team> = self.teams.values().map(|team| team.as_ref()).collect();
+        teams.sort_by_key(|team| team.team_id);
+        teams
+    }
+
+    fn sorted_team_ids(&self) -> Vec<i32> {
+        self.teams_sorted()
+            .iter()
+            .map(|team| team.team_id)
+            .collect()
+    }
+
+    /// Projected win rates (for example preseason win totals divided by games) blended with
+    /// each team's record to seed game probabilities. Turns on
+    /// `config.record_based_probabilities`; the prior's weight is `config.shrinkage_games`.
+    pub fn set_priors(&mut self, priors: HashMap<i32, f64>) {
+        self.priors = priors;
+        self.config.record_based_probabilities = true;
+    }
+
+    /// Current consecutive playoff appearances per team, reported alongside the odds as
+    /// `TeamOdds::streak_continues_pct`. Teams left out are treated as having no streak.
+    pub fn set_playoff_streaks(&mut self, playoff_streaks: HashMap<i32, i32>) {
+        self.playoff_streaks = playoff_streaks;
+    }
+
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng = Box::new(StdRng::seed_from_u64(seed));
+        self.seed = Some(seed);
+    }
+
+    /// Replaces the default entropy-seeded generator, which every simulated game and coin toss
+    /// draws from.
+    pub fn with_rng<R: SimulationRng + 'static>(mut self, rng: R) -> Season {
+        self.rng = Box::new(rng);
+        self.seed = None;
+        self
+    }
+
+    /// Replaces the built-in choice between Elo, record-based and even odds. Home-field
+    /// advantage is added on top of the model's probability unless the model includes it.
+    pub fn with_game_simulator<G: GameSimulator + 'static>(mut self, game_simulator: G) -> Season {
+        self.game_simulator = Some(Box::new(game_simulator));
+        self
+    }
+
+    /// Sends progress through the batch runs to `progress` instead of `tracing` events.
+    pub fn with_progress<P: ProgressSink + 'static>(mut self, progress: P) -> Season {
+        self.progress = Box::new(progress);
+        self
+    }
+
+    /// Runs the full simulation once per seed, for estimating Monte Carlo error across
+    /// reports with `SimulationReport::aggregate`.
+    pub fn simulate_batch(
+        &mut self,
+        seeds: &[u64],
+        sims_each: u64,
+    ) -> Result<Vec<SimulationReport>, Error> {
+        seeds
+            .iter()
+            .map(|seed| {
+                self.set_seed(*seed);
+                self.simulate(sims_each, false)
+            })
+            .collect()
+    }
+
+    pub fn report(&self) -> SimulationReport {
+        SimulationReport {
+            season_year: self.season_year,
+            simulation_id: self.simulation_id,
+            seed: self.seed,
+            teams: self.teams.clone(),
+            conference_mapping: self.conference_mapping.clone(),
+            division_mapping: self.division_mapping.clone(),
+            playoff_streaks: self.playoff_streaks.clone(),
+            division_playoff_counts: self.division_playoff_counts.clone(),
+            results: self.overall_results.clone(),
+            complete: false,
         }
     }
 
-    fn increment_overall_results(&mut self) {
-        let simulation_game: Option<&(i32, GameResult)> = self.current_simulation_game.as_ref();
-        let current_result = &self.current_simulation_result;
-        for team_id in current_result.division_winners.iter() {
-            let lookup = SimulationResultLookup::new(team_id, simulation_game);
-            match self.overall_results.get_mut(&lookup) {
-                Some(result) => {
-                    result.division_winner += 1;
+    /// Diagnostic: runs `simulate_current_state` and the baseline of `simulate` on separate
+    /// copies of the season, with independent random streams. Returns `Error::Inconsistent`
+    /// listing every team whose playoff or division odds differ by more than four standard
+    /// errors, or the error of a simulation that fails.
+    pub fn check_baseline_consistency(&self, sims: u64) -> Result<(), Error> {
+        let mut current_state: Season = self.clone();
+        let mut full_run: Season = self.clone();
+        full_run.rng = Box::new(StdRng::seed_from_u64(current_state.rng.next_u64()));
+
+        current_state.simulate_current_state(sims)?;
+        let current_state_report: SimulationReport = current_state.report();
+        let full_run_report: SimulationReport = full_run.simulate(sims, false)?;
+
+        let mut discrepancies: Vec<String> = Vec::new();
+        for team_id in self.sorted_team_ids() {
+            let (current_state_odds, full_run_odds) = match (
+                current_state_report.team_odds(team_id),
+                full_run_report.team_odds(team_id),
+            ) {
+                (Some(current_state_odds), Some(full_run_odds)) => {
+                    (current_state_odds, full_run_odds)
                 }
-                None => panic!("Overall results not initialized properly"),
-            }
-        }
-        for team_id in current_result.wildcard_teams.iter() {
-            let lookup = SimulationResultLookup::new(team_id, simulation_game);
-            match self.overall_results.get_mut(&lookup) {
-                Some(result) => {
-                    result.wildcard_team += 1;
+                _ => {
+                    discrepancies.push(format!("team {}: missing baseline results", team_id));
+                    continue;
                 }
-                None => panic!("Overall results not initialized properly"),
-            }
-        }
-        for (seed_number, teams) in current_result.playoff_seeding.iter() {
-            for team_id in teams.iter() {
-                let lookup = SimulationResultLookup::new(team_id, simulation_game);
-                match self.overall_results.get_mut(&lookup) {
-                    Some(result) => {
-                        result.playoff_seedings.insert(
-                            seed_number.clone(),
-                            result.playoff_seedings.get(seed_number).unwrap() + 1,
-                        );
-                    }
-                    None => panic!("Overall results not initialized properly"),
+            };
+            for (label, p1, n1, p2, n2) in [
+                (
+                    "make playoffs",
+                    current_state_odds.make_playoffs,
+                    current_state_odds.simulations,
+                    full_run_odds.make_playoffs,
+                    full_run_odds.simulations,
+                ),
+                (
+                    "win division",
+                    current_state_odds.win_division,
+                    current_state_odds.simulations,
+                    full_run_odds.win_division,
+                    full_run_odds.simulations,
+                ),
+            ] {
+                let (n1, n2): (f64, f64) = (f64::from(n1.max(1)), f64::from(n2.max(1)));
+                let standard_error: f64 = (p1 * (1.0 - p1) / n1 + p2 * (1.0 - p2) / n2)
+                    .sqrt()
+                    .max(1.0 / n1.min(n2));
+                if (p1 - p2).abs() > 4.0 * standard_error {
+                    discrepancies.push(format!(
+                        "team {}: {} {:.4} vs {:.4}",
+                        team_id, label, p1, p2
+                    ));
                 }
             }
         }
-        for (draft_position, team_id) in current_result.draft_order.iter() {
-            let lookup = SimulationResultLookup::new(team_id, simulation_game);
-            match self.overall_results.get_mut(&lookup) {
-                Some(result) => {
-                    result.draft_positions.insert(
-                        draft_position.clone(),
-                        result.draft_positions.get(draft_position).unwrap() + 1,
-                    );
-                }
-                None => panic!("Overall results not initialized properly"),
-            }
+
+        match discrepancies.is_empty() {
+            true => Ok(()),
+            false => Err(Error::Inconsistent(discrepancies)),
         }
     }
 
-    fn load_teams(&mut self) {
-        let query: String = format!(
-            "
-            SELECT
-                team_id,
-                abbreviation,
-                name,
-                conference,
-                division
-            FROM nfl.teams
-            WHERE team_id in (
-                SELECT DISTINCT home_team_id
-                FROM nfl.games
-                WHERE season={0}
-            )
-            ORDER BY division, abbreviation;
-        ",
-            self.season_year,
-        );
+    /// Runs the baseline and then each game's scenarios, sending each scenario to `sinks` as
+    /// soon as it finishes. With `stored_run`, scenarios it already completed are skipped,
+    /// keeping their stored results.
+    fn simulate_all_scenarios(
+        &mut self,
+        baseline_sims: u64,
+        scenario_sims: u64,
+        include_decided: bool,
+        sender: Option<&Sender<SimulationReport>>,
+        stored_run: Option<&StoredRun>,
+        sinks: &mut [&mut dyn ResultSink],
+    ) -> Result<SimulationReport, Error> {
+        if !sinks.is_empty() && self.config.seeding_detail != SeedingDetail::FullSeeds {
+            return Err(Error::InvalidArgument(String::from(
+                "stored results need SeedingDetail::FullSeeds to record division winners",
+            )));
+        }
+        let run: RunInfo = RunInfo {
+            season_year: self.season_year,
+            baseline_sims,
+            scenario_sims,
+            include_decided,
+        };
+        let mut assigned_id: Option<i32> = None;
+        for sink in sinks.iter_mut() {
+            assigned_id = assigned_id.or(sink.start(&run)?);
+        }
+        self.last_run = Some(run);
+        if assigned_id.is_some() {
+            self.simulation_id = assigned_id;
+        }
+        self.overall_results = match stored_run {
+            Some(stored_run) => stored_run.results.clone(),
+            None => HashMap::new(),
+        };
+        self.current_simulation_game = None;
+        self.current_simulation_base_games = self.actual_games.clone();
+        self.common_random_seed = match self.config.common_random_numbers {
+            true => Some(self.rng.next_u64()),
+            false => None,
+        };
 
-        for row in run_query(query) {
-            let team: Team = Team::new_from_db_row(row);
-            self.teams.insert(team.team_id, team);
+        if !stored_run.is_some_and(|stored_run| stored_run.completed.contains(&None)) {
+            self.simulate_current_state(baseline_sims)?;
+            self.store_scenario(sinks, None)?;
+            self.progress
+                .on_scenario_done(None, &self.scenario_team_odds(None));
+        }
+        if let Some(sender) = sender {
+            let _ = sender.send(self.report());
         }
-    }
 
-    fn load_conference_division_mapping(&mut self) {
-        for (_, team) in self.teams.iter() {
-            if !self.conference_mapping.contains_key(&team.conference) {
-                self.conference_mapping
-                    .insert(team.conference.clone(), Vec::new());
+        let mut game_ids: Vec<i32> = self.actual_games.keys().copied().collect();
+        game_ids.sort();
+        let total_games = game_ids.len();
+        for (i, game_id) in (1_usize..).zip(game_ids.iter()) {
+            let actual_game: Game = self.actual_games.get(game_id).unwrap().clone();
+            self.progress.on_game_started(&actual_game, i, total_games);
+            if actual_game.has_tbd_team() || (actual_game.game_result.is_some() && !include_decided)
+            {
+                continue;
             }
 
-            let conference_vector: &mut Vec<i32> =
-                self.conference_mapping.get_mut(&team.conference).unwrap();
-            conference_vector.push(team.team_id.clone());
+            for game_result in [GameResult::HomeWin, GameResult::AwayWin, GameResult::Tie] {
+                let scenario: Option<(i32, GameResult)> = Some((*game_id, game_result.clone()));
+                if stored_run.is_some_and(|stored_run| stored_run.completed.contains(&scenario)) {
+                    continue;
+                }
+                self.simulate_for_game(*game_id, game_result.clone(), scenario_sims)?;
+                self.store_scenario(sinks, Some((*game_id, &game_result)))?;
+                let scenario: Option<(i32, &GameResult)> = Some((*game_id, &game_result));
+                self.progress
+                    .on_scenario_done(scenario, &self.scenario_team_odds(scenario));
+            }
 
-            if !self.division_mapping.contains_key(&team.division) {
-                self.division_mapping
-                    .insert(team.division.clone(), Vec::new());
+            if let Some(sender) = sender {
+                let _ = sender.send(self.report());
             }
+        }
 
-            let division_vector: &mut Vec<i32> =
-                self.division_mapping.get_mut(&team.division).unwrap();
-            division_vector.push(team.team_id.clone());
+        for sink in sinks.iter_mut() {
+            sink.finish()?;
+        }
+        let mut report: SimulationReport = self.report();
+        report.complete = true;
+        if let Some(sender) = sender {
+            let _ = sender.send(report.clone());
         }
+        Ok(report)
     }
 
-    fn load_games(&mut self) {
-        let query: String = format!(
-            "
-            SELECT
-                game_id,
-                season,
-                week,
-                home_team_id,
-                away_team_id,
-                home_score,
-                away_score
-            FROM nfl.games
-            WHERE
-                season={0}
-                AND game_type='REG';
-        ",
-            self.season_year,
-        );
-
-        let results: Vec<Row> = run_query(query);
+    /// Every team's odds from the results of one scenario, for `ProgressSink::on_scenario_done`.
+    fn scenario_team_odds(&self, scenario: Option<(i32, &GameResult)>) -> HashMap<i32, TeamOdds> {
+        let scenario: Option<(i32, GameResult)> =
+            scenario.map(|(game_id, game_result)| (game_id, game_result.clone()));
+        self.teams
+            .keys()
+            .filter_map(|team_id| {
+                let lookup = SimulationResultLookup::new(team_id, scenario.as_ref());
+                let results: &TeamSimulationResults = self.overall_results.get(&lookup)?;
+                let playoff_streak: i32 = *self.playoff_streaks.get(team_id).unwrap_or(&0);
+                Some((
+                    *team_id,
+                    TeamOdds::new_from_results(*team_id, results)
+                        .with_playoff_streak(playoff_streak),
+                ))
+            })
+            .collect()
+    }
 
-        for row in results {
-            let game: Game = Game::new_from_db_row(row, self.teams.clone());
-            self.actual_games.insert(game.game_id.clone(), game);
+    pub fn simulate_current_state(&mut self, sims: u64) -> Result<(), Error> {
+        let _span = info_span!("scenario", sims).entered();
+        for team_id in self.sorted_team_ids() {
+            let new_lookup = SimulationResultLookup {
+                game_id: None,
+                game_result: None,
+                team_id,
+            };
+            self.overall_results
+                .insert(new_lookup, TeamSimulationResults::new());
         }
-
-        self.current_simulation_base_games = self.actual_games.clone();
+        self.division_playoff_counts = HashMap::new();
+        self.run_simulations(sims)
     }
 
-    pub fn set_simulation_id(&mut self, sims: u64) {
-        // Insert new simulation into db and add simulation_id to Season struct
-        let statement = format!(
-            "
-                INSERT INTO  nfl.simulations
-                VALUES (
-                    DEFAULT,
-                    NOW(),
-                    {},
-                    {}
-                )
-            ",
-            self.season_year, sims,
-        );
-        execute(statement);
+    pub fn simulate_for_game(
+        &mut self,
+        game_id: i32,
+        game_result: GameResult,
+        sims: u64,
+    ) -> Result<(), Error> {
+        let _span = info_span!("scenario", game_id, %game_result, sims).entered();
+        if !self.actual_games.contains_key(&game_id) {
+            return Err(Error::InvalidArgument(format!(
+                "game {game_id} is not in the {} season",
+                self.season_year
+            )));
+        }
+        self.current_simulation_game = Some((game_id, game_result.clone()));
+        self.current_simulation_base_games = self.actual_games.clone();
+        if let Some(game) = self.current_simulation_base_games.get_mut(&game_id) {
+            game.force_result(game_result.clone());
+        }
 
-        let query = String::from(
-            "
-            SELECT MAX(simulation_id)
-            FROM nfl.simulations;
-        ",
-        );
+        for team_id in self.sorted_team_ids() {
+            let new_lookup = SimulationResultLookup {
+                game_id: Some(game_id),
+                game_result: Some(game_result.clone()),
+                team_id,
+            };
+            self.overall_results
+                .insert(new_lookup, TeamSimulationResults::new());
+        }
 
-        let results: Vec<Row> = run_query(query);
+        self.run_simulations(sims)
+    }
 
-        for row in results {
-            self.simulation_id = Some(row.get(0));
+    /// Simulates the current state with every game in `overrides` pinned to its result, such as
+    /// one team winning out while a rival loses two of its last three. The results replace the
+    /// baseline in `overall_results` and are the returned report's baseline; `actual_games` is
+    /// unchanged. A game listed twice keeps its last result.
+    pub fn simulate_with_overrides(
+        &mut self,
+        overrides: Vec<(i32, GameResult)>,
+        sims: u64,
+    ) -> Result<SimulationReport, Error> {
+        let mut base_games: HashMap<i32, Game> = self.actual_games.clone();
+        for (game_id, game_result) in overrides {
+            match base_games.get_mut(&game_id) {
+                Some(game) if game.has_tbd_team() => {
+                    return Err(Error::InvalidArgument(format!(
+                        "game {game_id} has an undetermined team"
+                    )))
+                }
+                Some(game) => {
+                    game.force_result(game_result);
+                    game.is_simulated = true;
+                }
+                None => {
+                    return Err(Error::InvalidArgument(format!(
+                        "game {game_id} is not in the {} season",
+                        self.season_year
+                    )))
+                }
+            }
         }
+
+        self.overall_results = HashMap::new();
+        self.current_simulation_game = None;
+        self.current_simulation_base_games = base_games;
+        let simulated: Result<(), Error> = self.simulate_current_state(sims);
+        self.current_simulation_base_games = self.actual_games.clone();
+        simulated?;
+        Ok(self.report())
     }
 
-    fn insert_results(&self) {
-        // Insert all results in self.overall_results into database
-        println!("\n{} - Inserting results...", now(),);
-        let mut new_rows: Vec<String> = Vec::new();
-        for (lookup, result) in self.overall_results.iter() {
-            let simulation_id = self.simulation_id.unwrap();
-            let game_id: String = match lookup.game_id {
-                Some(gid) => format!("{gid}"),
-                None => String::from("NULL"),
-            };
-            let simulated_game_result = match &lookup.game_result {
-                Some(gr) => match gr {
-                    GameResult::HomeWin => String::from("'home win'"),
-                    GameResult::AwayWin => String::from("'away win'"),
-                    GameResult::Tie => String::from("'tie'"),
-                },
-                None => String::from("NULL"),
-            };
-            let simulation_team_id = lookup.team_id;
-            let mut results: HashMap<(String, u8), i32> = HashMap::new();
-            for (seed_number, occurences) in result.playoff_seedings.iter() {
-                results.insert(
-                    (String::from("playoff seed"), seed_number.clone()),
-                    occurences.clone(),
-                );
-            }
-            for (draft_position, occurences) in result.draft_positions.iter() {
-                results.insert(
-                    (String::from("draft position"), draft_position.clone()),
-                    occurences.clone(),
-                );
-            }
-
-            for ((result_set, team_rank), simulations_with_rank) in results.iter() {
-                let new_row: String = format!(
-                    "(DEFAULT,{simulation_id},{game_id},{simulated_game_result},{simulation_team_id},'{result_set}',{team_rank},{simulations_with_rank})",
-                );
-                new_rows.push(new_row);
-            }
-        }
-        let statement: String = format!(
-            "INSERT INTO nfl.simulation_results
-            VALUES {}",
-            new_rows.join(","),
-        );
-        execute(statement);
-        println!("\n{} - Finished", now(),);
+    /// How the current batch of `sims` simulations will be played out: `SimulationMode::Exact`
+    /// when few enough games are undecided, `sims` covers every combination of their results,
+    /// and the Elo model doesn't update within a simulation.
+    pub fn simulation_mode(&self, sims: u64) -> SimulationMode {
+        let undecided_games: usize = self.undecided_game_ids().len();
+        let elo_updates: bool = self
+            .elo_model
+            .as_ref()
+            .is_some_and(|elo_model| elo_model.update_in_simulation);
+        let exact: bool = undecided_games <= self.config.exact_threshold.min(MAX_EXACT_GAMES)
+            && sims >= 1 << undecided_games
+            && !elo_updates;
+        match exact {
+            true => SimulationMode::Exact,
+            false => SimulationMode::MonteCarlo,
+        }
     }
-}
 
-fn get_variable(key: &str) -> String {
-    match var(key) {
-        Ok(val) => val,
-        Err(err) => panic!("{}", err),
+    fn run_simulations(&mut self, sims: u64) -> Result<(), Error> {
+        self.check_home_field_settings()?;
+        self.base_records = Some(self.decided_base_records());
+        self.current_simulation_games = self.current_simulation_base_games.clone();
+        self.batch_game_ids = Some(self.simulation_game_ids());
+        self.schedule_index = Some(ScheduleIndex::new(&self.current_simulation_base_games));
+        let sims_run: Result<u64, Error> = match self.simulation_mode(sims) {
+            SimulationMode::Exact => self.run_exact_simulations(sims).map(|_| sims),
+            SimulationMode::MonteCarlo => self.run_monte_carlo_simulations(sims),
+        };
+        self.base_records = None;
+        self.batch_game_ids = None;
+        self.schedule_index = None;
+        self.progress.on_sims_completed(sims_run?);
+        Ok(())
     }
-}
 
-fn get_conn_string() -> String {
-    let pg_locn: String = get_variable("PG_LOCN");
-    let pg_dtbs: String = get_variable("PG_DTBS");
-    let pg_user: String = get_variable("PG_USER");
-    let pg_pass: String = get_variable("PG_PASS");
+    /// Runs up to `sims` simulations of the current batch, checking `config.stopping_rule`
+    /// after every `check_every` of them when it is set, and returns how many ran.
+    fn run_monte_carlo_simulations(&mut self, sims: u64) -> Result<u64, Error> {
+        let step: u64 = match &self.config.stopping_rule {
+            Some(stopping_rule) => stopping_rule.check_every.max(1),
+            None => sims,
+        };
+        let mut sims_run: u64 = 0;
+        while sims_run < sims {
+            let sim_indices: Range<u64> = sims_run..sims.min(sims_run + step);
+            sims_run = sim_indices.end;
+            match self.config.parallel_workers {
+                0 | 1 => self.run_simulation_range(sim_indices)?,
+                workers => self.run_parallel_simulations(sim_indices, workers)?,
+            }
+            if self.stopping_rule_met() {
+                break;
+            }
+        }
+        Ok(sims_run)
+    }
+
+    /// Whether the current lookups' playoff odds meet `config.stopping_rule`; always `false`
+    /// without one.
+    fn stopping_rule_met(&self) -> bool {
+        let stopping_rule: &StoppingRule = match &self.config.stopping_rule {
+            Some(stopping_rule) => stopping_rule,
+            None => return false,
+        };
+        let simulation_game: Option<&(i32, GameResult)> = self.current_simulation_game.as_ref();
+        let team_ids: Vec<i32> = match stopping_rule.team_ids.is_empty() {
+            true => self.teams.keys().copied().collect(),
+            false => stopping_rule.team_ids.clone(),
+        };
+        team_ids.iter().all(|team_id| {
+            match self
+                .overall_results
+                .get(&SimulationResultLookup::new(team_id, simulation_game))
+            {
+                Some(results) => {
+                    results.playoff_standard_error() <= stopping_rule.max_standard_error
+                }
+                None => true,
+            }
+        })
+    }
+
+    /// Undecided games of the current batch that can be simulated, by game id.
+    fn undecided_game_ids(&self) -> Vec<i32> {
+        let mut game_ids: Vec<i32> = self
+            .current_simulation_base_games
+            .values()
+            .filter(|game| game.game_result.is_none() && !game.has_tbd_team())
+            .map(|game| game.game_id)
+            .collect();
+        game_ids.sort();
+        game_ids
+    }
+
+    /// Evaluates each combination of home and away wins of the undecided games once, weighted
+    /// by its share of `sims`. Ties are never evaluated. `simulation_mode` only picks this when
+    /// `sims` is at least the number of combinations, but a combination whose share still
+    /// rounds to zero is skipped, so results are exact up to rounding.
+    fn run_exact_simulations(&mut self, sims: u64) -> Result<(), Error> {
+        if self.config.record_based_probabilities {
+            self.refresh_modeling_percents();
+        }
+        let game_ids: Vec<i32> = self.undecided_game_ids();
+        let home_win_probabilities: Vec<f64> = game_ids
+            .iter()
+            .map(|game_id| {
+                self.home_win_probability(self.current_simulation_base_games.get(game_id).unwrap())
+            })
+            .collect();
+        let probabilities: Vec<f64> = (0..1_usize << game_ids.len())
+            .map(|outcome| {
+                home_win_probabilities
+                    .iter()
+                    .enumerate()
+                    .map(|(i, p)| match outcome >> i & 1 {
+                        1 => *p,
+                        _ => 1.0 - p,
+                    })
+                    .product()
+            })
+            .collect();
+
+        let offset: f64 = self.rng.gen();
+        for (outcome, weight) in apportion(&probabilities, sims, offset)
+            .into_iter()
+            .enumerate()
+        {
+            if weight == 0 {
+                continue;
+            }
+            for (i, game_id) in game_ids.iter().enumerate() {
+                let game: &mut Game = self.current_simulation_games.get_mut(game_id).unwrap();
+                game.game_result = match outcome >> i & 1 {
+                    1 => Some(GameResult::HomeWin),
+                    _ => Some(GameResult::AwayWin),
+                };
+                game.is_simulated = true;
+            }
+            self.current_simulation_result = CurrentSimulationResult::new();
+            self.evaluate_simulation_results(false)?;
+            self.increment_overall_results(weight)?;
+        }
+        Ok(())
+    }
+
+    /// Runs simulations `sim_indices` of the current batch. The indices only matter for common
+    /// random numbers, where they pick each simulation's stream.
+    fn run_simulation_range(&mut self, sim_indices: Range<u64>) -> Result<(), Error> {
+        match self.common_random_seed {
+            Some(seed) => {
+                let rng: Box<dyn SimulationRng> = self.rng.clone();
+                let mut result: Result<(), Error> = Ok(());
+                for i in sim_indices {
+                    self.rng = Box::new(StdRng::seed_from_u64(seed.wrapping_add(i)));
+                    result = self.run_simulation(true);
+                    if result.is_err() {
+                        break;
+                    }
+                }
+                self.rng = rng;
+                result
+            }
+            None => {
+                for _ in sim_indices {
+                    self.run_simulation(true)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Splits simulations `sim_indices` across `workers` copies of the season, each with fresh
+    /// accumulators for the current lookups, and adds their counts back into
+    /// `overall_results` and `division_playoff_counts` once all of them finish.
+    fn run_parallel_simulations(
+        &mut self,
+        sim_indices: Range<u64>,
+        workers: u64,
+    ) -> Result<(), Error> {
+        let first: u64 = sim_indices.start;
+        let sims: u64 = sim_indices.end.saturating_sub(first);
+        let mut overall_results: HashMap<SimulationResultLookup, TeamSimulationResults> =
+            std::mem::take(&mut self.overall_results);
+        let mut division_playoff_counts: HashMap<String, HashMap<u8, i32>> =
+            std::mem::take(&mut self.division_playoff_counts);
+        let simulation_game: Option<&(i32, GameResult)> = self.current_simulation_game.as_ref();
+        let lookups: Vec<SimulationResultLookup> = self
+            .sorted_team_ids()
+            .iter()
+            .map(|team_id| SimulationResultLookup::new(team_id, simulation_game))
+            .collect();
+
+        let mut seasons: Vec<Season> = Vec::new();
+        let mut sim_ranges: Vec<Range<u64>> = Vec::new();
+        for worker in 0..workers {
+            let mut season: Season = self.clone();
+            season.rng = Box::new(StdRng::seed_from_u64(self.rng.next_u64()));
+            season.overall_results = lookups
+                .iter()
+                .map(|lookup| (lookup.clone(), TeamSimulationResults::new()))
+                .collect();
+            seasons.push(season);
+            sim_ranges.push(first + sims * worker / workers..first + sims * (worker + 1) / workers);
+        }
+        let outcomes: Vec<Result<(), Error>> = seasons
+            .par_iter_mut()
+            .zip(sim_ranges)
+            .map(|(season, sim_indices)| season.run_simulation_range(sim_indices))
+            .collect();
+        if let Some(error) = outcomes.into_iter().find_map(Result::err) {
+            self.overall_results = overall_results;
+            self.division_playoff_counts = division_playoff_counts;
+            return Err(error);
+        }
+
+        for season in seasons.iter() {
+            for (lookup, results) in season.overall_results.iter() {
+                match overall_result(&mut overall_results, lookup) {
+                    Ok(result) => result.add(results),
+                    Err(error) => {
+                        self.overall_results = overall_results;
+                        self.division_playoff_counts = division_playoff_counts;
+                        return Err(error);
+                    }
+                }
+            }
+            for (division, counts) in season.division_playoff_counts.iter() {
+                let division_counts: &mut HashMap<u8, i32> =
+                    division_playoff_counts.entry(division.clone()).or_default();
+                for (playoff_teams, count) in counts.iter() {
+                    *division_counts.entry(*playoff_teams).or_insert(0) += count;
+                }
+            }
+        }
+        self.overall_results = overall_results;
+        self.division_playoff_counts = division_playoff_counts;
+        if let Some(season) = seasons.pop() {
+            self.current_simulation_games = season.current_simulation_games;
+            self.current_simulation_result = season.current_simulation_result;
+        }
+        Ok(())
+    }
+
+    /// Testing/analysis tool, not a realistic simulation: overrides the results of every game
+    /// involving `team_ids` so that each of those teams finishes with exactly `target_record`,
+    /// which reliably drives the tiebreaker chain. Games not involving those teams are left as
+    /// they are and are still simulated as usual by `run_simulation`. Errors when a team's
+    /// schedule can't produce `target_record`.
+    pub fn force_tie_scenario(
+        &mut self,
+        team_ids: &[i32],
+        target_record: (u16, u16, u16),
+    ) -> Result<(), Error> {
+        let targets: HashSet<i32> = team_ids.iter().copied().collect();
+        let target_games: u16 = target_record.0 + target_record.1 + target_record.2;
+
+        let mut game_ids: Vec<i32> = self.actual_games.keys().copied().collect();
+        game_ids.sort();
+
+        let mut shared_games: Vec<(i32, i32, i32)> = Vec::new();
+        let mut other_games: Vec<(i32, i32, i32)> = Vec::new();
+        let mut games_played: HashMap<i32, u16> = HashMap::new();
+        for game in game_ids
+            .iter()
+            .filter_map(|game_id| self.actual_games.get(game_id))
+        {
+            if game.has_tbd_team() {
+                continue;
+            }
+            let game_id: i32 = game.game_id;
+            let home_team: i32 = game.home_team.team_id;
+            let away_team: i32 = game.away_team.team_id;
+            let matchup: (i32, i32, i32) = (game_id, home_team, away_team);
+            match (targets.contains(&home_team), targets.contains(&away_team)) {
+                (true, true) => shared_games.push(matchup),
+                (true, false) | (false, true) => other_games.push(matchup),
+                (false, false) => continue,
+            }
+            for team_id in [home_team, away_team] {
+                if targets.contains(&team_id) {
+                    *games_played.entry(team_id).or_insert(0) += 1;
+                }
+            }
+        }
+
+        for team_id in targets.iter() {
+            let played: u16 = games_played.get(team_id).copied().unwrap_or(0);
+            if played != target_games {
+                return Err(Error::InvalidArgument(format!(
+                    "team {} plays {} games, which cannot produce a {}-{}-{} record",
+                    team_id, played, target_record.0, target_record.1, target_record.2
+                )));
+            }
+        }
+
+        let mut records: HashMap<i32, (u16, u16, u16)> = targets
+            .iter()
+            .map(|team_id| (*team_id, (0, 0, 0)))
+            .collect();
+        let mut assignments: Vec<GameResult> = Vec::new();
+        if !Self::assign_forced_results(
+            &shared_games,
+            &mut records,
+            target_record,
+            &mut assignments,
+        ) {
+            return Err(Error::InvalidArgument(String::from(
+                "no combination of results gives every team the target record",
+            )));
+        }
+
+        self.current_simulation_game = None;
+        self.current_simulation_base_games = self.actual_games.clone();
+        let mut forced_results: Vec<(i32, GameResult)> = shared_games
+            .iter()
+            .map(|(game_id, _, _)| *game_id)
+            .zip(assignments)
+            .collect();
+        for (game_id, home_team, away_team) in other_games {
+            let home_is_target: bool = targets.contains(&home_team);
+            let team_id: i32 = if home_is_target { home_team } else { away_team };
+            let record: &mut (u16, u16, u16) = records.entry(team_id).or_insert((0, 0, 0));
+            let game_result: GameResult = if record.0 < target_record.0 {
+                record.0 += 1;
+                match home_is_target {
+                    true => GameResult::HomeWin,
+                    false => GameResult::AwayWin,
+                }
+            } else if record.1 < target_record.1 {
+                record.1 += 1;
+                match home_is_target {
+                    true => GameResult::AwayWin,
+                    false => GameResult::HomeWin,
+                }
+            } else {
+                record.2 += 1;
+                GameResult::Tie
+            };
+            forced_results.push((game_id, game_result));
+        }
+        for (game_id, game_result) in forced_results {
+            if let Some(game) = self.current_simulation_base_games.get_mut(&game_id) {
+                game.force_result(game_result);
+                game.is_simulated = true;
+            }
+        }
+        Ok(())
+    }
+
+    fn assign_forced_results(
+        matchups: &[(i32, i32, i32)],
+        records: &mut HashMap<i32, (u16, u16, u16)>,
+        target_record: (u16, u16, u16),
+        assignments: &mut Vec<GameResult>,
+    ) -> bool {
+        let (_, home_team, away_team) = match matchups.get(assignments.len()) {
+            Some(matchup) => *matchup,
+            None => return true,
+        };
+
+        // Try handing the win to whichever team still needs more wins first
+        let home_record: (u16, u16, u16) = *records.get(&home_team).unwrap();
+        let away_record: (u16, u16, u16) = *records.get(&away_team).unwrap();
+        let candidates: [GameResult; 3] = match home_record.0 >= away_record.0 {
+            true => [GameResult::AwayWin, GameResult::HomeWin, GameResult::Tie],
+            false => [GameResult::HomeWin, GameResult::AwayWin, GameResult::Tie],
+        };
+
+        for game_result in candidates {
+            let (home_change, away_change): ((u16, u16, u16), (u16, u16, u16)) = match game_result {
+                GameResult::HomeWin => ((1, 0, 0), (0, 1, 0)),
+                GameResult::AwayWin => ((0, 1, 0), (1, 0, 0)),
+                GameResult::Tie => ((0, 0, 1), (0, 0, 1)),
+            };
+            let new_home: (u16, u16, u16) = (
+                home_record.0 + home_change.0,
+                home_record.1 + home_change.1,
+                home_record.2 + home_change.2,
+            );
+            let new_away: (u16, u16, u16) = (
+                away_record.0 + away_change.0,
+                away_record.1 + away_change.1,
+                away_record.2 + away_change.2,
+            );
+            let within_target = |record: (u16, u16, u16)| {
+                record.0 <= target_record.0
+                    && record.1 <= target_record.1
+                    && record.2 <= target_record.2
+            };
+            if !within_target(new_home) || !within_target(new_away) {
+                continue;
+            }
+
+            records.insert(home_team, new_home);
+            records.insert(away_team, new_away);
+            assignments.push(game_result);
+            if Self::assign_forced_results(matchups, records, target_record, assignments) {
+                return true;
+            }
+            assignments.pop();
+            records.insert(home_team, home_record);
+            records.insert(away_team, away_record);
+        }
+
+        false
+    }
+
+    /// Runs the full tiebreaker chain on `current_simulation_base_games`, which must all be
+    /// decided, and returns every group of teams the chain could only separate at random.
+    pub fn undecidable_ties(&self) -> Result<Vec<TieGroup>, Error> {
+        if self
+            .current_simulation_base_games
+            .values()
+            .any(|game| game.game_result.is_none() && !game.has_tbd_team())
+        {
+            return Err(Error::InvalidArgument(String::from(
+                "undecidable ties require every game to have a result",
+            )));
+        }
+
+        let mut season: Season = self.clone();
+        season.current_simulation_result = CurrentSimulationResult::new();
+        season.current_simulation_games = season.current_simulation_base_games.clone();
+        season.evaluate_simulation_results(false)?;
+        Ok(season.current_simulation_result.random_ties)
+    }
+
+    pub fn tbd_games(&self) -> Vec<i32> {
+        let mut game_ids: Vec<i32> = self
+            .actual_games
+            .values()
+            .filter(|game| game.has_tbd_team())
+            .map(|game| game.game_id)
+            .collect();
+        game_ids.sort();
+        game_ids
+    }
+
+    /// Fills in (or replaces) the teams of a scheduled game, e.g. once a TBD opponent is known.
+    pub fn set_game_teams(
+        &mut self,
+        game_id: i32,
+        home_team_id: i32,
+        away_team_id: i32,
+    ) -> Result<(), Error> {
+        if !self.actual_games.contains_key(&game_id) {
+            return Err(Error::InvalidArgument(format!(
+                "game {game_id} is not in the {} season",
+                self.season_year
+            )));
+        }
+        let team = |team_id: i32| -> Result<Arc<Team>, Error> {
+            match self.teams.get(&team_id) {
+                Some(team) => Ok(Arc::clone(team)),
+                None => Err(Error::InvalidArgument(format!(
+                    "team {team_id} is not in the {} season",
+                    self.season_year
+                ))),
+            }
+        };
+        let home_team: Arc<Team> = team(home_team_id)?;
+        let away_team: Arc<Team> = team(away_team_id)?;
+        let (division_game, conference_game) = Game::matchup_flags(&home_team, &away_team);
+
+        for games in [
+            &mut self.actual_games,
+            &mut self.current_simulation_base_games,
+        ] {
+            if let Some(game) = games.get_mut(&game_id) {
+                game.home_team = Arc::clone(&home_team);
+                game.away_team = Arc::clone(&away_team);
+                game.division_game = division_game;
+                game.conference_game = conference_game;
+            }
+        }
+        Ok(())
+    }
+
+    /// Decides (or, with `None`, reopens) a scheduled game, so later simulations treat it as
+    /// played. Scenarios already simulated are unaffected.
+    pub fn set_game_result(
+        &mut self,
+        game_id: i32,
+        game_result: Option<GameResult>,
+    ) -> Result<(), Error> {
+        if !self.actual_games.contains_key(&game_id) {
+            return Err(Error::InvalidArgument(format!(
+                "game {game_id} is not in the {} season",
+                self.season_year
+            )));
+        }
+        for games in [
+            &mut self.actual_games,
+            &mut self.current_simulation_base_games,
+        ] {
+            if let Some(game) = games.get_mut(&game_id) {
+                match &game_result {
+                    Some(game_result) => game.force_result(game_result.clone()),
+                    None => {
+                        game.game_result = None;
+                        game.home_score = None;
+                        game.away_score = None;
+                        game.home_touchdowns = None;
+                        game.away_touchdowns = None;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn run_simulation(&mut self, increment: bool) -> Result<(), Error> {
+        self.current_simulation_result = CurrentSimulationResult::new();
+        if self.config.record_based_probabilities {
+            self.refresh_modeling_percents();
+        }
+        let in_batch: bool = self.batch_game_ids.is_some();
+        let game_ids: Vec<i32> = match self.batch_game_ids.take() {
+            Some(game_ids) => game_ids,
+            None => {
+                self.current_simulation_games = self.current_simulation_base_games.clone();
+                self.simulation_game_ids()
+            }
+        };
+        let mut games: HashMap<i32, Game> = std::mem::take(&mut self.current_simulation_games);
+        let preseason_elo_model: Option<EloModel> = match &self.elo_model {
+            Some(elo_model) if elo_model.update_in_simulation => Some(elo_model.clone()),
+            _ => None,
+        };
+        for game_id in game_ids.iter() {
+            let (game, base_game): (&mut Game, &Game) = match (
+                games.get_mut(game_id),
+                self.current_simulation_base_games.get(game_id),
+            ) {
+                (Some(game), Some(base_game)) => (game, base_game),
+                _ => {
+                    self.current_simulation_games = games;
+                    return Err(Error::InvalidData(format!(
+                        "game {} is not in the current simulation",
+                        game_id
+                    )));
+                }
+            };
+            game.game_result = base_game.game_result.clone();
+            game.home_score = base_game.home_score;
+            game.away_score = base_game.away_score;
+            game.home_touchdowns = base_game.home_touchdowns;
+            game.away_touchdowns = base_game.away_touchdowns;
+            game.is_simulated = base_game.is_simulated;
+            let home_win_probability: f64 = self.home_win_probability(game);
+            let score_model: Option<&ScoreModel> = self.config.score_model.as_ref();
+            let is_pinned: bool = game.game_result.is_some();
+            if is_pinned && self.common_random_seed.is_some() {
+                // Use up pinned games' draws so later games stay in step with the baseline.
+                if let Some(actual_game) = self.actual_games.get(game_id) {
+                    let mut actual_game: Game = actual_game.clone();
+                    actual_game.simulate_in_mode(
+                        self.rng.as_mut(),
+                        home_win_probability,
+                        score_model,
+                    );
+                }
+            }
+            let is_undecided: bool = game.game_result.is_none();
+            game.simulate_in_mode(self.rng.as_mut(), home_win_probability, score_model);
+            if let (true, Some(elo_model)) = (
+                is_undecided && game.game_result.is_some() && preseason_elo_model.is_some(),
+                self.elo_model.as_mut(),
+            ) {
+                elo_model.update(game);
+            }
+        }
+        self.current_simulation_games = games;
+        if in_batch {
+            self.batch_game_ids = Some(game_ids);
+        }
+        let evaluated: Result<(), Error> = self.evaluate_simulation_results(increment);
+        if preseason_elo_model.is_some() {
+            self.elo_model = preseason_elo_model;
+        }
+        evaluated
+    }
+
+    /// Games of `current_simulation_base_games` that `run_simulation` visits, in the order it
+    /// simulates them: by id, or by week and id when the Elo model updates within a simulation.
+    /// Decided games draw nothing and are left out, except pinned ones (decided in the base
+    /// games but not in `actual_games`), whose draws common random numbers use up.
+    fn simulation_game_ids(&self) -> Vec<i32> {
+        let mut game_ids: Vec<i32> = self
+            .current_simulation_base_games
+            .values()
+            .filter(|game| {
+                !game.has_tbd_team()
+                    && (game.game_result.is_none()
+                        || self
+                            .actual_games
+                            .get(&game.game_id)
+                            .is_some_and(|actual_game| actual_game.game_result.is_none()))
+            })
+            .map(|game| game.game_id)
+            .collect();
+        match self
+            .elo_model
+            .as_ref()
+            .is_some_and(|elo_model| elo_model.update_in_simulation)
+        {
+            true => game_ids.sort_by_key(|game_id| {
+                (
+                    self.current_simulation_base_games
+                        .get(game_id)
+                        .unwrap()
+                        .week,
+                    *game_id,
+                )
+            }),
+            false => game_ids.sort(),
+        }
+        game_ids
+    }
+
+    fn evaluate_simulation_results(&mut self, increment: bool) -> Result<(), Error> {
+        self.populate_records()?;
+        self.calculate_percentages();
+        self.calculate_strengths_of_schedule();
+        self.evaluate_divisions()?;
+        if self.config.seeding_detail == SeedingDetail::FullSeeds {
+            self.evaluate_division_winner_playoff_seedings()?;
+        }
+        self.evaluate_wildcards()?;
+        self.evaluate_draft_order()?;
+        if self.config.seeding_detail == SeedingDetail::FullSeeds {
+            let playoff_bracket: PlayoffBracket = self.simulate_playoff_bracket()?;
+            self.evaluate_playoff_draft_order(&playoff_bracket)?;
+            self.current_simulation_result.playoff_bracket = Some(playoff_bracket);
+        }
+        if increment {
+            self.increment_overall_results(1)?;
+        }
+        Ok(())
+    }
+
+    fn populate_records(&mut self) -> Result<(), Error> {
+        let mut team_records: HashMap<i32, TeamRecord> = match &self.base_records {
+            Some(base_records) => base_records.clone(),
+            None => self.empty_records(),
+        };
+        for (game_id, game) in self.current_simulation_games.iter() {
+            if self.base_records.is_some()
+                && self
+                    .current_simulation_base_games
+                    .get(game_id)
+                    .is_some_and(|base_game| base_game.game_result.is_some())
+            {
+                continue;
+            }
+            if game.game_result.is_none() && !game.has_tbd_team() {
+                return Err(Error::InvalidData(format!(
+                    "game {} has not been simulated",
+                    game_id
+                )));
+            }
+            record_game(&mut team_records, game);
+        }
+        self.current_simulation_result.team_records = team_records;
+        Ok(())
+    }
+
+    /// Records from the games already decided in `current_simulation_base_games`, which stay
+    /// the same in every simulation.
+    fn decided_base_records(&self) -> HashMap<i32, TeamRecord> {
+        let mut team_records: HashMap<i32, TeamRecord> = self.empty_records();
+        for game in self.current_simulation_base_games.values() {
+            if game.game_result.is_some() {
+                record_game(&mut team_records, game);
+            }
+        }
+        team_records
+    }
+
+    fn empty_records(&self) -> HashMap<i32, TeamRecord> {
+        self.teams
+            .keys()
+            .map(|team_id| (*team_id, TeamRecord::new()))
+            .collect()
+    }
+
+    fn calculate_percentages(&mut self) {
+        for (_, record) in self.current_simulation_result.team_records.iter_mut() {
+            record.update_percentages();
+        }
+    }
+
+    /// Each team's `StrengthOfSchedule` from the final records, counting an opponent once per
+    /// game. A game counts as played when it was decided in `current_simulation_base_games`.
+    fn calculate_strengths_of_schedule(&mut self) {
+        let built_index: ScheduleIndex;
+        let schedule: &ScheduleIndex = match &self.schedule_index {
+            Some(schedule_index) => schedule_index,
+            None => {
+                built_index = ScheduleIndex::new(&self.current_simulation_games);
+                &built_index
+            }
+        };
+        let team_records: &HashMap<i32, TeamRecord> = &self.current_simulation_result.team_records;
+        let mut strengths_of_schedule: HashMap<i32, StrengthOfSchedule> = HashMap::new();
+        for team_id in self.teams.keys() {
+            let mut strength_of_schedule: StrengthOfSchedule = StrengthOfSchedule::default();
+            for game_id in schedule.team_games.get(team_id).into_iter().flatten() {
+                let game: &Game = self.current_simulation_games.get(game_id).unwrap();
+                let opponent_id: i32 = match game.home_team.team_id == *team_id {
+                    true => game.away_team.team_id,
+                    false => game.home_team.team_id,
+                };
+                let record: (u16, u16, u16) =
+                    team_records.get(&opponent_id).unwrap().overall_record;
+                let played: bool = self
+                    .current_simulation_base_games
+                    .get(game_id)
+                    .is_some_and(|base_game| base_game.game_result.is_some());
+                let total: &mut (u16, u16, u16) = match played {
+                    true => &mut strength_of_schedule.played,
+                    false => &mut strength_of_schedule.remaining,
+                };
+                total.0 += record.0;
+                total.1 += record.1;
+                total.2 += record.2;
+            }
+            strengths_of_schedule.insert(*team_id, strength_of_schedule);
+        }
+        self.current_simulation_result.strengths_of_schedule = strengths_of_schedule;
+    }
+
+    pub fn calculate_percent_from_tuple(record_tuple: (u16, u16, u16)) -> u16 {
+        let (wins, losses, ties) = record_tuple;
+        let wins: u32 = u32::from(wins);
+        let losses: u32 = u32::from(losses);
+        let ties: u32 = u32::from(ties);
+        let computed_wins: u32 = (wins * 1000) + ((ties * 1000) / 2);
+
+        let total_games = wins + losses + ties;
+        let win_percent: u16 = match total_games {
+            tg if tg != 0 => u16::try_from(computed_wins / (wins + losses + ties)).unwrap(),
+            _ => 0,
+        };
+
+        win_percent
+    }
+
+    /// Evaluates a pool of `team_ids` against the current simulation with the season's
+    /// generator, keeps its random ties and tiebreaks, and returns its ranking, or just its
+    /// winner for a division pool.
+    pub(crate) fn evaluate_pool(
+        &mut self,
+        team_ids: Vec<i32>,
+        pool_type: PoolType,
+    ) -> Result<Vec<i32>, Error> {
+        let playoff_format: PlayoffFormat = self.playoff_format();
+        let built_index: ScheduleIndex;
+        let schedule: &ScheduleIndex = match &self.schedule_index {
+            Some(schedule_index) => schedule_index,
+            None => {
+                built_index = ScheduleIndex::new(&self.current_simulation_games);
+                &built_index
+            }
+        };
+        let context: PoolContext = PoolContext {
+            conference_mapping: &self.conference_mapping,
+            division_mapping: &self.division_mapping,
+            team_records: &self.current_simulation_result.team_records,
+            games: &self.current_simulation_games,
+            schedule,
+            strengths_of_schedule: &self.current_simulation_result.strengths_of_schedule,
+            tiebreakers: &self.config.tiebreakers,
+            playoff_format,
+        };
+        let mut team_pool: TeamPool = TeamPool::new(team_ids, pool_type, context);
+        team_pool.evaluate(self.rng.as_mut())?;
+        let TeamPool {
+            winner,
+            ranking,
+            mut random_ties,
+            mut trace,
+            ..
+        } = team_pool;
+        self.current_simulation_result
+            .random_ties
+            .append(&mut random_ties);
+        self.current_simulation_result.tiebreaks.append(&mut trace);
+        match (ranking, winner) {
+            (Some(ranking), _) => Ok(ranking),
+            (None, winner) => Ok(winner.into_iter().collect()),
+        }
+    }
+
+    fn evaluate_divisions(&mut self) -> Result<(), Error> {
+        let divisions: Vec<Vec<i32>> = sorted_mapping(&self.division_mapping)
+            .into_iter()
+            .map(|(_, team_ids)| team_ids.clone())
+            .collect();
+        for team_ids in divisions {
+            for team_id in self.evaluate_pool(team_ids, PoolType::Division)? {
+                self.current_simulation_result
+                    .division_winners
+                    .insert(team_id);
+            }
+        }
+        Ok(())
+    }
+
+    fn evaluate_division_winner_playoff_seedings(&mut self) -> Result<(), Error> {
+        for division_winners in self.conference_pools(true) {
+            let ranking: Vec<i32> =
+                self.evaluate_pool(division_winners, PoolType::DivisionWinnerSeeding)?;
+            for (playoff_seed, team_id) in (1..).zip(ranking) {
+                self.current_simulation_result
+                    .playoff_seeding
+                    .get_mut(&playoff_seed)
+                    .unwrap()
+                    .insert(team_id);
+            }
+        }
+        Ok(())
+    }
+
+    /// Each conference's division winners, or each conference's other teams, in conference
+    /// order.
+    fn conference_pools(&self, division_winners: bool) -> Vec<Vec<i32>> {
+        sorted_mapping(&self.conference_mapping)
+            .into_iter()
+            .map(|(_, team_ids)| {
+                team_ids
+                    .iter()
+                    .copied()
+                    .filter(|team_id| {
+                        self.current_simulation_result
+                            .division_winners
+                            .contains(team_id)
+                            == division_winners
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn evaluate_wildcards(&mut self) -> Result<(), Error> {
+        for team_ids_without_division_winners in self.conference_pools(false) {
+            let ranking: Vec<i32> =
+                self.evaluate_pool(team_ids_without_division_winners, PoolType::Wildcard)?;
+            for (playoff_seed, team_id) in (5..).zip(ranking) {
+                self.current_simulation_result
+                    .wildcard_teams
+                    .insert(team_id);
+                self.current_simulation_result
+                    .playoff_seeding
+                    .get_mut(&playoff_seed)
+                    .unwrap()
+                    .insert(team_id);
+            }
+        }
+        Ok(())
+    }
+
+    fn evaluate_draft_order(&mut self) -> Result<(), Error> {
+        let mut teams: HashSet<i32> = self.teams.keys().cloned().collect();
+        let current_result = &self.current_simulation_result;
+        for team_id in current_result
+            .division_winners
+            .iter()
+            .chain(current_result.wildcard_teams.iter())
+        {
+            teams.remove(team_id);
+        }
+
+        let ranking: Vec<i32> = self.evaluate_pool(Vec::from_iter(teams), PoolType::DraftOrder)?;
+        for (draft_position, team_id) in (1..).zip(ranking) {
+            self.current_simulation_result
+                .draft_order
+                .insert(draft_position, team_id);
+        }
+        Ok(())
+    }
+
+    /// Week in which each of the current simulation's playoff teams clinched. A team counts as
+    /// clinched once fewer conference rivals than there are playoff spots can still reach its
+    /// record even if it loses out; teams that only get in on tiebreakers clinch in the final week.
+    fn clinch_weeks(&self) -> HashMap<i32, i32> {
+        let mut games: Vec<&Game> = self
+            .current_simulation_games
+            .values()
+            .filter(|game| !game.has_tbd_team() && game.game_result.is_some())
+            .collect();
+        games.sort_by_key(|game| game.week);
+        let playoff_spots: usize = usize::from(self.playoff_format().playoff_teams());
+
+        // Records are kept in half-wins so that ties count for half a win
+        let mut points: HashMap<i32, i32> = HashMap::new();
+        let mut remaining_games: HashMap<i32, i32> = HashMap::new();
+        for game in games.iter() {
+            for team_id in [game.home_team.team_id, game.away_team.team_id] {
+                *remaining_games.entry(team_id).or_insert(0) += 1;
+                points.entry(team_id).or_insert(0);
+            }
+        }
+
+        let playoff_teams: Vec<i32> = self
+            .current_simulation_result
+            .division_winners
+            .union(&self.current_simulation_result.wildcard_teams)
+            .copied()
+            .collect();
+        let mut clinch_weeks: HashMap<i32, i32> = HashMap::new();
+        let mut game_index: usize = 0;
+        while game_index < games.len() {
+            let week: i32 = games[game_index].week;
+            while game_index < games.len() && games[game_index].week == week {
+                let game: &Game = games[game_index];
+                let (home_points, away_points) = match game.game_result {
+                    Some(GameResult::HomeWin) => (2, 0),
+                    Some(GameResult::AwayWin) => (0, 2),
+                    Some(GameResult::Tie) => (1, 1),
+                    None => (0, 0),
+                };
+                *points.get_mut(&game.home_team.team_id).unwrap() += home_points;
+                *points.get_mut(&game.away_team.team_id).unwrap() += away_points;
+                *remaining_games.get_mut(&game.home_team.team_id).unwrap() -= 1;
+                *remaining_games.get_mut(&game.away_team.team_id).unwrap() -= 1;
+                game_index += 1;
+            }
+
+            for team_id in playoff_teams.iter() {
+                if clinch_weeks.contains_key(team_id) {
+                    continue;
+                }
+                let guaranteed_points: i32 = *points.get(team_id).unwrap_or(&0);
+                let conference: &String = &self.teams.get(team_id).unwrap().conference;
+                let rivals_in_reach: usize = self
+                    .conference_mapping
+                    .get(conference)
+                    .unwrap()
+                    .iter()
+                    .filter(|rival_id| *rival_id != team_id)
+                    .filter(|rival_id| {
+                        let max_points: i32 = points.get(rival_id).unwrap_or(&0)
+                            + 2 * remaining_games.get(rival_id).unwrap_or(&0);
+                        max_points >= guaranteed_points
+                    })
+                    .count();
+                if rivals_in_reach < playoff_spots || game_index == games.len() {
+                    clinch_weeks.insert(*team_id, week);
+                }
+            }
+        }
+        clinch_weeks
+    }
+
+    /// Adds the current simulation to the results `weight` times. Errors when the simulation
+    /// seeded a team twice or has a team with no results set up for the scenario.
+    fn increment_overall_results(&mut self, weight: i32) -> Result<(), Error> {
+        let simulation_game: Option<&(i32, GameResult)> = self.current_simulation_game.as_ref();
+        let clinch_weeks: HashMap<i32, i32> = match simulation_game {
+            Some(_) => HashMap::new(),
+            None => self.clinch_weeks(),
+        };
+        let current_result = &self.current_simulation_result;
+        let seeding_violations: Vec<i32> = current_result.seeding_violations();
+        if !seeding_violations.is_empty() {
+            return Err(Error::InvalidData(format!(
+                "Teams {:?} are both division winners and wildcards in the same simulation",
+                seeding_violations
+            )));
+        }
+        let overall_results: &mut HashMap<SimulationResultLookup, TeamSimulationResults> =
+            &mut self.overall_results;
+        for team_id in self.teams.keys() {
+            let lookup = SimulationResultLookup::new(team_id, simulation_game);
+            let (record, strength_of_schedule): (&TeamRecord, &StrengthOfSchedule) = match (
+                current_result.team_records.get(team_id),
+                current_result.strengths_of_schedule.get(team_id),
+            ) {
+                (Some(record), Some(strength_of_schedule)) => (record, strength_of_schedule),
+                _ => {
+                    return Err(Error::InvalidData(format!(
+                        "team {} has no record in the current simulation",
+                        team_id
+                    )))
+                }
+            };
+            let wins: u16 = record.overall_record.0;
+            let win_total: u8 = u8::try_from(wins)
+                .map_err(|_| Error::InvalidData(format!("team {} won {} games", team_id, wins)))?;
+            let result: &mut TeamSimulationResults = overall_result(overall_results, &lookup)?;
+            result.simulations += weight;
+            result.total_wins += i32::from(wins) * weight;
+            *result.win_totals.entry(win_total).or_insert(0) += weight;
+            if let Some(pct) = strength_of_schedule.played_pct() {
+                result.total_played_sos += pct * f64::from(weight);
+                result.played_sos_simulations += weight;
+            }
+            if let Some(pct) = strength_of_schedule.remaining_pct() {
+                result.total_remaining_sos += pct * f64::from(weight);
+                result.remaining_sos_simulations += weight;
+            }
+        }
+        for team_id in current_result.coin_toss_teams().iter() {
+            let lookup = SimulationResultLookup::new(team_id, simulation_game);
+            overall_result(overall_results, &lookup)?.coin_tosses += weight;
+        }
+        for team_id in current_result.division_winners.iter() {
+            let lookup = SimulationResultLookup::new(team_id, simulation_game);
+            let result: &mut TeamSimulationResults = overall_result(overall_results, &lookup)?;
+            result.made_playoffs += weight;
+            result.division_winner += weight;
+        }
+        for team_id in current_result.wildcard_teams.iter() {
+            let lookup = SimulationResultLookup::new(team_id, simulation_game);
+            let result: &mut TeamSimulationResults = overall_result(overall_results, &lookup)?;
+            result.made_playoffs += weight;
+            result.wildcard_team += weight;
+        }
+        for (seed_number, teams) in current_result.playoff_seeding.iter() {
+            for team_id in teams.iter() {
+                let lookup = SimulationResultLookup::new(team_id, simulation_game);
+                *overall_result(overall_results, &lookup)?
+                    .playoff_seedings
+                    .entry(*seed_number)
+                    .or_insert(0) += weight;
+            }
+        }
+        for (draft_position, team_id) in current_result.draft_order.iter() {
+            let lookup = SimulationResultLookup::new(team_id, simulation_game);
+            *overall_result(overall_results, &lookup)?
+                .draft_positions
+                .entry(*draft_position)
+                .or_insert(0) += weight;
+        }
+        if let Some(playoff_bracket) = &current_result.playoff_bracket {
+            for (round, team_ids) in playoff_bracket.round_winners.iter() {
+                for team_id in team_ids {
+                    let lookup = SimulationResultLookup::new(team_id, simulation_game);
+                    *overall_result(overall_results, &lookup)?
+                        .playoff_round_wins
+                        .entry(*round)
+                        .or_insert(0) += weight;
+                }
+            }
+            for team_id in playoff_bracket.hosts().iter() {
+                let lookup = SimulationResultLookup::new(team_id, simulation_game);
+                overall_result(overall_results, &lookup)?.hosted_playoff_games += weight;
+            }
+            if simulation_game.is_none() {
+                for game in playoff_bracket.games.iter() {
+                    for (team_id, opponent_id) in [
+                        (game.home_team_id, game.away_team_id),
+                        (game.away_team_id, game.home_team_id),
+                    ] {
+                        let lookup = SimulationResultLookup::new(&team_id, None);
+                        *overall_result(overall_results, &lookup)?
+                            .playoff_opponents
+                            .entry(game.round)
+                            .or_default()
+                            .entry(opponent_id)
+                            .or_insert(0) += weight;
+                    }
+                }
+            }
+        }
+        for (team_id, clinch_week) in clinch_weeks {
+            let lookup = SimulationResultLookup::new(&team_id, None);
+            let result: &mut TeamSimulationResults = overall_result(overall_results, &lookup)?;
+            result.total_clinch_weeks += clinch_week * weight;
+            result.clinches += weight;
+        }
+        if simulation_game.is_none() {
+            for (division, team_ids) in self.division_mapping.iter() {
+                let playoff_teams: usize = team_ids
+                    .iter()
+                    .filter(|team_id| {
+                        current_result.division_winners.contains(team_id)
+                            || current_result.wildcard_teams.contains(team_id)
+                    })
+                    .count();
+                let playoff_teams: u8 = u8::try_from(playoff_teams).map_err(|_| {
+                    Error::InvalidData(format!("{} has {} playoff teams", division, playoff_teams))
+                })?;
+                *self
+                    .division_playoff_counts
+                    .entry(division.clone())
+                    .or_default()
+                    .entry(playoff_teams)
+                    .or_insert(0) += weight;
+            }
+        }
+        if simulation_game.is_none() && self.config.seeding_detail == SeedingDetail::FullSeeds {
+            for (_, team_ids) in self.conference_mapping.iter() {
+                let finish_order: Vec<i32> = current_result.finish_order(team_ids);
+                for (i, team_id) in finish_order.iter().enumerate() {
+                    let lookup = SimulationResultLookup::new(team_id, None);
+                    let result: &mut TeamSimulationResults =
+                        overall_result(overall_results, &lookup)?;
+                    for behind_team_id in finish_order[i + 1..].iter() {
+                        *result.finished_ahead.entry(*behind_team_id).or_insert(0) += weight;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn load_teams(&mut self, source: &mut dyn DataSource) -> Result<(), Error> {
+        for team in source.fetch_teams(self.season_year)? {
+            self.teams.insert(team.team_id, Arc::new(team));
+        }
+        Ok(())
+    }
+
+    fn load_conference_division_mapping(&mut self) {
+        let teams: Vec<Team> = self.teams_sorted().into_iter().cloned().collect();
+        for team in teams.iter() {
+            if !self.conference_mapping.contains_key(&team.conference) {
+                self.conference_mapping
+                    .insert(team.conference.clone(), Vec::new());
+            }
 
-    format!("postgres://{pg_user}:{pg_pass}@{pg_locn}/{pg_dtbs}")
+            let conference_vector: &mut Vec<i32> =
+                self.conference_mapping.get_mut(&team.conference).unwrap();
+            conference_vector.push(team.team_id);
+
+            if !self.division_mapping.contains_key(&team.division) {
+                self.division_mapping
+                    .insert(team.division.clone(), Vec::new());
+            }
+
+            let division_vector: &mut Vec<i32> =
+                self.division_mapping.get_mut(&team.division).unwrap();
+            division_vector.push(team.team_id);
+        }
+    }
+
+    #[cfg(feature = "postgres")]
+    pub fn load_historical(&self, seasons: &[i32]) -> Result<Vec<Game>, Error> {
+        let mut client: PooledClient = self.db()?.get()?;
+        self.load_historical_with_client(seasons, &mut client)
+    }
+
+    /// Regular season games from `seasons`, for example to seed `EloModel::from_historical`.
+    /// Teams that relocated or were renamed are mapped to this season's team of the same
+    /// franchise. Teams with no match in this season are loaded as TBD and skipped by the model.
+    #[cfg(feature = "postgres")]
+    pub fn load_historical_with_client(
+        &self,
+        seasons: &[i32],
+        client: &mut Client,
+    ) -> Result<Vec<Game>, Error> {
+        let query: String = String::from(
+            "
+            SELECT
+                team_id,
+                franchise_id
+            FROM nfl.franchises;
+        ",
+        );
+        let teams_by_franchise: HashMap<i32, &Arc<Team>> = self
+            .teams
+            .values()
+            .map(|team| (team.franchise_id(), team))
+            .collect();
+        let mut teams: HashMap<i32, Arc<Team>> = self.teams.clone();
+        for row in run_query_with_client(client, query)? {
+            let team_id: i32 = row.try_get(0)?;
+            let franchise_id: i32 = row.try_get(1)?;
+            if let Some(current_team) = teams_by_franchise.get(&franchise_id) {
+                teams
+                    .entry(team_id)
+                    .or_insert_with(|| Arc::clone(current_team));
+            }
+        }
+
+        let seasons: Vec<i32> = seasons.to_vec();
+        client
+            .query(
+                "
+            SELECT
+                game_id,
+                season,
+                week,
+                home_team_id,
+                away_team_id,
+                home_score,
+                away_score,
+                neutral_site,
+                home_touchdowns,
+                away_touchdowns
+            FROM nfl.games
+            WHERE
+                season = ANY($1)
+                AND game_type='REG';
+        ",
+                &[&seasons],
+            )?
+            .into_iter()
+            .map(|row| Game::new_from_db_row(row, &teams))
+            .collect()
+    }
+
+    fn load_games(&mut self, source: &mut dyn DataSource) -> Result<(), Error> {
+        for scheduled_game in source.fetch_games(self.season_year)? {
+            let game: Game = Game::new_from_scheduled(scheduled_game, &self.teams)?;
+            self.actual_games.insert(game.game_id, game);
+        }
+
+        self.current_simulation_base_games = self.actual_games.clone();
+        Ok(())
+    }
+
+    #[cfg(feature = "postgres")]
+    pub fn set_simulation_id(
+        &mut self,
+        baseline_sims: u64,
+        scenario_sims: u64,
+        include_decided: bool,
+    ) -> Result<(), Error> {
+        // Insert new simulation into db and add simulation_id to Season struct
+        let run: RunInfo = RunInfo {
+            season_year: self.season_year,
+            baseline_sims,
+            scenario_sims,
+            include_decided,
+        };
+        let mut client: PooledClient = self.db()?.get()?;
+        self.simulation_id = Some(sink::insert_simulation_with_client(&run, &mut *client)?);
+        Ok(())
+    }
+
+    #[cfg(feature = "postgres")]
+    pub fn load_results(&self, simulation_id: i32) -> Result<SimulationReport, Error> {
+        let mut client: PooledClient = self.db()?.get()?;
+        self.load_results_with_client(simulation_id, &mut client)
+    }
+
+    /// Reads a stored run from `nfl.simulation_results` back into a report. Division winner,
+    /// wildcard and playoff counts are rebuilt from the stored playoff seed counts.
+    #[cfg(feature = "postgres")]
+    pub fn load_results_with_client(
+        &self,
+        simulation_id: i32,
+        client: &mut Client,
+    ) -> Result<SimulationReport, Error> {
+        let simulations: Vec<Row> = client.query(
+            "
+            SELECT
+                season,
+                simulations_per_game_result,
+                baseline_simulations
+            FROM nfl.simulations
+            WHERE simulation_id=$1;
+        ",
+            &[&simulation_id],
+        )?;
+        let simulation: &Row = match simulations.first() {
+            Some(row) => row,
+            None => {
+                return Err(Error::InvalidArgument(format!(
+                    "simulation {simulation_id} does not exist"
+                )))
+            }
+        };
+        let season: i32 = simulation.try_get(0)?;
+        if season != self.season_year {
+            return Err(Error::InvalidArgument(format!(
+                "simulation {simulation_id} is for season {season}, not {}",
+                self.season_year
+            )));
+        }
+        let scenario_sims: i32 = count_from_db(simulation.try_get(1)?)?;
+        let baseline_sims: i32 = count_from_db(simulation.try_get(2)?)?;
+
+        let rows: Vec<Row> = client.query(
+            "
+            SELECT
+                game_id,
+                simulated_game_result::text,
+                simulation_team_id,
+                result_set::text,
+                team_rank,
+                simulations_with_rank,
+                simulations
+            FROM nfl.simulation_results
+            WHERE simulation_id=$1;
+        ",
+            &[&simulation_id],
+        )?;
+
+        let mut results: HashMap<SimulationResultLookup, TeamSimulationResults> = HashMap::new();
+        for row in rows {
+            let game_result: Option<String> = row.try_get(1)?;
+            let game_result: Option<GameResult> = match game_result {
+                Some(gr) => Some(GameResult::from_str(&gr).map_err(Error::InvalidData)?),
+                None => None,
+            };
+            let lookup = SimulationResultLookup {
+                game_id: row.try_get(0)?,
+                game_result,
+                team_id: row.try_get(2)?,
+            };
+            // Rows stored before each scenario carried its own count leave `simulations`
+            // empty and use the run's counts.
+            let stored_sims: Option<i64> = row.try_get(6)?;
+            let sims: i32 = match (stored_sims, lookup.game_id) {
+                (Some(stored_sims), _) => count_from_db(stored_sims)?,
+                (None, Some(_)) => scenario_sims,
+                (None, None) => baseline_sims,
+            };
+            let team_results: &mut TeamSimulationResults =
+                results.entry(lookup).or_insert_with(|| {
+                    let mut team_results = TeamSimulationResults::new();
+                    team_results.simulations = sims;
+                    team_results
+                });
+
+            let result_set: Option<String> = row.try_get(3)?;
+            let team_rank: Option<i16> = row.try_get(4)?;
+            let (result_set, team_rank) = match (result_set, team_rank) {
+                (Some(rs), Some(tr)) => match u8::try_from(tr) {
+                    Ok(tr) => (rs, tr),
+                    Err(_) => return Err(Error::InvalidData(format!("invalid team rank {}", tr))),
+                },
+                _ => continue,
+            };
+            let simulations_with_rank: i32 = count_from_db(row.try_get(5)?)?;
+            match ResultSet::from_str(&result_set) {
+                Ok(ResultSet::PlayoffSeed) => {
+                    team_results
+                        .playoff_seedings
+                        .insert(team_rank, simulations_with_rank);
+                }
+                Ok(ResultSet::DraftPosition) => {
+                    team_results
+                        .draft_positions
+                        .insert(team_rank, simulations_with_rank);
+                }
+                Ok(ResultSet::WinTotal) => {
+                    team_results
+                        .win_totals
+                        .insert(team_rank, simulations_with_rank);
+                    team_results.total_wins += i32::from(team_rank) * simulations_with_rank;
+                }
+                Ok(result_set) => {
+                    if let Some(round) = result_set.playoff_round() {
+                        team_results
+                            .playoff_round_wins
+                            .insert(round, simulations_with_rank);
+                    }
+                }
+                Err(e) => return Err(Error::InvalidData(e)),
+            }
+        }
+
+        for team_results in results.values_mut() {
+            for (seed, occurences) in team_results.playoff_seedings.iter() {
+                team_results.made_playoffs += occurences;
+                if *seed <= 4 {
+                    team_results.division_winner += occurences;
+                } else {
+                    team_results.wildcard_team += occurences;
+                }
+            }
+        }
+
+        Ok(SimulationReport {
+            season_year: self.season_year,
+            simulation_id: Some(simulation_id),
+            seed: None,
+            teams: self.teams.clone(),
+            conference_mapping: self.conference_mapping.clone(),
+            division_mapping: self.division_mapping.clone(),
+            playoff_streaks: self.playoff_streaks.clone(),
+            division_playoff_counts: HashMap::new(),
+            results,
+            complete: true,
+        })
+    }
+
+    #[cfg(feature = "postgres")]
+    pub fn persist_simulated_games(&self, simulation_id: i32, run_index: i32) -> Result<(), Error> {
+        let mut client: PooledClient = self.db()?.get()?;
+        self.persist_simulated_games_with_client(simulation_id, run_index, &mut client)
+    }
+
+    /// Saves the most recent run's games (`current_simulation_games`) to `nfl.simulated_games`
+    /// under `run_index`, so a sampled season can be reloaded with `load_simulated_games`.
+    /// Games with a TBD team, which are never simulated, are skipped.
+    #[cfg(feature = "postgres")]
+    pub fn persist_simulated_games_with_client(
+        &self,
+        simulation_id: i32,
+        run_index: i32,
+        client: &mut Client,
+    ) -> Result<(), Error> {
+        let mut games: Vec<&Game> = self
+            .current_simulation_games
+            .values()
+            .filter(|game| game.game_result.is_some())
+            .collect();
+        games.sort_by_key(|game| game.game_id);
+        let game_results: Vec<String> = games
+            .iter()
+            .map(|game| game.game_result.as_ref().unwrap().to_string())
+            .collect();
+
+        for (chunk_index, chunk) in games.chunks(SIMULATED_GAMES_BATCH_SIZE).enumerate() {
+            let offset: usize = chunk_index * SIMULATED_GAMES_BATCH_SIZE;
+            let mut values: Vec<String> = Vec::new();
+            let mut params: Vec<&(dyn ToSql + Sync)> = Vec::new();
+            for (i, game) in chunk.iter().enumerate() {
+                let p: usize = params.len();
+                values.push(format!(
+                    "(${},${},${},${}::text::nfl.gameresult,${},${},${})",
+                    p + 1,
+                    p + 2,
+                    p + 3,
+                    p + 4,
+                    p + 5,
+                    p + 6,
+                    p + 7
+                ));
+                params.push(&simulation_id);
+                params.push(&run_index);
+                params.push(&game.game_id);
+                params.push(&game_results[offset + i]);
+                params.push(&game.home_score);
+                params.push(&game.away_score);
+                params.push(&game.is_simulated);
+            }
+            let statement: String = format!(
+                "
+                INSERT INTO nfl.simulated_games
+                    (simulation_id, run_index, game_id, game_result, home_score, away_score, is_simulated)
+                VALUES {}
+            ",
+                values.join(","),
+            );
+            client.execute(&statement, &params)?;
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "postgres")]
+    pub fn load_simulated_games(
+        &self,
+        simulation_id: i32,
+        run_index: i32,
+    ) -> Result<HashMap<i32, Game>, Error> {
+        let mut client: PooledClient = self.db()?.get()?;
+        self.load_simulated_games_with_client(simulation_id, run_index, &mut client)
+    }
+
+    /// The season's games with results and scores replaced by a run saved with
+    /// `persist_simulated_games`.
+    #[cfg(feature = "postgres")]
+    pub fn load_simulated_games_with_client(
+        &self,
+        simulation_id: i32,
+        run_index: i32,
+        client: &mut Client,
+    ) -> Result<HashMap<i32, Game>, Error> {
+        let rows: Vec<Row> = client.query(
+            "
+            SELECT
+                game_id,
+                game_result::text,
+                home_score,
+                away_score,
+                is_simulated
+            FROM nfl.simulated_games
+            WHERE simulation_id=$1 AND run_index=$2;
+        ",
+            &[&simulation_id, &run_index],
+        )?;
+        if rows.is_empty() {
+            return Err(Error::InvalidArgument(format!(
+                "simulation {simulation_id} has no saved run {run_index}"
+            )));
+        }
+
+        let mut games: HashMap<i32, Game> = self.actual_games.clone();
+        for row in rows {
+            let game_id: i32 = row.try_get(0)?;
+            let game_result: String = row.try_get(1)?;
+            let game: &mut Game = match games.get_mut(&game_id) {
+                Some(game) => game,
+                None => {
+                    return Err(Error::InvalidData(format!(
+                        "game {game_id} is not part of season {}",
+                        self.season_year
+                    )))
+                }
+            };
+            game.game_result =
+                Some(GameResult::from_str(&game_result).map_err(Error::InvalidData)?);
+            game.home_score = row.try_get(2)?;
+            game.away_score = row.try_get(3)?;
+            game.is_simulated = row.try_get(4)?;
+        }
+        Ok(games)
+    }
+
+    /// Sends one scenario's results to each sink: the baseline when `scenario` is `None`,
+    /// otherwise a pinned game result.
+    fn store_scenario(
+        &self,
+        sinks: &mut [&mut dyn ResultSink],
+        scenario: Option<(i32, &GameResult)>,
+    ) -> Result<(), Error> {
+        if sinks.is_empty() {
+            return Ok(());
+        }
+        let records: Vec<ResultRecord> = report::result_records(
+            self.simulation_id,
+            self.overall_results.iter().filter(|(lookup, _)| {
+                lookup.game_id == scenario.map(|(game_id, _)| game_id)
+                    && lookup.game_result.as_ref() == scenario.map(|(_, game_result)| game_result)
+            }),
+        );
+        for sink in sinks.iter_mut() {
+            sink.write_scenario(&records)?;
+        }
+        Ok(())
+    }
 }
 
-fn connect() -> Client {
-    let conn_string = get_conn_string();
-    let client: Client = match Client::connect(&conn_string, NoTls) {
-        Ok(c) => c,
-        Err(e) => panic!("{}", e),
+/// Adds a decided game to both teams' records. Games with a TBD team are ignored.
+fn record_game(team_records: &mut HashMap<i32, TeamRecord>, game: &Game) {
+    if game.has_tbd_team() {
+        return;
+    }
+    let (home_index, away_index): (usize, usize) = match game.game_result {
+        Some(GameResult::HomeWin) => (0, 1),
+        Some(GameResult::AwayWin) => (1, 0),
+        Some(GameResult::Tie) => (2, 2),
+        None => return,
     };
-    client
+    let scores: Option<(i32, i32)> = game.home_score.zip(game.away_score);
+    for (team_id, index, points) in [
+        (game.home_team.team_id, home_index, scores),
+        (
+            game.away_team.team_id,
+            away_index,
+            scores.map(|(home_score, away_score)| (away_score, home_score)),
+        ),
+    ] {
+        let record: &mut TeamRecord = team_records.get_mut(&team_id).unwrap();
+        increment_record(&mut record.overall_record, index);
+        if game.conference_game {
+            increment_record(&mut record.conference_record, index);
+        }
+        if game.division_game {
+            increment_record(&mut record.division_record, index);
+        }
+        match points {
+            Some((points_for, points_against)) => {
+                record.points_for += points_for;
+                record.points_against += points_against;
+                if game.conference_game {
+                    record.conference_points_for += points_for;
+                    record.conference_points_against += points_against;
+                }
+            }
+            None => record.unscored_games += 1,
+        }
+    }
 }
 
-pub fn run_query(query: String) -> Vec<Row> {
-    let mut client: Client = connect();
-    let results = match client.query(&query, &[]) {
-        Ok(r) => r,
-        Err(e) => panic!("{}", e),
-    };
-    results
+/// The entry for `lookup` in `overall_results`, which every scenario sets up for each team
+/// before simulating.
+fn overall_result<'a>(
+    overall_results: &'a mut HashMap<SimulationResultLookup, TeamSimulationResults>,
+    lookup: &SimulationResultLookup,
+) -> Result<&'a mut TeamSimulationResults, Error> {
+    overall_results.get_mut(lookup).ok_or_else(|| {
+        Error::InvalidData(format!(
+            "no results set up for team {} in this scenario",
+            lookup.team_id
+        ))
+    })
 }
 
-pub fn execute(statement: String) {
-    let mut client: Client = connect();
-    match client.execute(&statement, &[]) {
-        Ok(_) => {}
-        Err(e) => println!(
-            "Failed to execute statement:\n\n{}\n\n{}\n------------------------------",
-            statement, e
-        ),
-    };
+fn increment_record(record: &mut (u16, u16, u16), index: usize) {
+    match index {
+        0 => record.0 += 1,
+        1 => record.1 += 1,
+        _ => record.2 += 1,
+    }
+}
+
+fn sorted_mapping(mapping: &HashMap<String, Vec<i32>>) -> Vec<(&String, &Vec<i32>)> {
+    let mut entries: Vec<(&String, &Vec<i32>)> = mapping.iter().collect();
+    entries.sort_by_key(|(key, _)| *key);
+    entries
+}
+
+fn head_to_head_matrix<'a>(
+    games: impl Iterator<Item = &'a Game>,
+    team_ids: &[i32],
+) -> HashMap<(i32, i32), (u16, u16, u16)> {
+    let mut matrix: HashMap<(i32, i32), (u16, u16, u16)> = HashMap::new();
+    for team_id in team_ids {
+        for opponent_id in team_ids {
+            if team_id != opponent_id {
+                matrix.insert((*team_id, *opponent_id), (0, 0, 0));
+            }
+        }
+    }
+
+    for game in games {
+        let home_team_id: i32 = game.home_team.team_id;
+        let away_team_id: i32 = game.away_team.team_id;
+        if home_team_id == away_team_id
+            || !team_ids.contains(&home_team_id)
+            || !team_ids.contains(&away_team_id)
+        {
+            continue;
+        }
+        let (home_result, away_result) = match game.game_result {
+            Some(GameResult::HomeWin) => ((1, 0, 0), (0, 1, 0)),
+            Some(GameResult::AwayWin) => ((0, 1, 0), (1, 0, 0)),
+            Some(GameResult::Tie) => ((0, 0, 1), (0, 0, 1)),
+            None => continue,
+        };
+        for (pairing, (wins, losses, ties)) in [
+            ((home_team_id, away_team_id), home_result),
+            ((away_team_id, home_team_id), away_result),
+        ] {
+            let record = matrix.get_mut(&pairing).unwrap();
+            record.0 += wins;
+            record.1 += losses;
+            record.2 += ties;
+        }
+    }
+    matrix
+}
+
+fn log5(percent: f64, opponent_percent: f64) -> f64 {
+    let odds: f64 = percent * (1.0 - opponent_percent);
+    let opponent_odds: f64 = opponent_percent * (1.0 - percent);
+    match odds + opponent_odds {
+        total if total > 0.0 => odds / total,
+        _ => 0.5,
+    }
+}
+
+/// Converts a simulation count for an `int8` column.
+#[cfg(feature = "postgres")]
+fn count_to_db(count: u64) -> Result<i64, Error> {
+    match i64::try_from(count) {
+        Ok(count) => Ok(count),
+        Err(_) => Err(Error::InvalidArgument(format!(
+            "simulation count {} is out of range",
+            count
+        ))),
+    }
+}
+
+/// Converts a stored `int8` simulation count.
+#[cfg(feature = "postgres")]
+fn count_from_db(count: i64) -> Result<i32, Error> {
+    match i32::try_from(count) {
+        Ok(count) => Ok(count),
+        Err(_) => Err(Error::InvalidData(format!(
+            "simulation count {} is out of range",
+            count
+        ))),
+    }
+}
+
+#[cfg(feature = "postgres")]
+fn get_variable(key: &str) -> Result<String, Error> {
+    match var(key) {
+        Ok(val) => Ok(val),
+        Err(_) => Err(Error::MissingVariable(String::from(key))),
+    }
+}
+
+#[cfg(feature = "postgres")]
+fn get_conn_string() -> Result<String, Error> {
+    let pg_locn: String = get_variable("PG_LOCN")?;
+    let pg_dtbs: String = get_variable("PG_DTBS")?;
+    let pg_user: String = get_variable("PG_USER")?;
+    let pg_pass: String = get_variable("PG_PASS")?;
+
+    Ok(format!(
+        "postgres://{pg_user}:{pg_pass}@{pg_locn}/{pg_dtbs}"
+    ))
+}
+
+#[cfg(feature = "postgres")]
+fn connect() -> Result<PooledClient, Error> {
+    Db::shared()?.get()
+}
+
+#[cfg(feature = "postgres")]
+pub fn run_query(query: String) -> Result<Vec<Row>, Error> {
+    Db::shared()?.run_query(query)
+}
+
+#[cfg(feature = "postgres")]
+pub fn run_query_with_client(client: &mut Client, query: String) -> Result<Vec<Row>, Error> {
+    Ok(client.query(&query, &[])?)
+}
+
+#[cfg(feature = "postgres")]
+pub fn execute(statement: String) -> Result<(), Error> {
+    Db::shared()?.execute(statement)
+}
+
+#[cfg(feature = "postgres")]
+pub fn execute_with_client(client: &mut Client, statement: String) -> Result<(), Error> {
+    client.execute(&statement, &[])?;
+    Ok(())
 }
 
 pub fn now() -> String {
@@ -1438,3 +4915,1129 @@ pub fn now() -> String {
 
     time.format("%Y-%m-%d %H:%M:%S%.3f").to_string()
 }
+
+/// Splits `total` into whole shares proportional to `probabilities` by systematic rounding:
+/// each share is its exact quota rounded down or up, chosen so the expected share equals the
+/// quota and the shares add up to `total`. `offset` is drawn uniformly from [0, 1).
+fn apportion(probabilities: &[f64], total: u64, offset: f64) -> Vec<i32> {
+    let total_probability: f64 = probabilities.iter().sum();
+    let total: f64 = total as f64;
+    let mut shares: Vec<i32> = Vec::new();
+    let mut cumulative: f64 = 0.0;
+    let mut assigned: f64 = 0.0;
+    for (i, p) in probabilities.iter().enumerate() {
+        cumulative += p / total_probability * total;
+        let boundary: f64 = match i == probabilities.len() - 1 {
+            true => total,
+            false => (cumulative - offset).ceil().max(0.0),
+        };
+        shares.push((boundary - assigned) as i32);
+        assigned = boundary;
+    }
+    shares
+}
+
+fn sorted_ids(team_ids: &HashSet<i32>) -> Vec<i32> {
+    let mut team_ids: Vec<i32> = team_ids.iter().copied().collect();
+    team_ids.sort();
+    team_ids
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEASON_YEAR: i32 = 2024;
+
+    /// Home team, away team and the final score once the game is played.
+    type FixtureGame = (i32, i32, Option<(i32, i32)>);
+
+    /// A `TiebreakDecision`'s step, the clubs tied before it and the clubs still tied after.
+    type TraceStep = (TiebreakStep, Vec<i32>, Vec<i32>);
+
+    /// Club `team_id` of a 16-club league: ids 1-8 are the AFC and 9-16 the NFC, with two
+    /// clubs per division in id order.
+    fn team(team_id: i32) -> Team {
+        let conference: &str = match team_id <= 8 {
+            true => "AFC",
+            false => "NFC",
+        };
+        let division: &str = ["East", "North", "South", "West"][((team_id - 1) % 8 / 2) as usize];
+        Team {
+            team_id,
+            abbreviation: format!("T{}", team_id),
+            name: format!("Team {}", team_id),
+            conference: String::from(conference),
+            division: format!("{} {}", conference, division),
+            franchise_id: None,
+        }
+    }
+
+    /// A season of `games` between `team_ids`, each game `(home, away, score)` and numbered
+    /// from 1 in order. Skips the game count check of `Season::from_parts`, so a fixture can
+    /// hold only the games a test is about.
+    fn season(team_ids: &[i32], games: &[FixtureGame]) -> Season {
+        season_of_teams(team_ids.iter().map(|team_id| team(*team_id)), games)
+    }
+
+    fn season_of_teams(teams: impl Iterator<Item = Team>, games: &[FixtureGame]) -> Season {
+        let mut season: Season = Season::empty(SEASON_YEAR);
+        for team in teams {
+            season.teams.insert(team.team_id, Arc::new(team));
+        }
+        season.load_conference_division_mapping();
+        for (game_id, (home_team_id, away_team_id, score)) in (1..).zip(games) {
+            let scheduled_game: ScheduledGame = ScheduledGame {
+                game_id,
+                season_year: SEASON_YEAR,
+                week: (game_id - 1) / 8 + 1,
+                home_team_id: Some(*home_team_id),
+                away_team_id: Some(*away_team_id),
+                home_score: score.map(|(home_score, _)| home_score),
+                away_score: score.map(|(_, away_score)| away_score),
+                neutral_site: false,
+                home_touchdowns: None,
+                away_touchdowns: None,
+            };
+            let game: Game = Game::new_from_scheduled(scheduled_game, &season.teams).unwrap();
+            season.actual_games.insert(game_id, game);
+        }
+        season.current_simulation_base_games = season.actual_games.clone();
+        season
+    }
+
+    /// The 16-club league, each club playing every conference rival once and its division
+    /// rival a second time at the rival's home. The first `decided_games` games are played,
+    /// mostly home wins with some away wins and ties.
+    fn league(decided_games: usize) -> Season {
+        let mut matchups: Vec<(i32, i32)> = Vec::new();
+        for first_team_id in [1, 9] {
+            let team_ids: Vec<i32> = (first_team_id..first_team_id + 8).collect();
+            for (i, home_team_id) in team_ids.iter().enumerate() {
+                for away_team_id in &team_ids[i + 1..] {
+                    matchups.push((*home_team_id, *away_team_id));
+                }
+            }
+            for home_team_id in team_ids.iter().skip(1).step_by(2) {
+                matchups.push((*home_team_id, home_team_id - 1));
+            }
+        }
+        let games: Vec<FixtureGame> = matchups
+            .into_iter()
+            .enumerate()
+            .map(|(i, (home_team_id, away_team_id))| {
+                let score: Option<(i32, i32)> = match (i < decided_games, i % 7) {
+                    (false, _) => None,
+                    (true, 0) => Some((17, 17)),
+                    (true, 1) | (true, 4) => Some((13, 20)),
+                    (true, _) => Some((24, 10)),
+                };
+                (home_team_id, away_team_id, score)
+            })
+            .collect();
+        season(&(1..=16).collect::<Vec<i32>>(), &games)
+    }
+
+    #[test]
+    fn base_records_plus_simulated_games_match_full_recompute() {
+        let mut season: Season = league(40);
+        season.set_seed(1);
+        let base_records: HashMap<i32, TeamRecord> = season.decided_base_records();
+        for _ in 0..20 {
+            season.base_records = Some(base_records.clone());
+            season.run_simulation(false).unwrap();
+            let incremental: HashMap<i32, TeamRecord> =
+                season.current_simulation_result.team_records.clone();
+
+            season.base_records = None;
+            season.populate_records().unwrap();
+            season.calculate_percentages();
+            assert_eq!(season.current_simulation_result.team_records, incremental);
+        }
+    }
+
+    /// A simulated field with one club moved from the division winners into the wildcards
+    /// as well.
+    fn season_with_double_counted_team() -> (Season, i32) {
+        let mut season: Season = league(40);
+        season.set_seed(2);
+        season.run_simulation(false).unwrap();
+        let result: &mut CurrentSimulationResult = &mut season.current_simulation_result;
+        let team_id: i32 = *result.division_winners.iter().min().unwrap();
+        result.wildcard_teams.insert(team_id);
+        (season, team_id)
+    }
+
+    #[test]
+    fn seeding_violations_finds_team_in_both_division_winners_and_wildcards() {
+        let mut season: Season = league(40);
+        season.set_seed(2);
+        season.run_simulation(false).unwrap();
+        assert!(season
+            .current_simulation_result
+            .seeding_violations()
+            .is_empty());
+
+        let (season, team_id) = season_with_double_counted_team();
+        assert_eq!(
+            season.current_simulation_result.seeding_violations(),
+            vec![team_id]
+        );
+    }
+
+    #[test]
+    fn increment_rejects_team_in_both_division_winners_and_wildcards() {
+        let (mut season, team_id) = season_with_double_counted_team();
+        match season.increment_overall_results(1) {
+            Err(Error::InvalidData(message)) => {
+                assert!(message.contains("both division winners and wildcards"));
+                assert!(message.contains(&format!("[{}]", team_id)));
+            }
+            other => panic!("expected an InvalidData error, got {:?}", other),
+        }
+    }
+
+    fn pool_context<'a>(season: &'a Season, schedule: &'a ScheduleIndex) -> PoolContext<'a> {
+        PoolContext {
+            conference_mapping: &season.conference_mapping,
+            division_mapping: &season.division_mapping,
+            team_records: &season.current_simulation_result.team_records,
+            games: &season.current_simulation_games,
+            schedule,
+            strengths_of_schedule: &season.current_simulation_result.strengths_of_schedule,
+            tiebreakers: &season.config.tiebreakers,
+            playoff_format: season.playoff_format(),
+        }
+    }
+
+    /// Clubs left after the head-to-head sweep step among `tied_teams`, which are AFC clubs
+    /// from different divisions tied for a wildcard.
+    fn head_to_head_sweep(games: &[FixtureGame], tied_teams: &[i32]) -> Vec<i32> {
+        let mut season: Season = season(&[1, 3, 5, 7], games);
+        season.current_simulation_games = season.actual_games.clone();
+        let schedule: ScheduleIndex = ScheduleIndex::new(&season.current_simulation_games);
+        let mut team_pool: TeamPool = TeamPool::new(
+            tied_teams.to_vec(),
+            PoolType::Wildcard,
+            pool_context(&season, &schedule),
+        );
+        team_pool.break_by_head_to_head_sweep();
+        sorted_ids(&team_pool.tied_teams)
+    }
+
+    #[test]
+    fn head_to_head_sweep_leaves_club_that_beat_everyone() {
+        let three_clubs: [FixtureGame; 3] = [
+            (1, 3, Some((20, 10))),
+            (5, 1, Some((10, 20))),
+            (3, 5, Some((20, 10))),
+        ];
+        assert_eq!(head_to_head_sweep(&three_clubs, &[1, 3, 5]), vec![1]);
+
+        let four_clubs: [FixtureGame; 6] = [
+            (1, 3, Some((20, 10))),
+            (1, 5, Some((20, 10))),
+            (7, 1, Some((10, 20))),
+            (3, 5, Some((10, 20))),
+            (5, 7, Some((10, 20))),
+            (7, 3, Some((10, 20))),
+        ];
+        assert_eq!(head_to_head_sweep(&four_clubs, &[1, 3, 5, 7]), vec![1]);
+    }
+
+    #[test]
+    fn head_to_head_sweep_eliminates_club_that_lost_to_everyone() {
+        let three_clubs: [FixtureGame; 3] = [
+            (1, 3, Some((17, 17))),
+            (1, 5, Some((20, 10))),
+            (5, 3, Some((10, 20))),
+        ];
+        assert_eq!(head_to_head_sweep(&three_clubs, &[1, 3, 5]), vec![1, 3]);
+
+        let four_clubs: [FixtureGame; 6] = [
+            (1, 3, Some((20, 10))),
+            (3, 5, Some((20, 10))),
+            (5, 1, Some((20, 10))),
+            (1, 7, Some((20, 10))),
+            (3, 7, Some((20, 10))),
+            (7, 5, Some((10, 20))),
+        ];
+        assert_eq!(
+            head_to_head_sweep(&four_clubs, &[1, 3, 5, 7]),
+            vec![1, 3, 5]
+        );
+    }
+
+    #[test]
+    fn head_to_head_sweep_needs_every_club_to_have_played() {
+        // 1 beat everyone it played and 5 lost to everyone it played, but they never met.
+        let three_clubs: [FixtureGame; 2] = [(1, 3, Some((20, 10))), (3, 5, Some((20, 10)))];
+        assert_eq!(head_to_head_sweep(&three_clubs, &[1, 3, 5]), vec![1, 3, 5]);
+
+        let four_clubs: [FixtureGame; 5] = [
+            (1, 3, Some((20, 10))),
+            (1, 5, Some((20, 10))),
+            (3, 5, Some((20, 10))),
+            (3, 7, Some((20, 10))),
+            (5, 7, Some((20, 10))),
+        ];
+        assert_eq!(
+            head_to_head_sweep(&four_clubs, &[1, 3, 5, 7]),
+            vec![1, 3, 5, 7]
+        );
+    }
+
+    /// Winner of a division of clubs 1-4 after `games`, and each step that separated them.
+    /// Clubs 5 and up are opponents outside it.
+    fn division_tiebreak(games: &[FixtureGame]) -> (Vec<i32>, Vec<TraceStep>) {
+        division_tiebreak_with_touchdowns(games, &[])
+    }
+
+    /// `division_tiebreak`, with the first games' home and away touchdowns from `touchdowns`.
+    fn division_tiebreak_with_touchdowns(
+        games: &[FixtureGame],
+        touchdowns: &[(i32, i32)],
+    ) -> (Vec<i32>, Vec<TraceStep>) {
+        let teams = (1..=14).map(|team_id| match team_id <= 4 {
+            true => Team {
+                division: String::from("AFC East"),
+                ..team(team_id)
+            },
+            false => team(team_id),
+        });
+        let mut season: Season = season_of_teams(teams, games);
+        for (game_id, (home_touchdowns, away_touchdowns)) in (1..).zip(touchdowns) {
+            let game: &mut Game = season.actual_games.get_mut(&game_id).unwrap();
+            game.home_touchdowns = Some(*home_touchdowns);
+            game.away_touchdowns = Some(*away_touchdowns);
+        }
+        season.current_simulation_games = season.actual_games.clone();
+        season.populate_records().unwrap();
+        season.calculate_percentages();
+        season.calculate_strengths_of_schedule();
+        let winner: Vec<i32> = season
+            .evaluate_pool(vec![1, 2, 3, 4], PoolType::Division)
+            .unwrap();
+        let steps = season
+            .current_simulation_result
+            .tiebreaks
+            .into_iter()
+            .map(|decision| (decision.step, decision.tied_teams, decision.remaining_teams))
+            .collect();
+        (winner, steps)
+    }
+
+    /// Modeled on the 2002 AFC East, scaled down to fewer games: the Jets (1), Patriots (2)
+    /// and Dolphins (3) finished tied and split their head-to-head games. Miami fell out on
+    /// division record, and the Jets then won the division over New England on record in
+    /// common games.
+    #[test]
+    fn three_club_division_tie_from_2002_afc_east() {
+        let win: Option<(i32, i32)> = Some((20, 10));
+        let loss: Option<(i32, i32)> = Some((10, 20));
+        let games: Vec<FixtureGame> = vec![
+            (1, 2, win),
+            (2, 1, win),
+            (1, 3, win),
+            (3, 1, win),
+            (2, 3, win),
+            (3, 2, win),
+            (1, 4, win),
+            (4, 1, loss),
+            (2, 4, win),
+            (4, 2, loss),
+            (3, 4, loss),
+            (4, 3, win),
+            (1, 5, win),
+            (1, 6, win),
+            (1, 9, loss),
+            (1, 10, loss),
+            (2, 5, loss),
+            (2, 6, loss),
+            (2, 7, win),
+            (2, 8, win),
+            (3, 11, win),
+            (3, 12, win),
+            (3, 13, win),
+            (3, 14, win),
+        ];
+
+        let (winner, steps) = division_tiebreak(&games);
+        assert_eq!(winner, vec![1]);
+        assert_eq!(
+            steps,
+            vec![
+                (
+                    TiebreakStep::OverallPercent,
+                    vec![1, 2, 3, 4],
+                    vec![1, 2, 3]
+                ),
+                (
+                    TiebreakStep::Rule(TiebreakRule::DivisionPercent),
+                    vec![1, 2, 3],
+                    vec![1, 2]
+                ),
+                (
+                    TiebreakStep::Rule(TiebreakRule::CommonGames(0)),
+                    vec![1, 2],
+                    vec![1]
+                ),
+            ]
+        );
+    }
+
+    /// Three clubs that beat each other in a circle, where club 3 falls out on common games.
+    /// The two left start again at head-to-head, which club 1 wins; carrying on from common
+    /// games instead would have picked club 2.
+    #[test]
+    fn division_tie_starts_over_after_eliminating_a_club() {
+        let win: Option<(i32, i32)> = Some((20, 10));
+        let loss: Option<(i32, i32)> = Some((10, 20));
+        let games: Vec<FixtureGame> = vec![
+            (1, 2, win),
+            (2, 1, loss),
+            (2, 3, win),
+            (3, 2, loss),
+            (3, 1, win),
+            (1, 3, loss),
+            (1, 4, win),
+            (4, 1, win),
+            (2, 4, win),
+            (4, 2, win),
+            (3, 4, win),
+            (4, 3, win),
+            (1, 5, win),
+            (1, 6, win),
+            (1, 8, loss),
+            (2, 5, win),
+            (2, 6, win),
+            (2, 9, loss),
+            (3, 5, loss),
+            (3, 6, win),
+            (3, 7, win),
+        ];
+
+        let (winner, steps) = division_tiebreak(&games);
+        assert_eq!(winner, vec![1]);
+        assert_eq!(
+            steps,
+            vec![
+                (
+                    TiebreakStep::OverallPercent,
+                    vec![1, 2, 3, 4],
+                    vec![1, 2, 3]
+                ),
+                (
+                    TiebreakStep::Rule(TiebreakRule::CommonGames(0)),
+                    vec![1, 2, 3],
+                    vec![1, 2]
+                ),
+                (
+                    TiebreakStep::Rule(TiebreakRule::HeadToHead),
+                    vec![1, 2],
+                    vec![1]
+                ),
+            ]
+        );
+    }
+
+    /// Share of `team_id`'s home games won over `sims` simulations of `season`.
+    fn home_win_share(season: &mut Season, team_id: i32, sims: u32) -> f64 {
+        let mut home_games: u32 = 0;
+        let mut home_wins: u32 = 0;
+        for _ in 0..sims {
+            season.run_simulation(false).unwrap();
+            for game in season.current_simulation_games.values() {
+                if game.home_team.team_id == team_id {
+                    home_games += 1;
+                    if game.game_result == Some(GameResult::HomeWin) {
+                        home_wins += 1;
+                    }
+                }
+            }
+        }
+        f64::from(home_wins) / f64::from(home_games)
+    }
+
+    #[test]
+    fn venue_home_advantage_wins_more_home_games() {
+        let mut season: Season = league(0).with_game_simulator(CoinFlip);
+        season.venue_home_advantage.insert(1, 0.3);
+        season.set_seed(3);
+        let boosted: f64 = home_win_share(&mut season, 1, 200);
+        let even: f64 = home_win_share(&mut season, 2, 200);
+        assert!(
+            (boosted - 0.8).abs() < 0.05,
+            "boosted home win share {}",
+            boosted
+        );
+        assert!((even - 0.5).abs() < 0.05, "even home win share {}", even);
+    }
+
+    #[test]
+    fn venue_home_advantage_takes_precedence_over_the_fixed_home_field() {
+        let mut season: Season = league(0);
+        season.config.home_field = HomeField::new(0.6);
+        season.config.home_field.game_overrides.insert(2, 0.9);
+        season.venue_home_advantage.insert(1, 0.3);
+        season.home_field_advantage = 0.1;
+        let probability =
+            |game_id: i32| season.home_win_probability(&season.actual_games[&game_id]);
+        // Game 2 has its own override, club 1 hosts game 1 and club 2 hosts game 8.
+        assert!((probability(2) - 0.9).abs() < 1e-9);
+        assert!((probability(1) - 0.8).abs() < 1e-9);
+        assert!((probability(8) - 0.6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn venue_home_advantage_is_rejected_with_a_model_that_has_home_field() {
+        let mut season: Season = league(0).with_game_simulator(HomeField::new(0.6));
+        season.venue_home_advantage.insert(1, 0.3);
+        assert!(matches!(
+            season.simulate_current_state(10),
+            Err(Error::InvalidArgument(_))
+        ));
+
+        let mut season: Season = league(0);
+        season.elo_model = Some(EloModel::from_historical(&[]));
+        season.home_field_advantage = 0.05;
+        assert!(matches!(
+            season.simulate_current_state(10),
+            Err(Error::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn modeling_percent_leans_on_prior_early_and_record_late() {
+        let config: SimulationConfig = SimulationConfig {
+            shrinkage_games: 4.0,
+            shrinkage_prior: 0.5,
+            ..SimulationConfig::default()
+        };
+
+        // 1-0 after week 1: four games of prior outweigh one game of record.
+        let week_1: f64 = config.modeling_percent((1, 0, 0));
+        assert!((week_1 - 0.6).abs() < 1e-9);
+        assert!((week_1 - 0.5).abs() < (week_1 - 1.0).abs());
+
+        // 13-2 after week 15: the record outweighs the same four games of prior.
+        let week_15: f64 = config.modeling_percent((13, 2, 0));
+        let record: f64 = 13.0 / 15.0;
+        assert!((week_15 - 15.0 / 19.0).abs() < 1e-9);
+        assert!((week_15 - record).abs() < (week_15 - 0.5).abs());
+    }
+
+    #[test]
+    fn modeling_percent_without_shrinkage_is_the_record() {
+        let config: SimulationConfig = SimulationConfig::default();
+        assert_eq!(config.modeling_percent((1, 0, 0)), 1.0);
+        assert_eq!(config.modeling_percent((13, 1, 1)), 13.5 / 15.0);
+        assert_eq!(config.modeling_percent((0, 0, 0)), 0.5);
+    }
+
+    #[test]
+    fn validate_schedule_symmetry_reports_missing_division_game() {
+        let mut season: Season = league(0);
+        assert!(season.validate_schedule_symmetry().is_empty());
+
+        let game_id: i32 = season
+            .actual_games
+            .values()
+            .find(|game| game.home_team.team_id == 2 && game.away_team.team_id == 1)
+            .map(|game| game.game_id)
+            .unwrap();
+        season.actual_games.remove(&game_id);
+        assert_eq!(
+            season.validate_schedule_symmetry(),
+            vec![ScheduleIssue {
+                team_id: 2,
+                opponent_id: 1,
+                expected: 1,
+                found: 0,
+            }]
+        );
+
+        season.config.division_schedule = DivisionSchedulePattern::Meetings(2);
+        assert_eq!(
+            season.validate_schedule_symmetry(),
+            vec![ScheduleIssue {
+                team_id: 1,
+                opponent_id: 2,
+                expected: 2,
+                found: 1,
+            }]
+        );
+    }
+
+    #[test]
+    fn elo_from_historical_rates_dominant_team_highest() {
+        let mut season: Season = league(64);
+        for game in season.actual_games.values_mut() {
+            match (game.home_team.team_id, game.away_team.team_id) {
+                (5, _) => game.game_result = Some(GameResult::HomeWin),
+                (_, 5) => game.game_result = Some(GameResult::AwayWin),
+                _ => {}
+            }
+        }
+        let games: Vec<Game> = season.actual_games.values().cloned().collect();
+        let elo_model: EloModel = EloModel::from_historical(&games);
+
+        let top_team_id: i32 = (1..=16)
+            .max_by(|a, b| elo_model.rating(*a).total_cmp(&elo_model.rating(*b)))
+            .unwrap();
+        assert_eq!(top_team_id, 5);
+        assert!(elo_model.rating(5) > elo_model.initial_rating);
+    }
+
+    /// Clubs 1 and 2 playing each other 16 times with the first `decided_games` played, club
+    /// 1 winning all but games 5, 10 and 15, and priors of 0.2 for club 1 and 0.8 for club 2.
+    fn season_with_priors(decided_games: usize) -> Season {
+        let games: Vec<FixtureGame> = (1..=16)
+            .map(|game_id| {
+                let (home_team_id, away_team_id): (i32, i32) = match game_id % 2 {
+                    1 => (1, 2),
+                    _ => (2, 1),
+                };
+                let club_1_wins: bool = game_id % 5 != 0;
+                let home_wins: bool = (home_team_id == 1) == club_1_wins;
+                let score: Option<(i32, i32)> = match (game_id <= decided_games, home_wins) {
+                    (false, _) => None,
+                    (true, true) => Some((24, 10)),
+                    (true, false) => Some((10, 24)),
+                };
+                (home_team_id, away_team_id, score)
+            })
+            .collect();
+        let mut season: Season = season(&[1, 2], &games);
+        season.config.shrinkage_games = 4.0;
+        season.set_priors(HashMap::from([(1, 0.2), (2, 0.8)]));
+        season.refresh_modeling_percents();
+        season
+    }
+
+    #[test]
+    fn set_priors_dominates_in_week_1() {
+        let season: Season = season_with_priors(1);
+        // Club 1 is 1-0 and club 2 is 0-1.
+        let club_1: f64 = season.modeling_percents[&1];
+        let club_2: f64 = season.modeling_percents[&2];
+        assert!((club_1 - 1.8 / 5.0).abs() < 1e-9);
+        assert!((club_2 - 3.2 / 5.0).abs() < 1e-9);
+        assert!((club_1 - 0.2).abs() < (club_1 - 1.0).abs());
+        assert!((club_2 - 0.8).abs() < (club_2 - 0.0).abs());
+    }
+
+    #[test]
+    fn record_dominates_in_week_16() {
+        let season: Season = season_with_priors(16);
+        // Club 1 is 13-3 and club 2 is 3-13.
+        let club_1: f64 = season.modeling_percents[&1];
+        let club_2: f64 = season.modeling_percents[&2];
+        assert!((club_1 - 13.8 / 20.0).abs() < 1e-9);
+        assert!((club_2 - 6.2 / 20.0).abs() < 1e-9);
+        assert!((club_1 - 13.0 / 16.0).abs() < (club_1 - 0.2).abs());
+        assert!((club_2 - 3.0 / 16.0).abs() < (club_2 - 0.8).abs());
+    }
+
+    #[test]
+    fn current_record_matches_known_standings() {
+        let mut season: Season = season(
+            &[1, 2, 3, 9],
+            &[
+                (1, 2, Some((24, 17))),
+                (2, 1, Some((20, 10))),
+                (1, 3, Some((13, 13))),
+                (9, 1, Some((3, 30))),
+                (3, 1, None),
+            ],
+        );
+        assert_eq!(
+            season.current_record(1).unwrap(),
+            TeamRecord {
+                overall_record: (2, 1, 1),
+                overall_percent: 625,
+                conference_record: (1, 1, 1),
+                conference_percent: 500,
+                division_record: (1, 1, 0),
+                division_percent: 500,
+                points_for: 77,
+                points_against: 53,
+                conference_points_for: 47,
+                conference_points_against: 50,
+                unscored_games: 0,
+            }
+        );
+        assert_eq!(season.current_record(9).unwrap().overall_record, (0, 1, 0));
+
+        // A newly decided game isn't hidden by the cached records.
+        let game: &mut Game = season.actual_games.get_mut(&5).unwrap();
+        game.game_result = Some(GameResult::AwayWin);
+        game.home_score = Some(7);
+        game.away_score = Some(14);
+        let record: TeamRecord = season.current_record(1).unwrap();
+        assert_eq!(record.overall_record, (3, 1, 1));
+        assert_eq!(record.conference_record, (2, 1, 1));
+
+        assert!(matches!(
+            season.current_record(4),
+            Err(Error::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn baseline_matches_current_state_simulation() {
+        let mut season: Season = league(60);
+        season.config.exact_threshold = 0;
+        season.set_seed(4);
+        match season.check_baseline_consistency(300) {
+            Ok(()) => {}
+            Err(Error::Inconsistent(discrepancies)) => {
+                panic!("baseline odds differ:\n{}", discrepancies.join("\n"))
+            }
+            Err(error) => panic!("simulation failed: {}", error),
+        }
+    }
+
+    #[test]
+    fn partial_league_is_an_error() {
+        let teams: Vec<Team> = [1, 2, 3, 9].iter().map(|t| team(*t)).collect();
+        let games: Vec<Game> = season(
+            &[1, 2, 3, 9],
+            &[(1, 2, Some((24, 17))), (3, 9, Some((10, 20)))],
+        )
+        .actual_games
+        .into_values()
+        .collect();
+        assert!(matches!(
+            compute_standings(&games, &teams),
+            Err(Error::InvalidData(_))
+        ));
+    }
+
+    /// Clubs left after the net points step among `tied_teams`, in the last simulation run.
+    fn net_points_tiebreak(season: &Season, tied_teams: &[i32]) -> Vec<i32> {
+        let schedule: ScheduleIndex = ScheduleIndex::new(&season.current_simulation_games);
+        let mut team_pool: TeamPool = TeamPool::new(
+            tied_teams.to_vec(),
+            PoolType::Wildcard,
+            pool_context(season, &schedule),
+        );
+        team_pool
+            .apply_tiebreak_rule(TiebreakRule::NetPoints, &mut StdRng::seed_from_u64(0))
+            .unwrap();
+        sorted_ids(&team_pool.tied_teams)
+    }
+
+    #[test]
+    fn forced_result_drops_the_real_score() {
+        // Game 3 is club 1's 24-10 home win over club 4, which decides net points between them.
+        let mut season: Season = league(64);
+        season.simulate_current_state(1).unwrap();
+        assert_eq!(net_points_tiebreak(&season, &[1, 4]), vec![1]);
+
+        season.simulate_for_game(3, GameResult::AwayWin, 1).unwrap();
+        let game: &Game = &season.current_simulation_games[&3];
+        assert_eq!((game.home_score, game.away_score), (None, None));
+        assert_eq!(game.to_string(), "Week 1: T4 @ T1 — T4 win");
+        assert_eq!(net_points_tiebreak(&season, &[1, 4]), vec![1, 4]);
+
+        season
+            .simulate_with_overrides(vec![(3, GameResult::AwayWin)], 1)
+            .unwrap();
+        let record: &TeamRecord = &season.current_simulation_result.team_records[&4];
+        assert_eq!(record.unscored_games, 1);
+        assert_eq!(net_points_tiebreak(&season, &[1, 4]), vec![1, 4]);
+
+        // A forced result that agrees with the real one keeps its score.
+        season.simulate_for_game(3, GameResult::HomeWin, 1).unwrap();
+        assert_eq!(net_points_tiebreak(&season, &[1, 4]), vec![1]);
+    }
+
+    #[test]
+    fn tiebreak_rules_skip_undecided_games() {
+        let games: [FixtureGame; 4] = [
+            (1, 5, Some((20, 10))),
+            (3, 5, Some((30, 10))),
+            (1, 7, None),
+            (3, 7, None),
+        ];
+        let mut season: Season = season(&[1, 3, 5, 7], &games);
+        season.current_simulation_games = season.actual_games.clone();
+        season.current_simulation_result.team_records = season.decided_base_records();
+        season.calculate_percentages();
+        season.calculate_strengths_of_schedule();
+        let schedule: ScheduleIndex = ScheduleIndex::new(&season.current_simulation_games);
+        let mut rng: StdRng = StdRng::seed_from_u64(0);
+        for (rule, remaining_teams) in [
+            (TiebreakRule::CommonGames(0), vec![1, 3]),
+            (TiebreakRule::StrengthOfVictory, vec![1, 3]),
+            (TiebreakRule::NetPointsCommonGames, vec![3]),
+        ] {
+            let mut team_pool: TeamPool = TeamPool::new(
+                vec![1, 3],
+                PoolType::Wildcard,
+                pool_context(&season, &schedule),
+            );
+            team_pool.apply_tiebreak_rule(rule, &mut rng).unwrap();
+            assert_eq!(sorted_ids(&team_pool.tied_teams), remaining_teams);
+        }
+    }
+
+    #[test]
+    fn finish_order_puts_teams_without_a_finish_last() {
+        let mut result: CurrentSimulationResult = CurrentSimulationResult::new();
+        result.playoff_seeding.insert(1, HashSet::from([3]));
+        result.draft_order.insert(31, 1);
+        result.draft_order.insert(32, 2);
+        assert_eq!(result.finish_order(&[9, 1, 2, 3, 8]), vec![3, 2, 1, 9, 8]);
+    }
+
+    #[test]
+    fn force_tie_scenario_gives_every_team_the_target_record() {
+        let mut season: Season = league(0);
+        season.set_seed(5);
+        season.force_tie_scenario(&[1, 2, 3], (4, 3, 1)).unwrap();
+        season.run_simulation(false).unwrap();
+        for team_id in [1, 2, 3] {
+            let record: &TeamRecord = &season.current_simulation_result.team_records[&team_id];
+            assert_eq!(record.overall_record, (4, 3, 1), "team {}", team_id);
+        }
+
+        // Every club plays eight games, and no club can win them all if the whole conference
+        // has to.
+        assert!(matches!(
+            season.force_tie_scenario(&[1, 2], (5, 4, 0)),
+            Err(Error::InvalidArgument(_))
+        ));
+        let conference: Vec<i32> = (1..=8).collect();
+        assert!(matches!(
+            season.force_tie_scenario(&conference, (8, 0, 0)),
+            Err(Error::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn exact_mode_matches_hand_computed_two_game_odds() {
+        // Games 63 (club 14 hosting 13) and 64 (club 16 hosting 15) are left, each a home win
+        // with probability 0.7, so the four combinations come up 49%, 21%, 21% and 9%.
+        let mut season: Season = league(62);
+        season.config.home_field = HomeField::new(0.7);
+        season.set_seed(6);
+        assert_eq!(season.simulation_mode(1000), SimulationMode::Exact);
+        season.simulate_current_state(1000).unwrap();
+        let report: SimulationReport = season.report();
+
+        for (team_id, home_win_share) in [(14, 0.7), (13, 0.3), (16, 0.7), (15, 0.3)] {
+            let wins: u16 = season.current_record(team_id).unwrap().overall_record.0;
+            let wins: u8 = u8::try_from(wins).unwrap();
+            let odds: TeamOdds = report.team_odds(team_id).unwrap();
+            assert_eq!(odds.simulations, 1000);
+            assert!((odds.expected_wins - (f64::from(wins) + home_win_share)).abs() < 1e-9);
+            assert!((odds.win_totals[&(wins + 1)] - home_win_share).abs() < 1e-9);
+            assert!((odds.win_totals[&wins] - (1.0 - home_win_share)).abs() < 1e-9);
+        }
+    }
+
+    /// Clubs 1 and 2 split their meetings by `margins` and beat clubs 3 and 4, winning the
+    /// games against club 3 by `club_3_scores`. Everything before the points steps stays
+    /// tied.
+    fn points_tie_games(
+        margins: [(i32, i32); 2],
+        club_3_scores: [(i32, i32); 2],
+    ) -> Vec<FixtureGame> {
+        vec![
+            (1, 2, Some(margins[0])),
+            (2, 1, Some(margins[1])),
+            (1, 3, Some(club_3_scores[0])),
+            (2, 3, Some(club_3_scores[1])),
+            (1, 4, Some((20, 10))),
+            (2, 4, Some((20, 10))),
+        ]
+    }
+
+    #[test]
+    fn division_tie_goes_to_net_points_in_common_games() {
+        let games: Vec<FixtureGame> = points_tie_games([(20, 10), (20, 10)], [(20, 10), (27, 10)]);
+        let (winner, steps) = division_tiebreak(&games);
+        assert_eq!(winner, vec![2]);
+        assert_eq!(
+            steps,
+            vec![
+                (TiebreakStep::OverallPercent, vec![1, 2, 3, 4], vec![1, 2]),
+                (
+                    TiebreakStep::Rule(TiebreakRule::NetPointsCommonGames),
+                    vec![1, 2],
+                    vec![2]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn division_tie_goes_to_net_points_in_all_games() {
+        // Club 2 won its meeting by 30 and club 1 by 10, which only counts once every game is.
+        let games: Vec<FixtureGame> = points_tie_games([(20, 10), (40, 10)], [(20, 10), (20, 10)]);
+        let (winner, steps) = division_tiebreak(&games);
+        assert_eq!(winner, vec![2]);
+        assert_eq!(
+            steps,
+            vec![
+                (TiebreakStep::OverallPercent, vec![1, 2, 3, 4], vec![1, 2]),
+                (
+                    TiebreakStep::Rule(TiebreakRule::NetPoints),
+                    vec![1, 2],
+                    vec![2]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn division_tie_goes_to_net_touchdowns() {
+        let games: Vec<FixtureGame> = points_tie_games([(20, 10), (20, 10)], [(20, 10), (20, 10)]);
+        // Each club scored two touchdowns in its home meeting, but club 2 allowed none.
+        let touchdowns: [(i32, i32); 6] = [(2, 1), (2, 0), (2, 1), (2, 1), (2, 1), (2, 1)];
+        let (winner, steps) = division_tiebreak_with_touchdowns(&games, &touchdowns);
+        assert_eq!(winner, vec![2]);
+        assert_eq!(
+            steps,
+            vec![
+                (TiebreakStep::OverallPercent, vec![1, 2, 3, 4], vec![1, 2]),
+                (
+                    TiebreakStep::Rule(TiebreakRule::NetTouchdowns),
+                    vec![1, 2],
+                    vec![2]
+                ),
+            ]
+        );
+
+        // Without touchdown counts for every game the step passes, and a coin toss decides.
+        let (_, steps) = division_tiebreak_with_touchdowns(&games, &touchdowns[..5]);
+        assert_eq!(
+            steps
+                .last()
+                .map(|(step, tied_teams, _)| (*step, tied_teams.clone())),
+            Some((TiebreakStep::Rule(TiebreakRule::CoinToss), vec![1, 2]))
+        );
+    }
+
+    #[test]
+    fn coin_tosses_count_each_team_in_a_tie_settled_by_one() {
+        // Every game is a 17-17 tie except club 1's win over club 2, so the other divisions
+        // and every playoff place but the AFC top seed and club 2's miss need a coin toss.
+        let mut season: Season = league(64);
+        for game in season.actual_games.values_mut() {
+            let score: (i32, i32) = match game.game_id {
+                1 => (20, 10),
+                _ => (17, 17),
+            };
+            game.home_score = Some(score.0);
+            game.away_score = Some(score.1);
+            game.game_result = match score.0 > score.1 {
+                true => Some(GameResult::HomeWin),
+                false => Some(GameResult::Tie),
+            };
+        }
+        season.current_simulation_base_games = season.actual_games.clone();
+        season.set_seed(7);
+        season.simulate_current_state(20).unwrap();
+
+        for team_id in 1..=16 {
+            let lookup = SimulationResultLookup::new(&team_id, None);
+            let expected: i32 = match team_id {
+                1 | 2 => 0,
+                _ => 20,
+            };
+            assert_eq!(
+                season.overall_results[&lookup].coin_tosses, expected,
+                "team {}",
+                team_id
+            );
+        }
+        assert!((season.report().team_odds(3).unwrap().coin_toss - 1.0).abs() < 1e-9);
+    }
+
+    /// Teams that won `round` of `bracket`.
+    fn round_winners(bracket: &PlayoffBracket, round: PlayoffRound) -> HashSet<i32> {
+        bracket.round_winners[&round].iter().copied().collect()
+    }
+
+    #[test]
+    fn playoff_teams_draft_in_order_of_elimination() {
+        let mut season: Season = league(64);
+        season.set_seed(8);
+        for _ in 0..10 {
+            season.run_simulation(false).unwrap();
+            let result: &CurrentSimulationResult = &season.current_simulation_result;
+            let bracket: &PlayoffBracket = result.playoff_bracket.as_ref().unwrap();
+            let picks = |first: u8, last: u8| -> HashSet<i32> {
+                (first..=last)
+                    .map(|pick| result.draft_order[&pick])
+                    .collect()
+            };
+            let playoff_teams: HashSet<i32> = result
+                .division_winners
+                .union(&result.wildcard_teams)
+                .copied()
+                .collect();
+            let all_teams: HashSet<i32> = (1..=16).collect();
+            let wild_card: HashSet<i32> = round_winners(bracket, PlayoffRound::WildCard);
+            let divisional: HashSet<i32> = round_winners(bracket, PlayoffRound::Divisional);
+            let conference: HashSet<i32> =
+                round_winners(bracket, PlayoffRound::ConferenceChampionship);
+            let super_bowl: HashSet<i32> = round_winners(bracket, PlayoffRound::SuperBowl);
+
+            assert_eq!(result.draft_order.len(), 16);
+            assert_eq!(picks(1, 2), &all_teams - &playoff_teams);
+            assert_eq!(picks(3, 8), &playoff_teams - &wild_card);
+            assert_eq!(picks(9, 12), &wild_card - &divisional);
+            assert_eq!(picks(13, 14), &divisional - &conference);
+            assert_eq!(picks(15, 15), &conference - &super_bowl);
+            assert_eq!(result.draft_order[&16], bracket.champion.unwrap());
+
+            // Wild-card round losers pick worst record first.
+            let percents: Vec<u16> = (3..=8)
+                .map(|pick| result.team_records[&result.draft_order[&pick]].overall_percent)
+                .collect();
+            assert!(percents.windows(2).all(|pair| pair[0] <= pair[1]));
+        }
+    }
+
+    /// Draft order of `pool`, earliest pick first, and each step that separated the clubs.
+    fn draft_order_tiebreak(games: &[FixtureGame], pool: &[i32]) -> (Vec<i32>, Vec<TraceStep>) {
+        let mut season: Season = season(&[1, 2, 3, 9], games);
+        season.current_simulation_games = season.actual_games.clone();
+        season.populate_records().unwrap();
+        season.calculate_percentages();
+        season.calculate_strengths_of_schedule();
+        let ranking: Vec<i32> = season
+            .evaluate_pool(pool.to_vec(), PoolType::DraftOrder)
+            .unwrap();
+        let steps = season
+            .current_simulation_result
+            .tiebreaks
+            .into_iter()
+            .map(|decision| (decision.step, decision.tied_teams, decision.remaining_teams))
+            .collect();
+        (ranking, steps)
+    }
+
+    #[test]
+    fn draft_order_ties_use_division_and_conference_tiebreakers() {
+        // Clubs 1, 2 and 3 each go 1-1 against the same opponents: 1 beat division rival 2,
+        // 2 beat 3 and 3 beat 1.
+        let games: [FixtureGame; 3] = [
+            (1, 2, Some((20, 10))),
+            (2, 3, Some((20, 10))),
+            (3, 1, Some((20, 10))),
+        ];
+
+        // Division rivals: club 1 wins head-to-head and picks later.
+        let (ranking, steps) = draft_order_tiebreak(&games, &[1, 2]);
+        assert_eq!(ranking, vec![2, 1]);
+        assert_eq!(
+            steps,
+            vec![(
+                TiebreakStep::Rule(TiebreakRule::HeadToHead),
+                vec![1, 2],
+                vec![1]
+            )]
+        );
+
+        // Same conference, different divisions: club 3 wins head-to-head.
+        let (ranking, steps) = draft_order_tiebreak(&games, &[1, 3]);
+        assert_eq!(ranking, vec![1, 3]);
+        assert_eq!(
+            steps,
+            vec![(
+                TiebreakStep::Rule(TiebreakRule::HeadToHead),
+                vec![1, 3],
+                vec![3]
+            )]
+        );
+    }
+
+    #[test]
+    fn playoff_round_tallies_add_up_over_the_bracket() {
+        let mut season: Season = league(40);
+        season.set_seed(9);
+        season.simulate_current_state(200).unwrap();
+        let results: Vec<&TeamSimulationResults> = (1..=16)
+            .map(|team_id| &season.overall_results[&SimulationResultLookup::new(&team_id, None)])
+            .collect();
+        let round_wins = |round: PlayoffRound| -> i32 {
+            results
+                .iter()
+                .map(|result| result.playoff_round_wins.get(&round).copied().unwrap_or(0))
+                .sum()
+        };
+
+        // Per conference, the top seed's bye and three wild-card winners, then two, one and
+        // the champion.
+        assert_eq!(round_wins(PlayoffRound::WildCard), 8 * 200);
+        assert_eq!(round_wins(PlayoffRound::Divisional), 4 * 200);
+        assert_eq!(round_wins(PlayoffRound::ConferenceChampionship), 2 * 200);
+        assert_eq!(round_wins(PlayoffRound::SuperBowl), 200);
+        for result in results.iter() {
+            assert!(result
+                .playoff_round_wins
+                .values()
+                .all(|wins| *wins <= result.made_playoffs));
+        }
+
+        // Every top seed gets a bye and hosts in the divisional round.
+        for team_id in 1..=16 {
+            let result: &TeamSimulationResults = results[team_id - 1];
+            let top_seeds: i32 = result.playoff_seedings.get(&1).copied().unwrap_or(0);
+            assert!(
+                result
+                    .playoff_round_wins
+                    .get(&PlayoffRound::WildCard)
+                    .copied()
+                    .unwrap_or(0)
+                    >= top_seeds
+            );
+            assert!(result.hosted_playoff_games >= top_seeds);
+        }
+    }
+
+    #[test]
+    fn seasons_through_2019_seed_six_teams_with_two_byes() {
+        let mut season: Season = league(64);
+        season.season_year = 2019;
+        assert_eq!(season.playoff_format(), PlayoffFormat::SixTeams);
+        season.set_seed(10);
+        season.run_simulation(false).unwrap();
+        let result: &CurrentSimulationResult = &season.current_simulation_result;
+
+        assert_eq!(result.wildcard_teams.len(), 4);
+        assert!(result
+            .playoff_seeding
+            .get(&7)
+            .is_none_or(|teams| teams.is_empty()));
+        let bracket: &PlayoffBracket = result.playoff_bracket.as_ref().unwrap();
+        let wild_card_games: Vec<&PlayoffMatchup> = bracket
+            .games
+            .iter()
+            .filter(|game| game.round == PlayoffRound::WildCard)
+            .collect();
+        assert_eq!(wild_card_games.len(), 4);
+        // Seeds 1 and 2 sit out the wild-card round and count as having won it.
+        for seed in [1, 2] {
+            for team_id in result.playoff_seeding[&seed].iter() {
+                assert!(wild_card_games
+                    .iter()
+                    .all(|game| game.home_team_id != *team_id && game.away_team_id != *team_id));
+                assert!(bracket.round_winners[&PlayoffRound::WildCard].contains(team_id));
+            }
+        }
+        // Non-playoff clubs take the first four picks.
+        let playoff_teams: HashSet<i32> = result
+            .division_winners
+            .union(&result.wildcard_teams)
+            .copied()
+            .collect();
+        assert!((1..=4).all(|pick| !playoff_teams.contains(&result.draft_order[&pick])));
+        assert!((5..=16).all(|pick| playoff_teams.contains(&result.draft_order[&pick])));
+    }
+}
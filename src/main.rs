@@ -1,50 +1,703 @@
+use chrono::Datelike;
+use clap::{Parser, Subcommand, ValueEnum};
 use nfl_schedule_simulator::*;
-use std::io::Write;
-use std::time::Instant;
+use postgres::Row;
+use progress_bars::ProgressBars;
+use std::env;
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::process;
+use std::thread;
+use std::time::Duration;
+use tracing_subscriber::EnvFilter;
+use what_if::WhatIf;
 
-// mod migrations;
+#[cfg(feature = "tui")]
+mod dashboard;
+mod migrations;
+mod progress_bars;
+mod what_if;
 
-fn main() {
-    // migrations::rebuild();
-    // migrations::create();
-    // migrations::destroy();
-    let season_year: i32 = 2023;
-    let mut season: Season = Season::new_from_year(season_year);
+#[derive(Parser)]
+#[command(version, about = "Monte Carlo playoff odds for an NFL season")]
+struct Cli {
+    /// Log more detail: -v for info, -vv for debug and -vvv for trace. RUST_LOG overrides it
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Simulate a season and store the results in the database
+    Simulate {
+        #[arg(long, default_value_t = default_season())]
+        season: i32,
+        /// Simulations of the current state and of each game result
+        #[arg(long, default_value_t = 10000)]
+        sims: u64,
+        /// Also simulate each result of games that have already been played
+        #[arg(long)]
+        include_decided: bool,
+        #[arg(long)]
+        seed: Option<u64>,
+        /// Home team's chance of winning each simulated game
+        #[arg(long, default_value_t = DEFAULT_HOME_WIN_PROBABILITY)]
+        home_win_probability: f64,
+        /// SQLite file to read the season from and store the results in, instead of Postgres
+        #[arg(long, env = "NFL_SQLITE_PATH")]
+        sqlite: Option<String>,
+        /// Also write each scenario's results to this CSV file as it finishes
+        #[arg(long)]
+        csv: Option<String>,
+        /// Also write each scenario's results to this newline-delimited JSON file
+        #[arg(long)]
+        json: Option<String>,
+        /// Stop each scenario once the standard error of the playoff odds is at most this for
+        /// every team (or every --stop-team), with --sims as the cap
+        #[arg(long)]
+        max_standard_error: Option<f64>,
+        /// Team abbreviations whose odds --max-standard-error applies to, comma separated
+        #[arg(long, value_delimiter = ',', requires = "max_standard_error")]
+        stop_team: Vec<String>,
+        /// Draw a final score for each simulated game, not just its result
+        #[arg(long)]
+        simulate_scores: bool,
+    },
+    /// Finish a stored simulation that stopped partway, keeping its completed scenarios
+    Resume {
+        #[arg(long)]
+        simulation_id: i32,
+        /// Home team's chance of winning each simulated game; should match the original run
+        #[arg(long, default_value_t = DEFAULT_HOME_WIN_PROBABILITY)]
+        home_win_probability: f64,
+    },
+    /// Simulate and store a season, then re-simulate and store it again whenever results
+    /// come in, until every game is final
+    Live {
+        #[arg(long, default_value_t = default_season())]
+        season: i32,
+        /// Simulations of the current state and of each game result
+        #[arg(long, default_value_t = 10000)]
+        sims: u64,
+        /// Also simulate each result of games that have already been played
+        #[arg(long)]
+        include_decided: bool,
+        /// Seconds between checks of the games table
+        #[arg(long, default_value_t = 300)]
+        interval: u64,
+        /// Home team's chance of winning each simulated game
+        #[arg(long, default_value_t = DEFAULT_HOME_WIN_PROBABILITY)]
+        home_win_probability: f64,
+    },
+    /// Queue a season's scenarios in the database for `work` processes to simulate
+    Enqueue {
+        #[arg(long, default_value_t = default_season())]
+        season: i32,
+        /// Simulations of the current state and of each game result
+        #[arg(long, default_value_t = 10000)]
+        sims: u64,
+        /// Also simulate each result of games that have already been played
+        #[arg(long)]
+        include_decided: bool,
+    },
+    /// Claim and simulate scenarios of a queued simulation until none are left
+    Work {
+        #[arg(long)]
+        simulation_id: i32,
+        /// Name the worker's claims are recorded under; host name and process id by default
+        #[arg(long)]
+        worker: Option<String>,
+        /// Minutes after which another worker's unfinished claim is taken over
+        #[arg(long, default_value_t = 60)]
+        claim_timeout: u64,
+        #[arg(long)]
+        seed: Option<u64>,
+        /// Home team's chance of winning each simulated game; should match the other workers
+        #[arg(long, default_value_t = DEFAULT_HOME_WIN_PROBABILITY)]
+        home_win_probability: f64,
+    },
+    /// Serve stored results and on-demand what-ifs over HTTP
+    #[cfg(feature = "server")]
+    Serve {
+        #[arg(long, default_value = "127.0.0.1:3000")]
+        addr: String,
+        /// Most simulations a single request may ask for
+        #[arg(long, default_value_t = server::DEFAULT_MAX_SIMS)]
+        max_sims: u64,
+    },
+    /// Compare odds under the forced results of a TOML scenario file against the baseline
+    #[cfg(feature = "scenarios")]
+    Scenario {
+        path: String,
+        /// Simulations of each side; overrides the file's `sims`
+        #[arg(long)]
+        sims: Option<u64>,
+        /// SQLite file to read the season from, instead of Postgres
+        #[arg(long, env = "NFL_SQLITE_PATH")]
+        sqlite: Option<String>,
+    },
+    /// Force game results interactively and see how the odds move
+    WhatIf {
+        #[arg(long, default_value_t = default_season())]
+        season: i32,
+        #[arg(long, default_value_t = DEFAULT_WHAT_IF_SIMS)]
+        sims: u64,
+        /// Seed shared by every simulation of the session; random when left out
+        #[arg(long)]
+        seed: Option<u64>,
+        /// SQLite file to read the season from, instead of Postgres
+        #[arg(long, env = "NFL_SQLITE_PATH")]
+        sqlite: Option<String>,
+    },
+    /// Simulate a season in a terminal dashboard of standings and odds that fills in live
+    #[cfg(feature = "tui")]
+    Dashboard {
+        #[arg(long, default_value_t = default_season())]
+        season: i32,
+        /// Simulations of the current state and of each game result
+        #[arg(long, default_value_t = 10000)]
+        sims: u64,
+        /// Also simulate each result of games that have already been played
+        #[arg(long)]
+        include_decided: bool,
+        #[arg(long)]
+        seed: Option<u64>,
+        /// SQLite file to read the season from, instead of Postgres
+        #[arg(long, env = "NFL_SQLITE_PATH")]
+        sqlite: Option<String>,
+    },
+    /// Create or drop the database schema
+    Migrate {
+        #[arg(value_enum)]
+        direction: MigrateDirection,
+    },
+    /// Print or export the results of a stored simulation
+    Report {
+        #[arg(long)]
+        simulation_id: i32,
+        #[arg(long, value_enum, default_value_t = ReportFormat::Table)]
+        format: ReportFormat,
+        /// File to write to instead of standard output
+        #[arg(long)]
+        output: Option<String>,
+    },
+    /// Explain the tiebreakers behind the current standings
+    Tiebreaks {
+        #[arg(long, default_value_t = default_season())]
+        season: i32,
+    },
+    /// Rank a stored simulation's games by how much they swing playoff odds
+    Leverage {
+        #[arg(long)]
+        simulation_id: i32,
+        /// Team abbreviation to rank games for; the whole league when left out
+        #[arg(long)]
+        team: Option<String>,
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum MigrateDirection {
+    Up,
+    Down,
+    Rebuild,
+}
+
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+enum ReportFormat {
+    Table,
+    Json,
+    Csv,
+}
+
+const DEFAULT_WHAT_IF_SIMS: u64 = 10000;
+
+fn main() -> Result<(), Error> {
+    dotenv::dotenv().ok();
+    let cli: Cli = Cli::parse();
+    init_logging(cli.verbose);
+    match cli.command {
+        Command::Simulate {
+            season,
+            sims,
+            include_decided,
+            seed,
+            home_win_probability,
+            sqlite,
+            csv,
+            json,
+            max_standard_error,
+            stop_team,
+            simulate_scores,
+        } => {
+            let progress: ProgressBars = ProgressBars::new(sims, sims);
+            let mut season: Season =
+                open_season(season, sqlite.as_deref())?.with_progress(progress.clone());
+            if let Some(seed) = seed {
+                season.set_seed(seed);
+            }
+            season.config.home_field = home_field(home_win_probability)?;
+            if let Some(max_standard_error) = max_standard_error {
+                season.config.stopping_rule =
+                    Some(stopping_rule(&season, max_standard_error, &stop_team)?);
+            }
+            if simulate_scores {
+                season.config.score_model = Some(ScoreModel::default());
+            }
+            let mut csv_sink: Option<CsvResultSink<File>> =
+                csv.as_deref().map(CsvResultSink::create).transpose()?;
+            let mut json_sink: Option<JsonResultSink<BufWriter<File>>> =
+                json.as_deref().map(JsonResultSink::create).transpose()?;
+            let mut store: Box<dyn ResultSink> = open_store(sqlite.as_deref())?;
+            let mut sinks: Vec<&mut dyn ResultSink> = vec![store.as_mut()];
+            if let Some(sink) = csv_sink.as_mut() {
+                sinks.push(sink);
+            }
+            if let Some(sink) = json_sink.as_mut() {
+                sinks.push(sink);
+            }
+            let report: SimulationReport =
+                season.simulate_into(sims, sims, include_decided, &mut sinks)?;
+            progress.finish();
+            println!("Stored simulation {}", report.simulation_id.unwrap());
+        }
+        Command::Resume {
+            simulation_id,
+            home_win_probability,
+        } => {
+            let (scenario_sims, baseline_sims) = load_simulation_counts(simulation_id)?;
+            let progress: ProgressBars = ProgressBars::new(baseline_sims, scenario_sims);
+            let mut season: Season = load_season(simulation_id)?.with_progress(progress.clone());
+            season.config.home_field = home_field(home_win_probability)?;
+            season.resume(simulation_id)?;
+            progress.finish();
+            println!("Stored simulation {}", simulation_id);
+        }
+        Command::Live {
+            season,
+            sims,
+            include_decided,
+            interval,
+            home_win_probability,
+        } => {
+            let mut season: Season = Season::new_from_year(season)?;
+            season.config.home_field = home_field(home_win_probability)?;
+            let report: SimulationReport =
+                season.run_all_game_simulations(sims, include_decided)?;
+            println!("Stored simulation {}", report.simulation_id.unwrap());
+            while season
+                .actual_games
+                .values()
+                .any(|game| game.game_result.is_none())
+            {
+                thread::sleep(Duration::from_secs(interval));
+                if let Some(refresh) = season.refresh()? {
+                    println!(
+                        "{} results changed; stored simulation {}",
+                        refresh.changed_game_ids.len(),
+                        refresh.report.simulation_id.unwrap()
+                    );
+                }
+            }
+        }
+        Command::Enqueue {
+            season,
+            sims,
+            include_decided,
+        } => {
+            let mut season: Season = Season::new_from_year(season)?;
+            let simulation_id: i32 = season.enqueue(sims, sims, include_decided)?;
+            println!("Queued simulation {}", simulation_id);
+        }
+        Command::Work {
+            simulation_id,
+            worker,
+            claim_timeout,
+            seed,
+            home_win_probability,
+        } => {
+            let mut season: Season = load_season(simulation_id)?;
+            if let Some(seed) = seed {
+                season.set_seed(seed);
+            }
+            season.config.home_field = home_field(home_win_probability)?;
+            let worker: String = worker.unwrap_or_else(|| {
+                format!(
+                    "{}-{}",
+                    env::var("HOSTNAME").unwrap_or_else(|_| String::from("worker")),
+                    process::id()
+                )
+            });
+            let stored: usize = season.work_queue(
+                simulation_id,
+                &worker,
+                Duration::from_secs(claim_timeout * 60),
+            )?;
+            let status: QueueStatus = queue_status(Db::shared()?, simulation_id)?;
+            println!(
+                "Stored {} scenarios of simulation {}; {} pending, {} claimed, {} completed",
+                stored, simulation_id, status.pending, status.claimed, status.completed
+            );
+        }
+        #[cfg(feature = "server")]
+        Command::Serve { addr, max_sims } => server::serve(&addr, Db::shared()?.clone(), max_sims)?,
+        #[cfg(feature = "scenarios")]
+        Command::Scenario { path, sims, sqlite } => {
+            let scenario: ScenarioFile = ScenarioFile::load(&path)?;
+            let mut season: Season = open_season(scenario.season, sqlite.as_deref())?;
+            let overrides: Vec<(i32, GameResult)> = scenario.overrides(&season)?;
+            let team_ids: Vec<i32> = scenario.team_ids(&season)?;
+            let sims: u64 = sims.or(scenario.sims).unwrap_or(DEFAULT_WHAT_IF_SIMS);
+            // Both sides draw from the same seed, so differences come from the forced results.
+            let seed: u64 = scenario.seed.unwrap_or_else(rand::random);
+            season.set_seed(seed);
+            let baseline: SimulationReport = season.simulate_with_overrides(Vec::new(), sims)?;
+            season.set_seed(seed);
+            let forced: SimulationReport = season.simulate_with_overrides(overrides, sims)?;
+            for forced_result in scenario.results.iter() {
+                println!(
+                    "Week {}: {} {}",
+                    forced_result.week, forced_result.team, forced_result.result
+                );
+            }
+            println!();
+            print_comparison(&baseline, &forced, &team_ids);
+        }
+        Command::WhatIf {
+            season,
+            sims,
+            seed,
+            sqlite,
+        } => {
+            let season: Season = open_season(season, sqlite.as_deref())?;
+            WhatIf::new(season, sims, seed.unwrap_or_else(rand::random)).run()?;
+        }
+        #[cfg(feature = "tui")]
+        Command::Dashboard {
+            season,
+            sims,
+            include_decided,
+            seed,
+            sqlite,
+        } => {
+            let mut season: Season = open_season(season, sqlite.as_deref())?;
+            if let Some(seed) = seed {
+                season.set_seed(seed);
+            }
+            dashboard::Dashboard::new(&season)?.run(season, sims, include_decided)?;
+        }
+        Command::Migrate { direction } => match direction {
+            MigrateDirection::Up => migrations::create(),
+            MigrateDirection::Down => migrations::destroy(),
+            MigrateDirection::Rebuild => migrations::rebuild(),
+        },
+        Command::Report {
+            simulation_id,
+            format,
+            output,
+        } => {
+            let season: Season = load_season(simulation_id)?;
+            let report: SimulationReport = season.load_results(simulation_id)?;
+            let writer: ResultWriter = ResultWriter::new(&report);
+            match (format, output) {
+                (ReportFormat::Table, None) => print_table(&season, &report)?,
+                (ReportFormat::Table, Some(_)) => {
+                    return Err(Error::InvalidArgument(String::from(
+                        "--output needs --format json or csv",
+                    )))
+                }
+                (ReportFormat::Json, Some(path)) => writer.write_json_file(&path)?,
+                (ReportFormat::Json, None) => writer.write_json(&mut io::stdout())?,
+                (ReportFormat::Csv, Some(path)) => writer.write_csv_file(&path)?,
+                (ReportFormat::Csv, None) => writer.write_csv(io::stdout())?,
+            }
+        }
+        Command::Tiebreaks { season } => {
+            for explanation in Season::new_from_year(season)?.tiebreak_explanations()? {
+                println!("{}", explanation);
+            }
+        }
+        Command::Leverage {
+            simulation_id,
+            team,
+            limit,
+        } => {
+            let season: Season = load_season(simulation_id)?;
+            let report: SimulationReport = season.load_results(simulation_id)?;
+            let target: LeverageTarget = match team {
+                Some(abbreviation) => match report
+                    .teams
+                    .values()
+                    .find(|team| team.abbreviation == abbreviation)
+                {
+                    Some(team) => LeverageTarget::Team(team.team_id),
+                    None => {
+                        return Err(Error::InvalidArgument(format!(
+                            "unknown team {abbreviation}"
+                        )))
+                    }
+                },
+                None => LeverageTarget::League,
+            };
+            print_leverage_table(&season, &report.game_leverage(target), limit);
+        }
+    }
+    Ok(())
+}
+
+/// Logs to standard error, filtered by `RUST_LOG` when it's set and by `verbose` otherwise.
+fn init_logging(verbose: u8) {
+    let level: &str = match verbose {
+        0 => "warn",
+        1 => "info",
+        2 => "debug",
+        _ => "trace",
+    };
+    let filter: EnvFilter =
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(level));
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(io::stderr)
+        .init();
+}
+
+/// The season that most recently kicked off, or is about to: this year's from March on.
+fn default_season() -> i32 {
+    let today = chrono::offset::Local::now();
+    match today.month() >= 3 {
+        true => today.year(),
+        false => today.year() - 1,
+    }
+}
 
-    // season.simulate_current_state(1);
-    // println!("{:#?}", season.current_simulation_result.draft_order);
+/// Loads a season from the SQLite file at `sqlite` when given, otherwise from Postgres.
+fn open_season(season_year: i32, sqlite: Option<&str>) -> Result<Season, Error> {
+    #[cfg(feature = "sqlite")]
+    if let Some(path) = sqlite {
+        return Season::new_from_source(season_year, &mut SqliteDataSource::open(path)?);
+    }
+    #[cfg(not(feature = "sqlite"))]
+    if sqlite.is_some() {
+        return Err(Error::InvalidArgument(String::from(
+            "--sqlite needs a build with the sqlite feature",
+        )));
+    }
+    Season::new_from_year(season_year)
+}
 
-    season.run_all_game_simulations(100000, false);
+/// Sink to store results in, to match `open_season`.
+fn open_store(sqlite: Option<&str>) -> Result<Box<dyn ResultSink>, Error> {
+    #[cfg(feature = "sqlite")]
+    if let Some(path) = sqlite {
+        return Ok(Box::new(SqliteResultSink::open(path)?));
+    }
+    #[cfg(not(feature = "sqlite"))]
+    if sqlite.is_some() {
+        return Err(Error::InvalidArgument(String::from(
+            "--sqlite needs a build with the sqlite feature",
+        )));
+    }
+    Ok(Box::new(PostgresResultSink::new(Db::shared()?.clone())))
+}
 
-    // season.set_simulation_id(1000);
+fn home_field(home_win_probability: f64) -> Result<HomeField, Error> {
+    match (0.0..=1.0).contains(&home_win_probability) {
+        true => Ok(HomeField::new(home_win_probability)),
+        false => Err(Error::InvalidArgument(String::from(
+            "--home-win-probability must be between 0 and 1",
+        ))),
+    }
+}
 
-    // println!("{:#?}", season);
+fn stopping_rule(
+    season: &Season,
+    max_standard_error: f64,
+    abbreviations: &[String],
+) -> Result<StoppingRule, Error> {
+    if !(max_standard_error > 0.0 && max_standard_error < 1.0) {
+        return Err(Error::InvalidArgument(String::from(
+            "--max-standard-error must be between 0 and 1",
+        )));
+    }
+    let team_ids: Vec<i32> = abbreviations
+        .iter()
+        .map(|abbreviation| {
+            match season
+                .teams
+                .values()
+                .find(|team| &team.abbreviation == abbreviation)
+            {
+                Some(team) => Ok(team.team_id),
+                None => Err(Error::InvalidArgument(format!(
+                    "unknown team {abbreviation}"
+                ))),
+            }
+        })
+        .collect::<Result<Vec<i32>, Error>>()?;
+    Ok(StoppingRule::new(max_standard_error).with_team_ids(team_ids))
+}
 
-    // println!("{:#?}", season.current_simulation_result.team_records);
-    // 26 is NYJ
-    // println!(
-    //     "{:#?}",
-    //     season.current_simulation_result.team_records.get(&26)
-    // );
+/// Simulations per game result and baseline simulations of a stored run.
+fn load_simulation_counts(simulation_id: i32) -> Result<(u64, u64), Error> {
+    let rows: Vec<Row> = Db::shared()?.query(
+        "
+        SELECT simulations_per_game_result, baseline_simulations
+        FROM nfl.simulations
+        WHERE simulation_id=$1;
+        ",
+        &[&simulation_id],
+    )?;
+    match rows.first() {
+        Some(row) => {
+            let scenario_sims: i64 = row.try_get(0)?;
+            let baseline_sims: i64 = row.try_get(1)?;
+            Ok((scenario_sims.unsigned_abs(), baseline_sims.unsigned_abs()))
+        }
+        None => Err(Error::InvalidArgument(format!(
+            "simulation {simulation_id} does not exist"
+        ))),
+    }
+}
 
-    // season.run_all_game_simulations(1000);
+fn load_season(simulation_id: i32) -> Result<Season, Error> {
+    let rows: Vec<Row> = Db::shared()?.query(
+        "SELECT season FROM nfl.simulations WHERE simulation_id=$1;",
+        &[&simulation_id],
+    )?;
+    let season_year: i32 = match rows.first() {
+        Some(row) => row.try_get(0)?,
+        None => {
+            return Err(Error::InvalidArgument(format!(
+                "simulation {simulation_id} does not exist"
+            )))
+        }
+    };
+    Season::new_from_year(season_year)
+}
 
-    // run_timed_simulations(season_year, 100000)
+/// Standings by division with each team's odds, seed distribution, expected wins and
+/// expected draft pick.
+fn print_table(season: &Season, report: &SimulationReport) -> Result<(), Error> {
+    let seeds: String = (1..=7)
+        .map(|seed| format!("{:>4}", seed))
+        .collect::<Vec<String>>()
+        .join("");
+    let mut division: Option<String> = None;
+    for summary in season.team_summaries(report)? {
+        let standing: &Standing = &summary.standing;
+        if division.as_ref() != Some(&standing.division) {
+            if division.is_some() {
+                println!();
+            }
+            println!(
+                "{:<12} {:>7} {:>9} {:>5} {:>9} {:>7} {:>5} {:>5} {}",
+                standing.division,
+                "W-L-T",
+                "Playoffs",
+                "+/-",
+                "Division",
+                "Bye",
+                "Wins",
+                "Pick",
+                seeds
+            );
+            division = Some(standing.division.clone());
+        }
+        let (wins, losses, ties) = standing.overall_record;
+        let seed_odds: String = (1..=7)
+            .map(|seed| {
+                let chance: f64 = *summary.playoff_seeds.get(&seed).unwrap_or(&0.0);
+                format!("{:>4.0}", chance * 100.0)
+            })
+            .collect::<Vec<String>>()
+            .join("");
+        let pick: String = summary
+            .expected_draft_position
+            .map_or_else(|| String::from("-"), |pick| format!("{:.1}", pick));
+        println!(
+            "{:<12} {:>7} {:>8.1}% {:>5.1} {:>8.1}% {:>6.1}% {:>5.1} {:>5} {}",
+            standing.abbreviation,
+            format!("{}-{}-{}", wins, losses, ties),
+            summary.make_playoffs * 100.0,
+            summary.make_playoffs_margin * 100.0,
+            summary.win_division * 100.0,
+            summary.first_round_bye * 100.0,
+            summary.expected_wins,
+            pick,
+            seed_odds
+        );
+    }
+    Ok(())
 }
 
-#[allow(dead_code)]
-fn run_timed_simulations(season_year: i32, sims: i32) {
-    let mut season: Season = Season::new_from_year(season_year);
+/// Each team's baseline odds, odds under the scenario and the change, in points, with the
+/// 95% margin of simulation noise in the change.
+fn print_comparison(baseline: &SimulationReport, scenario: &SimulationReport, team_ids: &[i32]) {
+    println!(
+        "{:<6} {:>23} {:>23} {:>23}",
+        "Team", "Playoffs", "Division", "Bye"
+    );
+    let sides: String = format!("{:>5} {:>5} {:>5} {:>5}", "Base", "If", "Chg", "+/-");
+    println!("{:<6} {:>23} {:>23} {:>23}", "", sides, sides, sides);
+    for team_id in team_ids {
+        let (before, after): (TeamOdds, TeamOdds) =
+            match (baseline.team_odds(*team_id), scenario.team_odds(*team_id)) {
+                (Some(before), Some(after)) => (before, after),
+                _ => continue,
+            };
+        let probabilities: [fn(&TeamOdds) -> f64; 3] = [
+            |odds| odds.make_playoffs,
+            |odds| odds.win_division,
+            |odds| odds.first_round_bye,
+        ];
+        let columns: Vec<String> = probabilities
+            .iter()
+            .map(|probability| {
+                let margin: f64 =
+                    CONFIDENCE_Z * before.difference_standard_error(&after, probability);
+                format!(
+                    "{:>5.1} {:>5.1} {:>+5.1} {:>5.1}",
+                    probability(&before) * 100.0,
+                    probability(&after) * 100.0,
+                    (probability(&after) - probability(&before)) * 100.0,
+                    margin * 100.0
+                )
+            })
+            .collect();
+        println!(
+            "{:<6} {:>23} {:>23} {:>23}",
+            baseline.teams[team_id].abbreviation, columns[0], columns[1], columns[2]
+        );
+    }
+}
 
-    let now: Instant = Instant::now();
-    for i in 0..sims {
-        season.run_simulation(false);
-        print!("\r{i}");
-        std::io::stdout()
-            .flush()
-            .expect("stdout could not be flushed");
+fn print_leverage_table(season: &Season, leverage: &[GameLeverage], limit: usize) {
+    println!(
+        "{:<4} {:<16} {:>9} {:>6}",
+        "Week", "Game", "Leverage", "+/-"
+    );
+    for game_leverage in leverage.iter().take(limit) {
+        let (week, matchup): (String, String) =
+            match season.actual_games.get(&game_leverage.game_id) {
+                Some(game) => (
+                    game.week.to_string(),
+                    format!(
+                        "{} @ {}",
+                        game.away_team.abbreviation, game.home_team.abbreviation
+                    ),
+                ),
+                None => (String::from("-"), game_leverage.game_id.to_string()),
+            };
+        println!(
+            "{:<4} {:<16} {:>8.1}% {:>5.1}%",
+            week,
+            matchup,
+            game_leverage.leverage * 100.0,
+            CONFIDENCE_Z * game_leverage.standard_error * 100.0
+        );
     }
-    let elapsed: std::time::Duration = now.elapsed();
-    println!("{:#?}", season.overall_results);
-    println!("\n{:.2?}", elapsed);
 }
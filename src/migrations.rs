@@ -1,8 +1,8 @@
 use nfl_schedule_simulator::execute;
 use std::fs::File;
 use std::io::prelude::*;
+use tracing::error;
 
-#[allow(dead_code)]
 pub fn rebuild() {
     destroy();
     create();
@@ -24,7 +24,9 @@ fn execute_sql_file(file_path: &str) {
     let statements: Vec<String> = parse_sql(contents);
 
     for statement in statements {
-        execute(statement)
+        if let Err(e) = execute(statement.clone()) {
+            error!("Failed to execute statement:\n\n{}\n\n{}", statement, e);
+        }
     }
 }
 
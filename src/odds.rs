@@ -0,0 +1,164 @@
+#[cfg(feature = "postgres")]
+use crate::{connect, PooledClient};
+use crate::{Error, Game, GameSimulator};
+#[cfg(feature = "postgres")]
+use postgres::{Client, Row};
+use std::collections::HashMap;
+use std::fs;
+
+/// Standard deviation, in points, of NFL final margins around the closing spread.
+pub(crate) const SPREAD_STANDARD_DEVIATION: f64 = 13.45;
+
+/// Market-implied win probabilities by game id, for `Season::with_game_simulator`. The lines
+/// already price in home field, so `Season::home_field_advantage` isn't added to them. Games
+/// without a line get even odds.
+#[derive(Clone, Debug, Default)]
+pub struct MarketOdds {
+    pub probabilities: HashMap<i32, f64>,
+}
+
+impl GameSimulator for MarketOdds {
+    fn win_prob(&self, game: &Game) -> f64 {
+        *self.probabilities.get(&game.game_id).unwrap_or(&0.5)
+    }
+
+    fn includes_home_field(&self) -> bool {
+        true
+    }
+}
+
+impl MarketOdds {
+    #[cfg(feature = "postgres")]
+    pub fn load(season_year: i32) -> Result<MarketOdds, Error> {
+        let mut client: PooledClient = connect()?;
+        MarketOdds::load_with_client(season_year, &mut client)
+    }
+
+    /// Lines for `season_year` from `nfl.game_odds`. Moneylines are used when both are present,
+    /// otherwise the home spread.
+    #[cfg(feature = "postgres")]
+    pub fn load_with_client(season_year: i32, client: &mut Client) -> Result<MarketOdds, Error> {
+        let rows: Vec<Row> = client.query(
+            "
+            SELECT
+                o.game_id,
+                o.home_spread,
+                o.home_moneyline,
+                o.away_moneyline
+            FROM nfl.game_odds o
+            INNER JOIN nfl.games g
+            USING (game_id)
+            WHERE g.season=$1;
+        ",
+            &[&season_year],
+        )?;
+        let mut odds: MarketOdds = MarketOdds::default();
+        for row in rows {
+            odds.insert_line(
+                row.try_get(0)?,
+                row.try_get(1)?,
+                row.try_get(2)?,
+                row.try_get(3)?,
+            );
+        }
+        Ok(odds)
+    }
+
+    /// Lines from a file of `game_id,home_spread,home_moneyline,away_moneyline` rows, where
+    /// any of the last three may be empty. A header row and lines starting with `#` are
+    /// skipped.
+    pub fn from_csv(path: &str) -> Result<MarketOdds, Error> {
+        let contents: String = fs::read_to_string(path)?;
+        let mut odds: MarketOdds = MarketOdds::default();
+        for line in contents.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') || line.starts_with("game_id") {
+                continue;
+            }
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            if fields.len() != 4 {
+                return Err(invalid_line(path, line));
+            }
+            let game_id: i32 = match fields[0].parse() {
+                Ok(game_id) => game_id,
+                Err(_) => return Err(invalid_line(path, line)),
+            };
+            let home_spread: Option<f64> = parse_optional(fields[1], path, line)?;
+            let home_moneyline: Option<i32> = parse_optional(fields[2], path, line)?;
+            let away_moneyline: Option<i32> = parse_optional(fields[3], path, line)?;
+            odds.insert_line(game_id, home_spread, home_moneyline, away_moneyline);
+        }
+        Ok(odds)
+    }
+
+    fn insert_line(
+        &mut self,
+        game_id: i32,
+        home_spread: Option<f64>,
+        home_moneyline: Option<i32>,
+        away_moneyline: Option<i32>,
+    ) {
+        let probability: Option<f64> = match (home_moneyline, away_moneyline, home_spread) {
+            (Some(home_moneyline), Some(away_moneyline), _) => {
+                Some(moneyline_probability(home_moneyline, away_moneyline))
+            }
+            (_, _, Some(home_spread)) => Some(spread_probability(home_spread)),
+            _ => None,
+        };
+        if let Some(probability) = probability {
+            self.probabilities.insert(game_id, probability);
+        }
+    }
+}
+
+/// Home win probability from American moneylines, with the bookmaker's margin removed.
+pub fn moneyline_probability(home_moneyline: i32, away_moneyline: i32) -> f64 {
+    let implied = |moneyline: i32| -> f64 {
+        let moneyline: f64 = f64::from(moneyline);
+        match moneyline {
+            m if m < 0.0 => -m / (-m + 100.0),
+            m => 100.0 / (m + 100.0),
+        }
+    };
+    let home: f64 = implied(home_moneyline);
+    let away: f64 = implied(away_moneyline);
+    home / (home + away)
+}
+
+/// Home win probability from the home spread (negative when the home team is favored),
+/// treating the final margin as normally distributed around the line.
+pub fn spread_probability(home_spread: f64) -> f64 {
+    normal_cdf(-home_spread / SPREAD_STANDARD_DEVIATION)
+}
+
+fn parse_optional<T: std::str::FromStr>(
+    field: &str,
+    path: &str,
+    line: &str,
+) -> Result<Option<T>, Error> {
+    match field {
+        "" => Ok(None),
+        f => match f.parse() {
+            Ok(value) => Ok(Some(value)),
+            Err(_) => Err(invalid_line(path, line)),
+        },
+    }
+}
+
+fn invalid_line(path: &str, line: &str) -> Error {
+    Error::InvalidData(format!("invalid odds line in {}: {}", path, line))
+}
+
+fn normal_cdf(x: f64) -> f64 {
+    0.5 * (1.0 + erf(x / std::f64::consts::SQRT_2))
+}
+
+// Abramowitz and Stegun 7.1.26, accurate to about 1.5e-7
+fn erf(x: f64) -> f64 {
+    let sign: f64 = x.signum();
+    let x: f64 = x.abs();
+    let t: f64 = 1.0 / (1.0 + 0.3275911 * x);
+    let polynomial: f64 = t
+        * (0.254829592
+            + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    sign * (1.0 - polynomial * (-x * x).exp())
+}
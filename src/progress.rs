@@ -0,0 +1,96 @@
+use crate::{now, Game, GameResult, TeamOdds};
+use std::collections::HashMap;
+use std::fmt;
+use tracing::{debug, info, trace};
+
+/// Hooks into a running simulation, set with `Season::with_progress`. Every method does
+/// nothing by default. `TracingProgress`, the default sink, emits `tracing` events, and
+/// `StdoutProgress` prints timestamped lines.
+pub trait ProgressSink: ProgressSinkClone + Send + fmt::Debug {
+    /// Before a game's scenarios are simulated. `index` counts from 1 up to `total_games`, and
+    /// includes games that are skipped for having an undetermined team.
+    fn on_game_started(&self, _game: &Game, _index: usize, _total_games: usize) {}
+
+    /// After a scenario's simulations, and after its results are stored when the run is being
+    /// stored: the current state when `scenario` is `None`, otherwise a game id and the result
+    /// it was pinned to. `odds` are every team's odds in that scenario, by team id.
+    fn on_scenario_done(
+        &self,
+        _scenario: Option<(i32, &GameResult)>,
+        _odds: &HashMap<i32, TeamOdds>,
+    ) {
+    }
+
+    /// After each batch of `sims` simulations, whether run one by one or enumerated exactly.
+    fn on_sims_completed(&self, _sims: u64) {}
+}
+
+pub trait ProgressSinkClone {
+    fn box_clone(&self) -> Box<dyn ProgressSink>;
+}
+
+impl<P: ProgressSink + Clone + 'static> ProgressSinkClone for P {
+    fn box_clone(&self) -> Box<dyn ProgressSink> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn ProgressSink> {
+    fn clone(&self) -> Box<dyn ProgressSink> {
+        (**self).box_clone()
+    }
+}
+
+/// Prints each game and scenario to standard output as it finishes.
+#[derive(Clone, Debug, Default)]
+pub struct StdoutProgress;
+
+impl ProgressSink for StdoutProgress {
+    fn on_game_started(&self, game: &Game, index: usize, total_games: usize) {
+        println!(
+            "\n{} - Processing game {} of {} (id: {}, {})...",
+            now(),
+            index,
+            total_games,
+            game.game_id,
+            game
+        );
+    }
+
+    fn on_scenario_done(
+        &self,
+        scenario: Option<(i32, &GameResult)>,
+        _odds: &HashMap<i32, TeamOdds>,
+    ) {
+        match scenario {
+            Some((_, game_result)) => println!("{} - Simulated {}", now(), game_result),
+            None => println!("\n{} - Simulated current season state", now()),
+        }
+    }
+}
+
+/// Emits each step as a `tracing` event: games at `info`, scenarios at `debug` and batches
+/// of simulations at `trace`. Nothing is shown without a subscriber.
+#[derive(Clone, Debug, Default)]
+pub struct TracingProgress;
+
+impl ProgressSink for TracingProgress {
+    fn on_game_started(&self, game: &Game, index: usize, total_games: usize) {
+        info!(game_id = game.game_id, %game, "processing game {} of {}", index, total_games);
+    }
+
+    fn on_scenario_done(
+        &self,
+        scenario: Option<(i32, &GameResult)>,
+        _odds: &HashMap<i32, TeamOdds>,
+    ) {
+        match scenario {
+            Some((game_id, game_result)) => debug!(game_id, %game_result, "simulated scenario"),
+            None => debug!("simulated current season state"),
+        }
+    }
+
+    fn on_sims_completed(&self, sims: u64) {
+        trace!(sims, "completed simulations");
+    }
+}
@@ -0,0 +1,60 @@
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use nfl_schedule_simulator::{Game, ProgressSink};
+
+/// Nested progress bars for `simulate`: games on the outer bar, with its estimated time
+/// left, and the current game's simulations across all of its scenarios on the inner bar.
+#[derive(Clone, Debug)]
+pub struct ProgressBars {
+    games: ProgressBar,
+    sims: ProgressBar,
+    scenario_sims: u64,
+}
+
+impl ProgressBars {
+    pub fn new(baseline_sims: u64, scenario_sims: u64) -> ProgressBars {
+        let bars: MultiProgress = MultiProgress::new();
+        let games: ProgressBar = bars.add(ProgressBar::new(0));
+        games.set_style(
+            ProgressStyle::with_template(
+                "{prefix:>8} [{elapsed_precise}] {wide_bar} {pos}/{len} games, ETA {eta_precise}",
+            )
+            .unwrap(),
+        );
+        games.set_prefix("Games");
+        let sims: ProgressBar = bars.add(ProgressBar::new(baseline_sims));
+        sims.set_style(
+            ProgressStyle::with_template(
+                "{prefix:>8} {wide_bar} {pos}/{len} sims, {per_sec} {msg}",
+            )
+            .unwrap(),
+        );
+        sims.set_prefix("Sims");
+        sims.set_message("current state");
+        ProgressBars {
+            games,
+            sims,
+            scenario_sims,
+        }
+    }
+
+    /// Fills the games bar and clears the simulations bar once the run is over.
+    pub fn finish(&self) {
+        self.games.set_position(self.games.length().unwrap_or(0));
+        self.games.finish();
+        self.sims.finish_and_clear();
+    }
+}
+
+impl ProgressSink for ProgressBars {
+    fn on_game_started(&self, game: &Game, index: usize, total_games: usize) {
+        self.games.set_length(total_games as u64);
+        self.games.set_position(index as u64 - 1);
+        self.sims.reset();
+        self.sims.set_length(self.scenario_sims * 3);
+        self.sims.set_message(game.to_string());
+    }
+
+    fn on_sims_completed(&self, sims: u64) {
+        self.sims.inc(sims);
+    }
+}
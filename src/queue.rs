@@ -0,0 +1,273 @@
+use crate::report::result_records;
+use crate::sink::{copy_results, insert_simulation_with_client};
+use crate::{
+    count_from_db, Db, Error, Game, GameResult, PooledClient, ResultRecord, RunInfo, Season,
+    SeedingDetail,
+};
+use postgres::{Row, Transaction};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::time::Duration;
+use tracing::{info, info_span, warn};
+
+/// Progress of a queued run, from `queue_status`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct QueueStatus {
+    /// Scenarios no worker has claimed yet.
+    pub pending: i64,
+    /// Scenarios a worker is simulating, or was when it stopped.
+    pub claimed: i64,
+    pub completed: i64,
+}
+
+impl QueueStatus {
+    pub fn is_done(&self) -> bool {
+        self.pending == 0 && self.claimed == 0
+    }
+}
+
+/// A scenario claimed from `nfl.simulation_queue`.
+struct WorkItem {
+    work_id: i64,
+    scenario: Option<(i32, GameResult)>,
+}
+
+impl Season {
+    /// Stores a new run like `simulate_into` would, but instead of simulating it, writes the
+    /// baseline and each game result's scenario to `nfl.simulation_queue` for workers to
+    /// claim with `work_queue`, on this machine or others sharing the database. Returns the
+    /// run's simulation id.
+    pub fn enqueue(
+        &mut self,
+        baseline_sims: u64,
+        scenario_sims: u64,
+        include_decided: bool,
+    ) -> Result<i32, Error> {
+        let run: RunInfo = RunInfo {
+            season_year: self.season_year,
+            baseline_sims,
+            scenario_sims,
+            include_decided,
+        };
+        let mut scenarios: Vec<Option<(i32, GameResult)>> = vec![None];
+        let mut games: Vec<&Game> = self
+            .actual_games
+            .values()
+            .filter(|game| !game.has_tbd_team() && (game.game_result.is_none() || include_decided))
+            .collect();
+        games.sort_by_key(|game| game.game_id);
+        for game in games {
+            for game_result in [GameResult::HomeWin, GameResult::AwayWin, GameResult::Tie] {
+                scenarios.push(Some((game.game_id, game_result)));
+            }
+        }
+
+        let mut client: PooledClient = self.db()?.get()?;
+        let mut transaction: Transaction = client.transaction()?;
+        let simulation_id: i32 = insert_simulation_with_client(&run, &mut transaction)?;
+        for scenario in scenarios.iter() {
+            let game_id: Option<i32> = scenario.as_ref().map(|(game_id, _)| *game_id);
+            let game_result: Option<String> = scenario
+                .as_ref()
+                .map(|(_, game_result)| game_result.to_string());
+            transaction.execute(
+                "
+                INSERT INTO nfl.simulation_queue (
+                    simulation_id,
+                    game_id,
+                    simulated_game_result
+                )
+                VALUES ($1, $2, $3::text::nfl.gameresult);
+            ",
+                &[&simulation_id, &game_id, &game_result],
+            )?;
+        }
+        transaction.commit()?;
+        info!(
+            simulation_id,
+            scenarios = scenarios.len(),
+            "queued simulation"
+        );
+        self.simulation_id = Some(simulation_id);
+        Ok(simulation_id)
+    }
+
+    /// Claims scenarios of queued run `simulation_id` one at a time, simulates each with the
+    /// run's stored counts and stores its results, until none are left to claim. Any number
+    /// of workers can share a run. A scenario claimed longer than `claim_timeout` ago without
+    /// finishing is taken to belong to a worker that died and is claimed again; if the first
+    /// worker finishes after all, its results are dropped. Returns how many scenarios this
+    /// worker stored.
+    pub fn work_queue(
+        &mut self,
+        simulation_id: i32,
+        worker: &str,
+        claim_timeout: Duration,
+    ) -> Result<usize, Error> {
+        if self.config.seeding_detail != SeedingDetail::FullSeeds {
+            return Err(Error::InvalidArgument(String::from(
+                "stored results need SeedingDetail::FullSeeds to record division winners",
+            )));
+        }
+        let db: Db = self.db()?;
+        let rows: Vec<Row> = db.query(
+            "
+            SELECT
+                season,
+                simulations_per_game_result,
+                baseline_simulations
+            FROM nfl.simulations
+            WHERE simulation_id=$1;
+        ",
+            &[&simulation_id],
+        )?;
+        let row: &Row = match rows.first() {
+            Some(row) => row,
+            None => {
+                return Err(Error::InvalidArgument(format!(
+                    "simulation {simulation_id} does not exist"
+                )))
+            }
+        };
+        let season: i32 = row.try_get(0)?;
+        if season != self.season_year {
+            return Err(Error::InvalidArgument(format!(
+                "simulation {simulation_id} is for season {season}, not {}",
+                self.season_year
+            )));
+        }
+        let scenario_sims: u64 = count_from_db(row.try_get(1)?)?.unsigned_abs().into();
+        let baseline_sims: u64 = count_from_db(row.try_get(2)?)?.unsigned_abs().into();
+        self.simulation_id = Some(simulation_id);
+
+        let mut stored: usize = 0;
+        while let Some(work_item) = claim(&db, simulation_id, worker, claim_timeout)? {
+            let _span = info_span!("work_item", work_item.work_id).entered();
+            self.overall_results = HashMap::new();
+            self.current_simulation_game = None;
+            self.current_simulation_base_games = self.actual_games.clone();
+            match &work_item.scenario {
+                Some((game_id, game_result)) => {
+                    self.simulate_for_game(*game_id, game_result.clone(), scenario_sims)
+                }
+                None => self.simulate_current_state(baseline_sims),
+            }?;
+            let records: Vec<ResultRecord> =
+                result_records(Some(simulation_id), self.overall_results.iter());
+            match complete(&db, simulation_id, &work_item, worker, &records)? {
+                true => stored += 1,
+                false => warn!(
+                    work_item.work_id,
+                    "claim was taken over by another worker; dropping results"
+                ),
+            }
+            let scenario: Option<(i32, &GameResult)> = work_item
+                .scenario
+                .as_ref()
+                .map(|(game_id, game_result)| (*game_id, game_result));
+            self.progress
+                .on_scenario_done(scenario, &self.scenario_team_odds(scenario));
+        }
+        Ok(stored)
+    }
+}
+
+/// How many of queued run `simulation_id`'s scenarios are pending, claimed and completed.
+pub fn queue_status(db: &Db, simulation_id: i32) -> Result<QueueStatus, Error> {
+    let rows: Vec<Row> = db.query(
+        "
+        SELECT
+            count(*) FILTER (WHERE claimed_at IS NULL),
+            count(*) FILTER (WHERE claimed_at IS NOT NULL AND completed_at IS NULL),
+            count(*) FILTER (WHERE completed_at IS NOT NULL)
+        FROM nfl.simulation_queue
+        WHERE simulation_id=$1;
+    ",
+        &[&simulation_id],
+    )?;
+    match rows.first() {
+        Some(row) => Ok(QueueStatus {
+            pending: row.try_get(0)?,
+            claimed: row.try_get(1)?,
+            completed: row.try_get(2)?,
+        }),
+        None => Ok(QueueStatus::default()),
+    }
+}
+
+/// Marks the run's lowest unclaimed scenario, or one whose claim has timed out, as claimed
+/// by `worker`. `SKIP LOCKED` lets workers claim at the same time without waiting on each
+/// other.
+fn claim(
+    db: &Db,
+    simulation_id: i32,
+    worker: &str,
+    claim_timeout: Duration,
+) -> Result<Option<WorkItem>, Error> {
+    let rows: Vec<Row> = db.query(
+        "
+        UPDATE nfl.simulation_queue
+        SET claimed_by=$2, claimed_at=NOW()
+        WHERE work_id = (
+            SELECT work_id
+            FROM nfl.simulation_queue
+            WHERE
+                simulation_id=$1
+                AND completed_at IS NULL
+                AND (
+                    claimed_at IS NULL
+                    OR claimed_at < NOW() - make_interval(secs => $3)
+                )
+            ORDER BY work_id
+            LIMIT 1
+            FOR UPDATE SKIP LOCKED
+        )
+        RETURNING work_id, game_id, simulated_game_result::text;
+    ",
+        &[&simulation_id, &worker, &claim_timeout.as_secs_f64()],
+    )?;
+    let row: &Row = match rows.first() {
+        Some(row) => row,
+        None => return Ok(None),
+    };
+    let game_id: Option<i32> = row.try_get(1)?;
+    let game_result: Option<String> = row.try_get(2)?;
+    let scenario: Option<(i32, GameResult)> = match (game_id, game_result) {
+        (Some(game_id), Some(game_result)) => Some((
+            game_id,
+            GameResult::from_str(&game_result).map_err(Error::InvalidData)?,
+        )),
+        _ => None,
+    };
+    Ok(Some(WorkItem {
+        work_id: row.try_get(0)?,
+        scenario,
+    }))
+}
+
+/// Stores `records` and marks the scenario completed in one transaction, unless another
+/// worker has claimed it since, in which case nothing is stored and it returns `false`.
+fn complete(
+    db: &Db,
+    simulation_id: i32,
+    work_item: &WorkItem,
+    worker: &str,
+    records: &[ResultRecord],
+) -> Result<bool, Error> {
+    let mut client: PooledClient = db.get()?;
+    let mut transaction: Transaction = client.transaction()?;
+    let updated: u64 = transaction.execute(
+        "
+        UPDATE nfl.simulation_queue
+        SET completed_at=NOW()
+        WHERE work_id=$1 AND claimed_by=$2 AND completed_at IS NULL;
+    ",
+        &[&work_item.work_id, &worker],
+    )?;
+    if updated == 0 {
+        return Ok(false);
+    }
+    copy_results(&mut transaction, simulation_id, records)?;
+    transaction.commit()?;
+    Ok(true)
+}
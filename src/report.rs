@@ -0,0 +1,751 @@
+use crate::{
+    GameResult, PlayoffRound, ResultSet, SimulationResultLookup, Team, TeamSimulationResults,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::io;
+use std::sync::Arc;
+
+#[derive(Clone, Debug)]
+pub struct SimulationReport {
+    pub season_year: i32,
+    pub simulation_id: Option<i32>,
+    pub seed: Option<u64>,
+    pub teams: HashMap<i32, Arc<Team>>,
+    pub conference_mapping: HashMap<String, Vec<i32>>,
+    pub division_mapping: HashMap<String, Vec<i32>>,
+    pub playoff_streaks: HashMap<i32, i32>,
+    pub division_playoff_counts: HashMap<String, HashMap<u8, i32>>,
+    pub results: HashMap<SimulationResultLookup, TeamSimulationResults>,
+    pub complete: bool,
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct TeamOdds {
+    pub team_id: i32,
+    pub simulations: i32,
+    pub make_playoffs: f64,
+    /// 95% Wilson score interval for `make_playoffs`.
+    pub make_playoffs_ci: (f64, f64),
+    /// Monte Carlo standard error of `make_playoffs`; see `TeamOdds::standard_error`.
+    pub make_playoffs_se: f64,
+    pub win_division: f64,
+    pub win_division_se: f64,
+    pub wildcard: f64,
+    pub playoff_seeds: HashMap<u8, f64>,
+    /// Chance of the top seed and its wild-card round bye.
+    pub first_round_bye: f64,
+    pub draft_positions: HashMap<u8, f64>,
+    /// Average pick over the simulations with a draft order; `None` when there are none.
+    pub expected_draft_position: Option<f64>,
+    /// Chance of finishing with each number of wins.
+    pub win_totals: HashMap<u8, f64>,
+    pub expected_wins: f64,
+    pub playoff_streak: i32,
+    /// Chance of extending `playoff_streak`; `None` when the team has no current streak. The
+    /// chance the streak ends is the complement.
+    pub streak_continues_pct: Option<f64>,
+    /// Average week of clinching a playoff spot, over the simulations in which the team made
+    /// the playoffs. Only tracked for the baseline.
+    pub expected_clinch_week: Option<f64>,
+    /// Chance of winning each playoff round; the Super Bowl entry is the title odds. Reports
+    /// loaded from the database only carry the divisional round onward.
+    pub playoff_rounds: HashMap<PlayoffRound, f64>,
+    /// Average final win percentage of the opponents in games already decided, and in games
+    /// left to play; see `StrengthOfSchedule`. `None` when the team has no such games, and
+    /// for reports loaded from the database.
+    pub played_strength_of_schedule: Option<f64>,
+    pub remaining_strength_of_schedule: Option<f64>,
+    /// Chance a coin toss settled one of the team's division, wildcard or seeding ties. Always
+    /// zero for reports loaded from the database.
+    pub coin_toss: f64,
+    /// Chance of hosting at least one playoff game. Always zero for reports loaded from the
+    /// database.
+    pub host_playoff_game: f64,
+}
+
+/// One row of results in the shape of `nfl.simulation_results`: how many of a scenario's
+/// simulations ended with the team at `team_rank` in `result_set`.
+#[derive(Clone, Debug)]
+pub struct ResultRecord {
+    pub simulation_id: Option<i32>,
+    pub game_id: Option<i32>,
+    pub game_result: Option<GameResult>,
+    pub team_id: i32,
+    pub result_set: ResultSet,
+    pub team_rank: u8,
+    pub simulations_with_rank: i32,
+    pub simulations: i32,
+}
+
+#[derive(Clone, Debug)]
+pub struct BatchOdds {
+    pub team_id: i32,
+    pub reports: usize,
+    pub make_playoffs_mean: f64,
+    pub make_playoffs_variance: f64,
+    pub win_division_mean: f64,
+    pub win_division_variance: f64,
+    pub wildcard_mean: f64,
+    pub wildcard_variance: f64,
+}
+
+/// Which side of a game a team should root for. `root_for` is the team id whose win gives the
+/// better playoff odds, or `None` when both outcomes leave the odds unchanged.
+#[derive(Clone, Debug)]
+pub struct RootingInterest {
+    pub game_id: i32,
+    pub root_for: Option<i32>,
+    pub make_playoffs_home_win: f64,
+    pub make_playoffs_away_win: f64,
+}
+
+/// A team's odds under each result of one game. Results that weren't simulated are `None`;
+/// the swings are the largest differences between any two results that were.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct ConditionalOdds {
+    pub team_id: i32,
+    pub home_win: Option<TeamOdds>,
+    pub away_win: Option<TeamOdds>,
+    pub tie: Option<TeamOdds>,
+    pub make_playoffs_swing: f64,
+    /// Standard error of `make_playoffs_swing`, from the two results it is measured between.
+    pub make_playoffs_swing_se: f64,
+    pub win_division_swing: f64,
+    pub win_division_swing_se: f64,
+    /// Largest change in the chance of any one playoff seed.
+    pub playoff_seed_swing: f64,
+}
+
+/// How one game's result moves every team's odds, from `SimulationReport::conditional_report`.
+#[derive(Clone, Debug)]
+pub struct ConditionalReport {
+    pub game_id: i32,
+    pub teams: HashMap<i32, ConditionalOdds>,
+}
+
+/// Whose playoff odds `SimulationReport::game_leverage` ranks games by.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LeverageTarget {
+    Team(i32),
+    /// The sum of every team's swing.
+    League,
+}
+
+#[derive(Clone, Debug)]
+pub struct GameLeverage {
+    pub game_id: i32,
+    /// Swing in playoff odds between the game's results, from `ConditionalOdds`.
+    pub leverage: f64,
+    /// Standard error of `leverage`. The league's is combined over teams as if their swings
+    /// were independent.
+    pub standard_error: f64,
+}
+
+#[derive(Clone, Debug)]
+pub struct DivisionStrength {
+    pub division: String,
+    pub expected_wins: f64,
+    pub expected_playoff_teams: f64,
+    pub two_or_more_playoff_teams: f64,
+    pub three_or_more_playoff_teams: f64,
+    pub four_playoff_teams: f64,
+}
+
+impl TeamOdds {
+    pub fn new_from_results(team_id: i32, results: &TeamSimulationResults) -> TeamOdds {
+        let simulations: i32 = results.simulations;
+        let probability = |count: i32| match simulations {
+            0 => 0.0,
+            s => f64::from(count) / f64::from(s),
+        };
+
+        TeamOdds {
+            team_id,
+            simulations,
+            make_playoffs: probability(results.made_playoffs),
+            make_playoffs_ci: wilson_interval(results.made_playoffs, simulations),
+            make_playoffs_se: standard_error(probability(results.made_playoffs), simulations),
+            win_division: probability(results.division_winner),
+            win_division_se: standard_error(probability(results.division_winner), simulations),
+            wildcard: probability(results.wildcard_team),
+            playoff_seeds: results
+                .playoff_seedings
+                .iter()
+                .map(|(seed, count)| (*seed, probability(*count)))
+                .collect(),
+            first_round_bye: probability(*results.playoff_seedings.get(&1).unwrap_or(&0)),
+            draft_positions: results
+                .draft_positions
+                .iter()
+                .map(|(position, count)| (*position, probability(*count)))
+                .collect(),
+            expected_draft_position: expected_draft_position(&results.draft_positions),
+            win_totals: results
+                .win_totals
+                .iter()
+                .map(|(wins, count)| (*wins, probability(*count)))
+                .collect(),
+            expected_wins: match simulations {
+                0 => 0.0,
+                s => f64::from(results.total_wins) / f64::from(s),
+            },
+            playoff_streak: 0,
+            streak_continues_pct: None,
+            expected_clinch_week: match results.clinches {
+                0 => None,
+                c => Some(f64::from(results.total_clinch_weeks) / f64::from(c)),
+            },
+            playoff_rounds: results
+                .playoff_round_wins
+                .iter()
+                .map(|(round, count)| (*round, probability(*count)))
+                .collect(),
+            played_strength_of_schedule: match results.played_sos_simulations {
+                0 => None,
+                s => Some(results.total_played_sos / f64::from(s)),
+            },
+            coin_toss: probability(results.coin_tosses),
+            host_playoff_game: probability(results.hosted_playoff_games),
+            remaining_strength_of_schedule: match results.remaining_sos_simulations {
+                0 => None,
+                s => Some(results.total_remaining_sos / f64::from(s)),
+            },
+        }
+    }
+
+    /// Monte Carlo standard error of any of these odds, such as a seed's chance, from the
+    /// number of simulations behind them.
+    pub fn standard_error(&self, probability: f64) -> f64 {
+        standard_error(probability, self.simulations)
+    }
+
+    /// Half-width of the 95% normal interval around `probability`.
+    pub fn margin_of_error(&self, probability: f64) -> f64 {
+        CONFIDENCE_Z * self.standard_error(probability)
+    }
+
+    /// Standard error of the change from `self` to `other` in the odds picked by
+    /// `probability`, treating the two as independent runs. Runs sharing a seed or common
+    /// random numbers are positively correlated, so this overstates their error.
+    pub fn difference_standard_error(
+        &self,
+        other: &TeamOdds,
+        probability: impl Fn(&TeamOdds) -> f64,
+    ) -> f64 {
+        self.standard_error(probability(self))
+            .hypot(other.standard_error(probability(other)))
+    }
+
+    /// The fewest wins the team reaches in at least `percentile` (0 to 1) of simulations, such
+    /// as the median at 0.5. `None` when there are no win totals.
+    pub fn win_total_percentile(&self, percentile: f64) -> Option<u8> {
+        let mut win_totals: Vec<(&u8, &f64)> = self.win_totals.iter().collect();
+        win_totals.sort_by_key(|(wins, _)| **wins);
+        let mut cumulative: f64 = 0.0;
+        for (wins, probability) in win_totals.iter() {
+            cumulative += *probability;
+            if cumulative >= percentile {
+                return Some(**wins);
+            }
+        }
+        win_totals.last().map(|(wins, _)| **wins)
+    }
+
+    pub(crate) fn with_playoff_streak(mut self, playoff_streak: i32) -> TeamOdds {
+        self.playoff_streak = playoff_streak;
+        self.streak_continues_pct = match playoff_streak {
+            s if s > 0 => Some(self.make_playoffs),
+            _ => None,
+        };
+        self
+    }
+}
+
+impl ConditionalOdds {
+    /// Whether `make_playoffs_swing` is larger than the 95% margin of Monte Carlo noise
+    /// between the results it is measured between.
+    pub fn make_playoffs_swing_is_significant(&self) -> bool {
+        self.make_playoffs_swing > CONFIDENCE_Z * self.make_playoffs_swing_se
+    }
+}
+
+impl SimulationReport {
+    pub fn team_odds(&self, team_id: i32) -> Option<TeamOdds> {
+        let lookup = SimulationResultLookup::new(&team_id, None);
+        self.results.get(&lookup).map(|results| {
+            TeamOdds::new_from_results(team_id, results)
+                .with_playoff_streak(self.playoff_streak(team_id))
+        })
+    }
+
+    pub fn scenario_odds(
+        &self,
+        team_id: i32,
+        game_id: i32,
+        game_result: GameResult,
+    ) -> Option<TeamOdds> {
+        let lookup = SimulationResultLookup::new(&team_id, Some(&(game_id, game_result)));
+        self.results.get(&lookup).map(|results| {
+            TeamOdds::new_from_results(team_id, results)
+                .with_playoff_streak(self.playoff_streak(team_id))
+        })
+    }
+
+    /// How much `game_id`'s outcome swings `team_id`'s playoff odds: the absolute difference
+    /// between its odds given a home win and given an away win. `None` when the game's
+    /// scenarios were not simulated.
+    pub fn playoff_leverage(&self, game_id: i32, team_id: i32) -> Option<f64> {
+        let home_win: TeamOdds = self.scenario_odds(team_id, game_id, GameResult::HomeWin)?;
+        let away_win: TeamOdds = self.scenario_odds(team_id, game_id, GameResult::AwayWin)?;
+        Some((home_win.make_playoffs - away_win.make_playoffs).abs())
+    }
+
+    /// Every team's odds given each result of `game_id`. `None` when the game's scenarios were
+    /// not simulated.
+    pub fn conditional_report(&self, game_id: i32) -> Option<ConditionalReport> {
+        let mut teams: HashMap<i32, ConditionalOdds> = HashMap::new();
+        for team_id in self.teams.keys() {
+            let home_win: Option<TeamOdds> =
+                self.scenario_odds(*team_id, game_id, GameResult::HomeWin);
+            let away_win: Option<TeamOdds> =
+                self.scenario_odds(*team_id, game_id, GameResult::AwayWin);
+            let tie: Option<TeamOdds> = self.scenario_odds(*team_id, game_id, GameResult::Tie);
+            let outcomes: Vec<&TeamOdds> =
+                [&home_win, &away_win, &tie].into_iter().flatten().collect();
+            if outcomes.is_empty() {
+                continue;
+            }
+            let seeds: HashSet<u8> = outcomes
+                .iter()
+                .flat_map(|odds| odds.playoff_seeds.keys().copied())
+                .collect();
+            let playoff_seed_swing: f64 = seeds
+                .iter()
+                .map(|seed| {
+                    swing(&outcomes, |odds| {
+                        *odds.playoff_seeds.get(seed).unwrap_or(&0.0)
+                    })
+                })
+                .fold(0.0, f64::max);
+            let (make_playoffs_swing, make_playoffs_swing_se): (f64, f64) =
+                swing_with_error(&outcomes, |odds| odds.make_playoffs);
+            let (win_division_swing, win_division_swing_se): (f64, f64) =
+                swing_with_error(&outcomes, |odds| odds.win_division);
+            let conditional_odds = ConditionalOdds {
+                team_id: *team_id,
+                make_playoffs_swing,
+                make_playoffs_swing_se,
+                win_division_swing,
+                win_division_swing_se,
+                playoff_seed_swing,
+                home_win,
+                away_win,
+                tie,
+            };
+            teams.insert(*team_id, conditional_odds);
+        }
+        match teams.is_empty() {
+            true => None,
+            false => Some(ConditionalReport { game_id, teams }),
+        }
+    }
+
+    /// `conditional_report` for every game with scenario results, ordered by game id. These
+    /// are the undecided games, plus the decided ones when the season was simulated with
+    /// `include_decided`.
+    pub fn conditional_reports(&self) -> Vec<ConditionalReport> {
+        let game_ids: BTreeSet<i32> = self
+            .results
+            .keys()
+            .filter_map(|lookup| lookup.game_id)
+            .collect();
+        game_ids
+            .into_iter()
+            .filter_map(|game_id| self.conditional_report(game_id))
+            .collect()
+    }
+
+    /// Games with scenario results ranked by how much their result swings `target`'s playoff
+    /// odds, highest leverage first.
+    pub fn game_leverage(&self, target: LeverageTarget) -> Vec<GameLeverage> {
+        let mut leverage: Vec<GameLeverage> = self
+            .conditional_reports()
+            .into_iter()
+            .filter_map(|conditional_report| {
+                let (leverage, standard_error): (f64, f64) = match target {
+                    LeverageTarget::Team(team_id) => {
+                        let odds: &ConditionalOdds = conditional_report.teams.get(&team_id)?;
+                        (odds.make_playoffs_swing, odds.make_playoffs_swing_se)
+                    }
+                    LeverageTarget::League => (
+                        conditional_report
+                            .teams
+                            .values()
+                            .map(|odds| odds.make_playoffs_swing)
+                            .sum(),
+                        conditional_report
+                            .teams
+                            .values()
+                            .map(|odds| odds.make_playoffs_swing_se.powi(2))
+                            .sum::<f64>()
+                            .sqrt(),
+                    ),
+                };
+                Some(GameLeverage {
+                    game_id: conditional_report.game_id,
+                    leverage,
+                    standard_error,
+                })
+            })
+            .collect();
+        leverage.sort_by(|a, b| {
+            b.leverage
+                .total_cmp(&a.leverage)
+                .then(a.game_id.cmp(&b.game_id))
+        });
+        leverage
+    }
+
+    /// Flattens the results into one record per scenario, team and outcome, ordered by game,
+    /// game result, team, result set and rank. The baseline comes first.
+    pub fn result_records(&self) -> Vec<ResultRecord> {
+        result_records(self.simulation_id, self.results.iter())
+    }
+
+    /// Writes `result_records` as newline-delimited JSON, one object per line.
+    pub fn write_ndjson<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        for record in self.result_records() {
+            write_ndjson_record(writer, self.season_year, &record)?;
+        }
+        Ok(())
+    }
+
+    fn playoff_streak(&self, team_id: i32) -> i32 {
+        *self.playoff_streaks.get(&team_id).unwrap_or(&0)
+    }
+
+    /// Mean and sample variance of each team's baseline odds across a batch of reports, such as
+    /// the ones returned by `Season::simulate_batch`.
+    pub fn aggregate(reports: &[SimulationReport]) -> HashMap<i32, BatchOdds> {
+        let mut odds_by_team: HashMap<i32, Vec<TeamOdds>> = HashMap::new();
+        for report in reports {
+            for (team_id, team_odds) in report.all_team_odds() {
+                odds_by_team.entry(team_id).or_default().push(team_odds);
+            }
+        }
+
+        odds_by_team
+            .into_iter()
+            .map(|(team_id, team_odds)| {
+                let (make_playoffs_mean, make_playoffs_variance) =
+                    mean_and_variance(team_odds.iter().map(|odds| odds.make_playoffs));
+                let (win_division_mean, win_division_variance) =
+                    mean_and_variance(team_odds.iter().map(|odds| odds.win_division));
+                let (wildcard_mean, wildcard_variance) =
+                    mean_and_variance(team_odds.iter().map(|odds| odds.wildcard));
+                let batch_odds = BatchOdds {
+                    team_id,
+                    reports: team_odds.len(),
+                    make_playoffs_mean,
+                    make_playoffs_variance,
+                    win_division_mean,
+                    win_division_variance,
+                    wildcard_mean,
+                    wildcard_variance,
+                };
+                (team_id, batch_odds)
+            })
+            .collect()
+    }
+
+    /// For every ordered pair of teams in `conference`, the probability that the first finishes
+    /// ahead of the second: playoff teams by seed, then non-playoff teams by draft position
+    /// (a later pick finishes ahead). Reports loaded from the database carry no pairwise counts.
+    pub fn finish_ahead_matrix(&self, conference: &str) -> HashMap<(i32, i32), f64> {
+        let team_ids: &Vec<i32> = match self.conference_mapping.get(conference) {
+            Some(team_ids) => team_ids,
+            None => panic!("Unknown conference: {}", conference),
+        };
+
+        let mut matrix: HashMap<(i32, i32), f64> = HashMap::new();
+        for team_id in team_ids {
+            let lookup = SimulationResultLookup::new(team_id, None);
+            let results: &TeamSimulationResults = match self.results.get(&lookup) {
+                Some(results) => results,
+                None => continue,
+            };
+            for other_team_id in team_ids.iter().filter(|other| *other != team_id) {
+                let finished_ahead: i32 = *results.finished_ahead.get(other_team_id).unwrap_or(&0);
+                let probability: f64 = match results.simulations {
+                    0 => 0.0,
+                    s => f64::from(finished_ahead) / f64::from(s),
+                };
+                matrix.insert((*team_id, *other_team_id), probability);
+            }
+        }
+        matrix
+    }
+
+    /// For every pair of teams that met in `round` in some baseline simulation, the
+    /// probability that they did, keyed both ways round. Reports loaded from the database
+    /// carry no matchups.
+    pub fn matchup_matrix(&self, round: PlayoffRound) -> HashMap<(i32, i32), f64> {
+        let mut matrix: HashMap<(i32, i32), f64> = HashMap::new();
+        for (lookup, results) in self.results.iter() {
+            if lookup.game_id.is_some() || results.simulations == 0 {
+                continue;
+            }
+            let opponents: &HashMap<i32, i32> = match results.playoff_opponents.get(&round) {
+                Some(opponents) => opponents,
+                None => continue,
+            };
+            for (opponent_id, count) in opponents.iter() {
+                matrix.insert(
+                    (lookup.team_id, *opponent_id),
+                    f64::from(*count) / f64::from(results.simulations),
+                );
+            }
+        }
+        matrix
+    }
+
+    /// Combined expected wins of each division's teams and how often the division sends two,
+    /// three or four teams to the playoffs, from the baseline simulations.
+    pub fn division_strength(&self) -> HashMap<String, DivisionStrength> {
+        let mut strengths: HashMap<String, DivisionStrength> = HashMap::new();
+        for (division, team_ids) in self.division_mapping.iter() {
+            let mut expected_wins: f64 = 0.0;
+            for team_id in team_ids {
+                let lookup = SimulationResultLookup::new(team_id, None);
+                if let Some(results) = self.results.get(&lookup) {
+                    if results.simulations > 0 {
+                        expected_wins +=
+                            f64::from(results.total_wins) / f64::from(results.simulations);
+                    }
+                }
+            }
+
+            let empty_counts: HashMap<u8, i32> = HashMap::new();
+            let playoff_counts: &HashMap<u8, i32> = self
+                .division_playoff_counts
+                .get(division)
+                .unwrap_or(&empty_counts);
+            let simulations: i32 = playoff_counts.values().sum();
+            let probability = |min_teams: u8| match simulations {
+                0 => 0.0,
+                s => {
+                    let count: i32 = playoff_counts
+                        .iter()
+                        .filter(|(playoff_teams, _)| **playoff_teams >= min_teams)
+                        .map(|(_, count)| count)
+                        .sum();
+                    f64::from(count) / f64::from(s)
+                }
+            };
+            let expected_playoff_teams: f64 = match simulations {
+                0 => 0.0,
+                s => {
+                    let total: i32 = playoff_counts
+                        .iter()
+                        .map(|(playoff_teams, count)| i32::from(*playoff_teams) * count)
+                        .sum();
+                    f64::from(total) / f64::from(s)
+                }
+            };
+
+            let strength = DivisionStrength {
+                division: division.clone(),
+                expected_wins,
+                expected_playoff_teams,
+                two_or_more_playoff_teams: probability(2),
+                three_or_more_playoff_teams: probability(3),
+                four_playoff_teams: probability(4),
+            };
+            strengths.insert(division.clone(), strength);
+        }
+        strengths
+    }
+
+    pub fn all_team_odds(&self) -> HashMap<i32, TeamOdds> {
+        self.teams
+            .keys()
+            .filter_map(|team_id| {
+                self.team_odds(*team_id)
+                    .map(|team_odds| (*team_id, team_odds))
+            })
+            .collect()
+    }
+}
+
+/// Normal quantile for the 95% intervals and margins reported alongside odds.
+pub const CONFIDENCE_Z: f64 = 1.96;
+
+/// Standard error of a probability estimated from `simulations` runs, `sqrt(p(1 - p) / n)`.
+/// Zero without any simulations.
+pub fn standard_error(probability: f64, simulations: i32) -> f64 {
+    match simulations {
+        s if s > 0 => (probability * (1.0 - probability) / f64::from(s)).sqrt(),
+        _ => 0.0,
+    }
+}
+
+fn json_or_null<T: ToString>(value: Option<T>) -> String {
+    match value {
+        Some(value) => value.to_string(),
+        None => String::from("null"),
+    }
+}
+
+fn wilson_interval(successes: i32, trials: i32) -> (f64, f64) {
+    if trials <= 0 {
+        return (0.0, 1.0);
+    }
+    let n: f64 = f64::from(trials);
+    let p: f64 = f64::from(successes) / n;
+    let z_squared: f64 = CONFIDENCE_Z * CONFIDENCE_Z;
+    let denominator: f64 = 1.0 + z_squared / n;
+    let center: f64 = (p + z_squared / (2.0 * n)) / denominator;
+    let margin: f64 =
+        CONFIDENCE_Z * (p * (1.0 - p) / n + z_squared / (4.0 * n * n)).sqrt() / denominator;
+    ((center - margin).max(0.0), (center + margin).min(1.0))
+}
+
+fn expected_draft_position(draft_positions: &HashMap<u8, i32>) -> Option<f64> {
+    let picks: i32 = draft_positions.values().sum();
+    let total: i32 = draft_positions
+        .iter()
+        .map(|(position, count)| i32::from(*position) * count)
+        .sum();
+    match picks {
+        0 => None,
+        p => Some(f64::from(total) / f64::from(p)),
+    }
+}
+
+fn swing(outcomes: &[&TeamOdds], probability: impl Fn(&TeamOdds) -> f64) -> f64 {
+    let probabilities: Vec<f64> = outcomes.iter().map(|odds| probability(odds)).collect();
+    let max: f64 = probabilities.iter().copied().fold(f64::MIN, f64::max);
+    let min: f64 = probabilities.iter().copied().fold(f64::MAX, f64::min);
+    max - min
+}
+
+/// `swing`, with the standard error of the difference between the results at either end.
+fn swing_with_error(outcomes: &[&TeamOdds], probability: impl Fn(&TeamOdds) -> f64) -> (f64, f64) {
+    let highest: Option<&&TeamOdds> = outcomes
+        .iter()
+        .max_by(|a, b| probability(a).total_cmp(&probability(b)));
+    let lowest: Option<&&TeamOdds> = outcomes
+        .iter()
+        .min_by(|a, b| probability(a).total_cmp(&probability(b)));
+    let standard_error: f64 = match (highest, lowest) {
+        (Some(highest), Some(lowest)) => highest.difference_standard_error(lowest, &probability),
+        _ => 0.0,
+    };
+    (swing(outcomes, &probability), standard_error)
+}
+
+fn mean_and_variance(values: impl Iterator<Item = f64>) -> (f64, f64) {
+    let values: Vec<f64> = values.collect();
+    let count = values.len() as f64;
+    let mean: f64 = match values.len() {
+        0 => 0.0,
+        _ => values.iter().sum::<f64>() / count,
+    };
+    let variance: f64 = match values.len() {
+        0 | 1 => 0.0,
+        _ => {
+            values
+                .iter()
+                .map(|value| (value - mean).powi(2))
+                .sum::<f64>()
+                / (count - 1.0)
+        }
+    };
+    (mean, variance)
+}
+
+/// One line of `SimulationReport::write_ndjson`.
+pub(crate) fn write_ndjson_record<W: io::Write>(
+    writer: &mut W,
+    season_year: i32,
+    record: &ResultRecord,
+) -> io::Result<()> {
+    writeln!(
+        writer,
+        "{{\"simulation_id\":{},\"season\":{},\"game_id\":{},\"game_result\":{},\
+         \"team_id\":{},\"result_set\":\"{}\",\"team_rank\":{},\
+         \"simulations_with_rank\":{},\"simulations\":{}}}",
+        json_or_null(record.simulation_id),
+        season_year,
+        json_or_null(record.game_id),
+        json_or_null(record.game_result.as_ref().map(|gr| format!("\"{}\"", gr))),
+        record.team_id,
+        record.result_set,
+        record.team_rank,
+        record.simulations_with_rank,
+        record.simulations,
+    )
+}
+
+/// Records for `results`, ordered like `SimulationReport::result_records`.
+pub(crate) fn result_records<'a>(
+    simulation_id: Option<i32>,
+    results: impl Iterator<Item = (&'a SimulationResultLookup, &'a TeamSimulationResults)>,
+) -> Vec<ResultRecord> {
+    let mut records: Vec<ResultRecord> = Vec::new();
+    for (lookup, results) in results {
+        for (result_set, counts) in [
+            (ResultSet::PlayoffSeed, &results.playoff_seedings),
+            (ResultSet::DraftPosition, &results.draft_positions),
+            (ResultSet::WinTotal, &results.win_totals),
+        ] {
+            for (team_rank, simulations_with_rank) in counts.iter() {
+                records.push(ResultRecord {
+                    simulation_id,
+                    game_id: lookup.game_id,
+                    game_result: lookup.game_result.clone(),
+                    team_id: lookup.team_id,
+                    result_set: result_set.clone(),
+                    team_rank: *team_rank,
+                    simulations_with_rank: *simulations_with_rank,
+                    simulations: results.simulations,
+                });
+            }
+        }
+        // Runs without full seeding play no playoffs, so only rounds some simulation reached
+        // are written.
+        for result_set in ResultSet::PLAYOFF_ROUNDS {
+            let simulations_with_rank: i32 = match result_set.playoff_round() {
+                Some(round) => *results.playoff_round_wins.get(&round).unwrap_or(&0),
+                None => 0,
+            };
+            if simulations_with_rank == 0 {
+                continue;
+            }
+            records.push(ResultRecord {
+                simulation_id,
+                game_id: lookup.game_id,
+                game_result: lookup.game_result.clone(),
+                team_id: lookup.team_id,
+                result_set,
+                team_rank: 1,
+                simulations_with_rank,
+                simulations: results.simulations,
+            });
+        }
+    }
+    records.sort_by_key(|record| {
+        (
+            record.game_id,
+            record.game_result.as_ref().map(|gr| gr.to_string()),
+            record.team_id,
+            record.result_set.to_string(),
+            record.team_rank,
+        )
+    });
+    records
+}
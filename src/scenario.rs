@@ -0,0 +1,132 @@
+use crate::{Error, Game, GameResult, Season, Team};
+use serde::Deserialize;
+use std::fmt;
+use std::fs;
+
+/// A what-if scenario read from a TOML file, such as:
+///
+/// ```toml
+/// season = 2024
+/// sims = 20000
+/// teams = ["NYJ", "BUF"]
+///
+/// [[results]]
+/// week = 16
+/// team = "NYJ"
+/// result = "win"
+/// ```
+///
+/// `sims` and `seed` are optional, and `teams` lists the teams to compare, all of them when
+/// left out. Each forced result names a team and the week of its game, with `win`, `loss` or
+/// `tie` from that team's side.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ScenarioFile {
+    pub season: i32,
+    pub sims: Option<u64>,
+    pub seed: Option<u64>,
+    #[serde(default)]
+    pub teams: Vec<String>,
+    #[serde(default)]
+    pub results: Vec<ForcedResult>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct ForcedResult {
+    pub week: i32,
+    pub team: String,
+    pub result: TeamOutcome,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TeamOutcome {
+    Win,
+    Loss,
+    Tie,
+}
+
+impl fmt::Display for TeamOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TeamOutcome::Win => write!(f, "win"),
+            TeamOutcome::Loss => write!(f, "loss"),
+            TeamOutcome::Tie => write!(f, "tie"),
+        }
+    }
+}
+
+impl ScenarioFile {
+    pub fn load(path: &str) -> Result<ScenarioFile, Error> {
+        toml::from_str(&fs::read_to_string(path)?)
+            .map_err(|e| Error::InvalidData(format!("{path}: {e}")))
+    }
+
+    pub fn parse(contents: &str) -> Result<ScenarioFile, Error> {
+        toml::from_str(contents).map_err(|e| Error::InvalidData(e.to_string()))
+    }
+
+    /// The forced results as games of `season` and their results, for
+    /// `Season::simulate_with_overrides`.
+    pub fn overrides(&self, season: &Season) -> Result<Vec<(i32, GameResult)>, Error> {
+        self.results
+            .iter()
+            .map(|forced_result| forced_result.resolve(season))
+            .collect()
+    }
+
+    /// Ids of the teams to compare, in the file's order, or every team by division.
+    pub fn team_ids(&self, season: &Season) -> Result<Vec<i32>, Error> {
+        match self.teams.is_empty() {
+            true => Ok(season
+                .teams_sorted()
+                .into_iter()
+                .map(|team| team.team_id)
+                .collect()),
+            false => self
+                .teams
+                .iter()
+                .map(|abbreviation| Ok(find_team(season, abbreviation)?.team_id))
+                .collect(),
+        }
+    }
+}
+
+impl ForcedResult {
+    /// The team's game in `week`, with the result that gives the team `result`.
+    pub fn resolve(&self, season: &Season) -> Result<(i32, GameResult), Error> {
+        let team: &Team = find_team(season, &self.team)?;
+        let game: &Game = match season.actual_games.values().find(|game| {
+            game.week == self.week
+                && (game.home_team.team_id == team.team_id
+                    || game.away_team.team_id == team.team_id)
+        }) {
+            Some(game) => game,
+            None => {
+                return Err(Error::InvalidArgument(format!(
+                    "{} has no game in week {}",
+                    self.team, self.week
+                )))
+            }
+        };
+        let is_home: bool = game.home_team.team_id == team.team_id;
+        let game_result: GameResult = match (self.result, is_home) {
+            (TeamOutcome::Tie, _) => GameResult::Tie,
+            (TeamOutcome::Win, true) | (TeamOutcome::Loss, false) => GameResult::HomeWin,
+            (TeamOutcome::Win, false) | (TeamOutcome::Loss, true) => GameResult::AwayWin,
+        };
+        Ok((game.game_id, game_result))
+    }
+}
+
+fn find_team<'a>(season: &'a Season, abbreviation: &str) -> Result<&'a Team, Error> {
+    match season
+        .teams
+        .values()
+        .find(|team| team.abbreviation == abbreviation)
+    {
+        Some(team) => Ok(team),
+        None => Err(Error::InvalidArgument(format!(
+            "unknown team {abbreviation}"
+        ))),
+    }
+}
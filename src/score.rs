@@ -0,0 +1,132 @@
+use crate::odds::SPREAD_STANDARD_DEVIATION;
+use crate::GameResult;
+use rand::{Rng, RngCore};
+
+/// Widest margin `ScoreModel` draws.
+const MAX_MARGIN: i32 = 50;
+
+/// Final scores for simulated games, for `SimulationConfig::score_model`. The result is drawn
+/// exactly as without scores, so odds of winning don't change; the margin is then drawn for
+/// that result from a normal around the margin the win probability implies, weighted toward
+/// the key numbers, and the loser's points from a normal around what is left of the average
+/// total.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScoreModel {
+    /// Standard deviation, in points, of the final margin around its expectation.
+    pub margin_standard_deviation: f64,
+    /// Relative weights of winning margins, multiplying the normal density. Margins not listed
+    /// weigh 1.
+    pub key_numbers: Vec<(i32, f64)>,
+    /// Average combined points of both teams.
+    pub average_total: f64,
+    /// Standard deviation, in points, of the losing team's score.
+    pub losing_score_standard_deviation: f64,
+}
+
+impl Default for ScoreModel {
+    fn default() -> ScoreModel {
+        ScoreModel {
+            margin_standard_deviation: SPREAD_STANDARD_DEVIATION,
+            key_numbers: vec![
+                (1, 1.2),
+                (3, 2.6),
+                (4, 1.3),
+                (6, 1.4),
+                (7, 1.9),
+                (10, 1.4),
+                (14, 1.3),
+                (17, 1.1),
+            ],
+            average_total: 44.0,
+            losing_score_standard_deviation: 7.0,
+        }
+    }
+}
+
+impl ScoreModel {
+    pub fn with_key_numbers(mut self, key_numbers: Vec<(i32, f64)>) -> ScoreModel {
+        self.key_numbers = key_numbers;
+        self
+    }
+
+    pub fn with_average_total(mut self, average_total: f64) -> ScoreModel {
+        self.average_total = average_total;
+        self
+    }
+
+    /// Home and away points for a game the home team wins with `home_win_probability`,
+    /// given its result. Always takes three draws from `rng`, so common random numbers stay
+    /// in step whatever the result.
+    pub fn draw_scores(
+        &self,
+        rng: &mut dyn RngCore,
+        home_win_probability: f64,
+        game_result: &GameResult,
+    ) -> (i32, i32) {
+        let margin_predictor: f64 = rng.gen();
+        let first_uniform: f64 = rng.gen();
+        let second_uniform: f64 = rng.gen();
+
+        let expected_home_margin: f64 = self.margin_standard_deviation
+            * inverse_normal_cdf(home_win_probability.clamp(0.001, 0.999));
+        let margin: i32 = match game_result {
+            GameResult::HomeWin => self.draw_margin(margin_predictor, expected_home_margin),
+            GameResult::AwayWin => self.draw_margin(margin_predictor, -expected_home_margin),
+            GameResult::Tie => 0,
+        };
+        // Box-Muller
+        let standard_normal: f64 = (-2.0 * (1.0 - first_uniform).ln()).sqrt()
+            * (std::f64::consts::TAU * second_uniform).cos();
+        let losing_score: f64 = (self.average_total - f64::from(margin)) / 2.0
+            + self.losing_score_standard_deviation * standard_normal;
+        let losing_score: i32 = match losing_score.round().max(0.0) as i32 {
+            // A team can't finish with a single point.
+            1 => 0,
+            points => points,
+        };
+        match game_result {
+            GameResult::AwayWin => (losing_score, losing_score + margin),
+            _ => (losing_score + margin, losing_score),
+        }
+    }
+
+    /// Winning margin from 1 to `MAX_MARGIN` for a winner expected to win by
+    /// `expected_margin`, which is negative for an underdog.
+    fn draw_margin(&self, predictor: f64, expected_margin: f64) -> i32 {
+        let weights: Vec<f64> = (1..=MAX_MARGIN)
+            .map(|margin| {
+                let z: f64 = (f64::from(margin) - expected_margin) / self.margin_standard_deviation;
+                let key_weight: f64 = self
+                    .key_numbers
+                    .iter()
+                    .find(|(key_number, _)| *key_number == margin)
+                    .map_or(1.0, |(_, weight)| *weight);
+                (-0.5 * z * z).exp() * key_weight
+            })
+            .collect();
+        let mut remaining: f64 = predictor * weights.iter().sum::<f64>();
+        for (margin, weight) in (1..=MAX_MARGIN).zip(weights.iter()) {
+            remaining -= weight;
+            if remaining < 0.0 {
+                return margin;
+            }
+        }
+        MAX_MARGIN
+    }
+}
+
+// Abramowitz and Stegun 26.2.23, accurate to about 4.5e-4
+fn inverse_normal_cdf(p: f64) -> f64 {
+    let tail: f64 = match p < 0.5 {
+        true => p,
+        false => 1.0 - p,
+    };
+    let t: f64 = (-2.0 * tail.ln()).sqrt();
+    let x: f64 = t
+        - (2.515517 + t * (0.802853 + t * 0.010328))
+            / (1.0 + t * (1.432788 + t * (0.189269 + t * 0.001308)));
+    match p < 0.5 {
+        true => -x,
+        false => x,
+    }
+}
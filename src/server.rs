@@ -0,0 +1,405 @@
+use crate::{
+    ConditionalOdds, Db, Error, Game, GameResult, ProgressSink, Season, SimulationReport, Team,
+    TeamOdds,
+};
+use axum::extract::{FromRef, Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use postgres::Row;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::convert::Infallible;
+use tokio::net::TcpListener;
+use tokio::runtime::Runtime;
+use tokio::sync::mpsc::{self, UnboundedSender};
+use tokio::task;
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use tokio_stream::{Stream, StreamExt};
+use tracing::{error, info};
+
+const DEFAULT_WHAT_IF_SIMS: u64 = 10000;
+/// Most simulations a single request may ask for unless the server is given another limit.
+pub const DEFAULT_MAX_SIMS: u64 = 100000;
+
+/// What every handler can reach: the database and the largest `sims` a request may ask for.
+#[derive(Clone)]
+struct ServerState {
+    db: Db,
+    max_sims: u64,
+}
+
+impl FromRef<ServerState> for Db {
+    fn from_ref(state: &ServerState) -> Db {
+        state.db.clone()
+    }
+}
+
+/// Selects a stored simulation; the season's most recent one when left out.
+#[derive(Deserialize)]
+struct SimulationQuery {
+    simulation_id: Option<i32>,
+}
+
+#[derive(Serialize)]
+struct TeamOddsResponse {
+    team: Team,
+    odds: TeamOdds,
+}
+
+#[derive(Serialize)]
+struct OddsResponse {
+    season: i32,
+    simulation_id: Option<i32>,
+    teams: Vec<TeamOddsResponse>,
+}
+
+/// One game's effect on a team: its odds under each of the game's results.
+#[derive(Serialize)]
+struct GameScenarioResponse {
+    game: Game,
+    odds: ConditionalOdds,
+}
+
+#[derive(Serialize)]
+struct ScenariosResponse {
+    season: i32,
+    simulation_id: i32,
+    team: Team,
+    games: Vec<GameScenarioResponse>,
+}
+
+#[derive(Deserialize)]
+struct ForcedResult {
+    game_id: i32,
+    game_result: GameResult,
+}
+
+#[derive(Deserialize)]
+struct SimulateRequest {
+    season: i32,
+    #[serde(default)]
+    forced_results: Vec<ForcedResult>,
+    #[serde(default = "default_what_if_sims")]
+    sims: u64,
+    seed: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct StreamQuery {
+    #[serde(default = "default_what_if_sims")]
+    sims: u64,
+    #[serde(default)]
+    include_decided: bool,
+    seed: Option<u64>,
+}
+
+#[derive(Serialize)]
+struct GameEvent<'a> {
+    index: usize,
+    total_games: usize,
+    game: &'a Game,
+}
+
+/// A finished scenario; `game_id` and `game_result` are `null` for the current state.
+#[derive(Serialize)]
+struct ScenarioEvent<'a> {
+    game_id: Option<i32>,
+    game_result: Option<&'a GameResult>,
+    odds: Vec<&'a TeamOdds>,
+}
+
+/// Forwards a running simulation's progress as server-sent events: `game` as each game's
+/// scenarios start, and `scenario` with every team's odds as each scenario finishes.
+#[derive(Clone, Debug)]
+struct EventProgress {
+    sender: UnboundedSender<Event>,
+}
+
+impl EventProgress {
+    fn send<T: Serialize>(&self, name: &str, data: &T) {
+        match Event::default().event(name).json_data(data) {
+            Ok(event) => {
+                let _ = self.sender.send(event);
+            }
+            Err(e) => error!(error = %e, "could not serialize {} event", name),
+        }
+    }
+}
+
+impl ProgressSink for EventProgress {
+    fn on_game_started(&self, game: &Game, index: usize, total_games: usize) {
+        self.send(
+            "game",
+            &GameEvent {
+                index,
+                total_games,
+                game,
+            },
+        );
+    }
+
+    fn on_scenario_done(
+        &self,
+        scenario: Option<(i32, &GameResult)>,
+        odds: &HashMap<i32, TeamOdds>,
+    ) {
+        let mut odds: Vec<&TeamOdds> = odds.values().collect();
+        odds.sort_by_key(|team_odds| team_odds.team_id);
+        self.send(
+            "scenario",
+            &ScenarioEvent {
+                game_id: scenario.map(|(game_id, _)| game_id),
+                game_result: scenario.map(|(_, game_result)| game_result),
+                odds,
+            },
+        );
+    }
+}
+
+fn default_what_if_sims() -> u64 {
+    DEFAULT_WHAT_IF_SIMS
+}
+
+/// `Error` as an HTTP response: 400 for bad arguments, 404 for missing seasons, teams and
+/// simulations, and 500 for everything else.
+enum ApiError {
+    NotFound(String),
+    Internal(Error),
+}
+
+impl From<Error> for ApiError {
+    fn from(e: Error) -> ApiError {
+        ApiError::Internal(e)
+    }
+}
+
+impl From<postgres::Error> for ApiError {
+    fn from(e: postgres::Error) -> ApiError {
+        ApiError::Internal(Error::from(e))
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let (status, message): (StatusCode, String) = match self {
+            ApiError::NotFound(message) => (StatusCode::NOT_FOUND, message),
+            ApiError::Internal(Error::InvalidArgument(message)) => {
+                (StatusCode::BAD_REQUEST, message)
+            }
+            ApiError::Internal(e) => {
+                error!(error = %e, "request failed");
+                (StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
+            }
+        };
+        (status, Json(serde_json::json!({ "error": message }))).into_response()
+    }
+}
+
+/// Routes for a frontend over the stored results, all returning JSON:
+///
+/// - `GET /seasons/{year}/odds` - every team's baseline odds
+/// - `GET /teams/{id}/scenarios` - the team's odds under each result of each simulated game
+/// - `POST /simulate` - baseline odds with `forced_results` decided, simulated on demand and
+///   not stored. Asking for more than `max_sims` simulations is a 400
+/// - `GET /seasons/{year}/stream` - a full run of the current state and every game's
+///   scenarios, streamed as server-sent events while it runs and ending with `done` or
+///   `error`. Takes `sims`, `include_decided` and `seed` query parameters, with `sims`
+///   limited like `POST /simulate`. Nothing is stored. The run continues if the client
+///   disconnects
+///
+/// The `GET` routes read the season's most recent simulation unless given `?simulation_id=`.
+pub fn router(db: Db, max_sims: u64) -> Router {
+    Router::new()
+        .route("/seasons/{year}/odds", get(season_odds))
+        .route("/teams/{id}/scenarios", get(team_scenarios))
+        .route("/simulate", post(simulate))
+        .route("/seasons/{year}/stream", get(season_stream))
+        .with_state(ServerState { db, max_sims })
+}
+
+/// Serves `router` on `addr` until the process is stopped.
+pub fn serve(addr: &str, db: Db, max_sims: u64) -> Result<(), Error> {
+    let runtime: Runtime = Runtime::new()?;
+    runtime.block_on(async {
+        let listener: TcpListener = TcpListener::bind(addr).await?;
+        info!(addr, "serving");
+        axum::serve(listener, router(db, max_sims)).await?;
+        Ok(())
+    })
+}
+
+/// Rejects a request for more than `max_sims` simulations before any work starts.
+fn check_sims(sims: u64, max_sims: u64) -> Result<(), ApiError> {
+    match sims > max_sims {
+        true => Err(ApiError::Internal(Error::InvalidArgument(format!(
+            "sims must be at most {max_sims}, got {sims}"
+        )))),
+        false => Ok(()),
+    }
+}
+
+/// Runs blocking database or simulation work off the async workers.
+async fn blocking<T, F>(work: F) -> Result<T, ApiError>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, ApiError> + Send + 'static,
+{
+    match task::spawn_blocking(work).await {
+        Ok(result) => result,
+        Err(e) => Err(ApiError::Internal(Error::InvalidData(format!(
+            "request task failed: {e}"
+        )))),
+    }
+}
+
+async fn season_odds(
+    State(db): State<Db>,
+    Path(year): Path<i32>,
+    Query(query): Query<SimulationQuery>,
+) -> Result<Json<OddsResponse>, ApiError> {
+    blocking(move || {
+        let simulation_id: i32 = match query.simulation_id {
+            Some(simulation_id) => simulation_id,
+            None => latest_simulation_id(&db, year)?,
+        };
+        let season: Season = Season::new_from_year_with_db(year, &db)?;
+        let report: SimulationReport = season.load_results(simulation_id)?;
+        Ok(Json(odds_response(&season, &report)))
+    })
+    .await
+}
+
+async fn team_scenarios(
+    State(db): State<Db>,
+    Path(team_id): Path<i32>,
+    Query(query): Query<SimulationQuery>,
+) -> Result<Json<ScenariosResponse>, ApiError> {
+    blocking(move || {
+        let rows: Vec<Row> = db.query(
+            "
+            SELECT max(season)
+            FROM nfl.games
+            WHERE home_team_id=$1 OR away_team_id=$1;
+            ",
+            &[&team_id],
+        )?;
+        let year: i32 = match rows.first().map(|row| row.try_get(0)).transpose()? {
+            Some(Some(year)) => year,
+            _ => return Err(ApiError::NotFound(format!("team {team_id} has no games"))),
+        };
+        let simulation_id: i32 = match query.simulation_id {
+            Some(simulation_id) => simulation_id,
+            None => latest_simulation_id(&db, year)?,
+        };
+        let season: Season = Season::new_from_year_with_db(year, &db)?;
+        let report: SimulationReport = season.load_results(simulation_id)?;
+        let team: Team = match season.teams.get(&team_id) {
+            Some(team) => Team::clone(team),
+            None => return Err(ApiError::NotFound(format!("team {team_id} does not exist"))),
+        };
+        let games: Vec<GameScenarioResponse> = report
+            .conditional_reports()
+            .into_iter()
+            .filter_map(|mut conditional_report| {
+                let odds: ConditionalOdds = conditional_report.teams.remove(&team_id)?;
+                let game: Game = season
+                    .actual_games
+                    .get(&conditional_report.game_id)?
+                    .clone();
+                Some(GameScenarioResponse { game, odds })
+            })
+            .collect();
+        Ok(Json(ScenariosResponse {
+            season: year,
+            simulation_id,
+            team,
+            games,
+        }))
+    })
+    .await
+}
+
+async fn simulate(
+    State(state): State<ServerState>,
+    Json(request): Json<SimulateRequest>,
+) -> Result<Json<OddsResponse>, ApiError> {
+    check_sims(request.sims, state.max_sims)?;
+    let db: Db = state.db;
+    blocking(move || {
+        let mut season: Season = Season::new_from_year_with_db(request.season, &db)?;
+        if let Some(seed) = request.seed {
+            season.set_seed(seed);
+        }
+        let overrides: Vec<(i32, GameResult)> = request
+            .forced_results
+            .into_iter()
+            .map(|forced_result| (forced_result.game_id, forced_result.game_result))
+            .collect();
+        let report: SimulationReport = season.simulate_with_overrides(overrides, request.sims)?;
+        Ok(Json(odds_response(&season, &report)))
+    })
+    .await
+}
+
+async fn season_stream(
+    State(state): State<ServerState>,
+    Path(year): Path<i32>,
+    Query(query): Query<StreamQuery>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    check_sims(query.sims, state.max_sims)?;
+    let db: Db = state.db;
+    let season: Season = blocking(move || Ok(Season::new_from_year_with_db(year, &db)?)).await?;
+    let (sender, receiver) = mpsc::unbounded_channel::<Event>();
+    let progress: EventProgress = EventProgress { sender };
+    let mut season: Season = season.with_progress(progress.clone());
+    if let Some(seed) = query.seed {
+        season.set_seed(seed);
+    }
+
+    task::spawn_blocking(
+        move || match season.simulate(query.sims, query.include_decided) {
+            Ok(_) => progress.send("done", &serde_json::json!({})),
+            Err(e) => {
+                error!(error = %e, "streamed simulation failed");
+                progress.send("error", &serde_json::json!({ "error": e.to_string() }));
+            }
+        },
+    );
+
+    let events = UnboundedReceiverStream::new(receiver).map(Ok);
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+fn latest_simulation_id(db: &Db, year: i32) -> Result<i32, ApiError> {
+    let rows: Vec<Row> = db.query(
+        "SELECT max(simulation_id) FROM nfl.simulations WHERE season=$1;",
+        &[&year],
+    )?;
+    match rows.first().map(|row| row.try_get(0)).transpose()? {
+        Some(Some(simulation_id)) => Ok(simulation_id),
+        _ => Err(ApiError::NotFound(format!(
+            "season {year} has no stored simulations"
+        ))),
+    }
+}
+
+fn odds_response(season: &Season, report: &SimulationReport) -> OddsResponse {
+    let teams: Vec<TeamOddsResponse> = season
+        .teams_sorted()
+        .into_iter()
+        .filter_map(|team| {
+            Some(TeamOddsResponse {
+                team: team.clone(),
+                odds: report.team_odds(team.team_id)?,
+            })
+        })
+        .collect();
+    OddsResponse {
+        season: report.season_year,
+        simulation_id: report.simulation_id,
+        teams,
+    }
+}
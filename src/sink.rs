@@ -0,0 +1,335 @@
+use crate::report::write_ndjson_record;
+#[cfg(feature = "postgres")]
+use crate::{count_to_db, Db, PooledClient};
+use crate::{Error, ResultRecord};
+#[cfg(feature = "postgres")]
+use postgres::{Client, CopyInWriter, GenericClient, Row, Transaction};
+use std::fs::File;
+use std::io::{self, BufWriter, StdoutLock, Write};
+#[cfg(feature = "postgres")]
+use tracing::{debug, info_span};
+
+/// Null marker in `COPY` text format.
+#[cfg(feature = "postgres")]
+const COPY_NULL: &str = "\\N";
+
+/// The run a `ResultSink` is about to receive. With a `StoppingRule` the counts are caps, and
+/// each record carries its scenario's actual count.
+#[derive(Clone, Debug)]
+pub struct RunInfo {
+    pub season_year: i32,
+    pub baseline_sims: u64,
+    pub scenario_sims: u64,
+    pub include_decided: bool,
+}
+
+/// Receives a run's results one scenario at a time, as each finishes. Any number of sinks can
+/// be attached to a run with `Season::simulate_into`, e.g. Postgres and a CSV file together.
+pub trait ResultSink {
+    /// Before the first scenario. Returns the id the sink stores the run under, if it assigns
+    /// one; the first id returned becomes the season's `simulation_id`.
+    fn start(&mut self, _run: &RunInfo) -> Result<Option<i32>, Error> {
+        Ok(None)
+    }
+
+    /// One scenario's records: the current state's, or one pinned game result's.
+    fn write_scenario(&mut self, records: &[ResultRecord]) -> Result<(), Error>;
+
+    /// After the last scenario.
+    fn finish(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// Inserts each scenario into `nfl.simulation_results` in its own transaction, so that an
+/// interrupted run can be finished with `Season::resume`.
+#[cfg(feature = "postgres")]
+pub struct PostgresResultSink {
+    db: Db,
+    simulation_id: Option<i32>,
+}
+
+#[cfg(feature = "postgres")]
+impl PostgresResultSink {
+    /// Stores the run under a new row of `nfl.simulations`.
+    pub fn new(db: Db) -> PostgresResultSink {
+        PostgresResultSink {
+            db,
+            simulation_id: None,
+        }
+    }
+
+    /// Adds to a simulation that is already stored, such as one being resumed.
+    pub fn resuming(db: Db, simulation_id: i32) -> PostgresResultSink {
+        PostgresResultSink {
+            db,
+            simulation_id: Some(simulation_id),
+        }
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl ResultSink for PostgresResultSink {
+    fn start(&mut self, run: &RunInfo) -> Result<Option<i32>, Error> {
+        if self.simulation_id.is_none() {
+            let mut client: PooledClient = self.db.get()?;
+            self.simulation_id = Some(insert_simulation_with_client(run, &mut *client)?);
+        }
+        Ok(self.simulation_id)
+    }
+
+    fn write_scenario(&mut self, records: &[ResultRecord]) -> Result<(), Error> {
+        let simulation_id: i32 = match self.simulation_id {
+            Some(simulation_id) => simulation_id,
+            None => {
+                return Err(Error::InvalidArgument(String::from(
+                    "PostgresResultSink received results before start",
+                )))
+            }
+        };
+        let mut client: PooledClient = self.db.get()?;
+        insert_results_with_client(simulation_id, records, &mut client)
+    }
+}
+
+/// Writes records as CSV with the fields of `SimulationReport::write_ndjson` as columns.
+pub struct CsvResultSink<W: Write> {
+    writer: csv::Writer<W>,
+    season_year: i32,
+}
+
+impl CsvResultSink<File> {
+    pub fn create(path: &str) -> Result<CsvResultSink<File>, Error> {
+        Ok(CsvResultSink::new(File::create(path)?))
+    }
+}
+
+impl<W: Write> CsvResultSink<W> {
+    pub fn new(writer: W) -> CsvResultSink<W> {
+        CsvResultSink {
+            writer: csv::Writer::from_writer(writer),
+            season_year: 0,
+        }
+    }
+}
+
+impl<W: Write> ResultSink for CsvResultSink<W> {
+    fn start(&mut self, run: &RunInfo) -> Result<Option<i32>, Error> {
+        self.season_year = run.season_year;
+        self.writer.write_record([
+            "simulation_id",
+            "season",
+            "game_id",
+            "game_result",
+            "team_id",
+            "result_set",
+            "team_rank",
+            "simulations_with_rank",
+            "simulations",
+        ])?;
+        Ok(None)
+    }
+
+    fn write_scenario(&mut self, records: &[ResultRecord]) -> Result<(), Error> {
+        let season: String = self.season_year.to_string();
+        for record in records {
+            self.writer.write_record([
+                record
+                    .simulation_id
+                    .map_or_else(String::new, |id| id.to_string()),
+                season.clone(),
+                record.game_id.map_or_else(String::new, |id| id.to_string()),
+                record
+                    .game_result
+                    .as_ref()
+                    .map_or_else(String::new, |gr| gr.to_string()),
+                record.team_id.to_string(),
+                record.result_set.to_string(),
+                record.team_rank.to_string(),
+                record.simulations_with_rank.to_string(),
+                record.simulations.to_string(),
+            ])?;
+        }
+        // Flushed per scenario so the file is usable while a long run is still going.
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<(), Error> {
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Writes records as newline-delimited JSON, in the format of `SimulationReport::write_ndjson`.
+pub struct JsonResultSink<W: Write> {
+    writer: W,
+    season_year: i32,
+}
+
+impl JsonResultSink<BufWriter<File>> {
+    pub fn create(path: &str) -> Result<JsonResultSink<BufWriter<File>>, Error> {
+        Ok(JsonResultSink::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<W: Write> JsonResultSink<W> {
+    pub fn new(writer: W) -> JsonResultSink<W> {
+        JsonResultSink {
+            writer,
+            season_year: 0,
+        }
+    }
+}
+
+impl<W: Write> ResultSink for JsonResultSink<W> {
+    fn start(&mut self, run: &RunInfo) -> Result<Option<i32>, Error> {
+        self.season_year = run.season_year;
+        Ok(None)
+    }
+
+    fn write_scenario(&mut self, records: &[ResultRecord]) -> Result<(), Error> {
+        for record in records {
+            write_ndjson_record(&mut self.writer, self.season_year, record)?;
+        }
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Prints each scenario's records as an aligned table on standard output.
+#[derive(Clone, Debug, Default)]
+pub struct StdoutResultSink;
+
+impl ResultSink for StdoutResultSink {
+    fn write_scenario(&mut self, records: &[ResultRecord]) -> Result<(), Error> {
+        let mut stdout: StdoutLock = io::stdout().lock();
+        if let Some(record) = records.first() {
+            match (record.game_id, &record.game_result) {
+                (Some(game_id), Some(game_result)) => {
+                    writeln!(stdout, "\nGame {} {}", game_id, game_result)?
+                }
+                _ => writeln!(stdout, "\nCurrent state")?,
+            }
+        }
+        writeln!(
+            stdout,
+            "{:>6} {:<14} {:>4} {:>9} {:>9}",
+            "Team", "Result set", "Rank", "Sims", "Percent"
+        )?;
+        for record in records {
+            let percent: f64 = match record.simulations {
+                0 => 0.0,
+                s => f64::from(record.simulations_with_rank) / f64::from(s) * 100.0,
+            };
+            writeln!(
+                stdout,
+                "{:>6} {:<14} {:>4} {:>9} {:>8.1}%",
+                record.team_id,
+                record.result_set.to_string(),
+                record.team_rank,
+                record.simulations_with_rank,
+                percent
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Inserts a row into `nfl.simulations` for `run` and returns its id.
+#[cfg(feature = "postgres")]
+pub(crate) fn insert_simulation_with_client(
+    run: &RunInfo,
+    client: &mut impl GenericClient,
+) -> Result<i32, Error> {
+    let scenario_sims: i64 = count_to_db(run.scenario_sims)?;
+    let baseline_sims: i64 = count_to_db(run.baseline_sims)?;
+    let rows: Vec<Row> = client.query(
+        "
+            INSERT INTO  nfl.simulations
+            VALUES (
+                DEFAULT,
+                NOW(),
+                $1,
+                $2,
+                $3,
+                $4
+            )
+            RETURNING simulation_id;
+        ",
+        &[
+            &run.season_year,
+            &scenario_sims,
+            &baseline_sims,
+            &run.include_decided,
+        ],
+    )?;
+    match rows.first() {
+        Some(row) => Ok(row.try_get(0)?),
+        None => Err(Error::InvalidData(String::from(
+            "inserting a simulation returned no id",
+        ))),
+    }
+}
+
+/// Inserts one scenario's records in a transaction of its own.
+#[cfg(feature = "postgres")]
+fn insert_results_with_client(
+    simulation_id: i32,
+    records: &[ResultRecord],
+    client: &mut Client,
+) -> Result<(), Error> {
+    let mut transaction: Transaction = client.transaction()?;
+    copy_results(&mut transaction, simulation_id, records)?;
+    transaction.commit()?;
+    Ok(())
+}
+
+/// Streams the rows with `COPY` inside `transaction`, so a scenario of any size goes in as
+/// one unit without building a single huge statement. Rows are sent as the copy buffer
+/// fills rather than all at once.
+#[cfg(feature = "postgres")]
+pub(crate) fn copy_results(
+    transaction: &mut Transaction,
+    simulation_id: i32,
+    records: &[ResultRecord],
+) -> Result<(), Error> {
+    let _span = info_span!("insert_results", simulation_id).entered();
+    let mut writer: CopyInWriter = transaction.copy_in(
+        "
+        COPY nfl.simulation_results (
+            simulation_id,
+            game_id,
+            simulated_game_result,
+            simulation_team_id,
+            result_set,
+            team_rank,
+            simulations_with_rank,
+            simulations
+        )
+        FROM STDIN
+    ",
+    )?;
+    debug!(rows = records.len(), "copying simulation results");
+    for record in records {
+        let game_id: String = match record.game_id {
+            Some(gid) => gid.to_string(),
+            None => String::from(COPY_NULL),
+        };
+        let simulated_game_result: String = match &record.game_result {
+            Some(gr) => gr.to_string(),
+            None => String::from(COPY_NULL),
+        };
+        writeln!(
+            writer,
+            "{simulation_id}\t{game_id}\t{simulated_game_result}\t{}\t{}\t{}\t{}\t{}",
+            record.team_id,
+            record.result_set,
+            record.team_rank,
+            record.simulations_with_rank,
+            record.simulations,
+        )?;
+    }
+    writer.finish()?;
+    Ok(())
+}
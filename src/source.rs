@@ -0,0 +1,331 @@
+#[cfg(feature = "postgres")]
+use crate::{Db, PooledClient};
+use crate::{Error, Team};
+use csv::StringRecord;
+#[cfg(feature = "postgres")]
+use postgres::{Client, Row};
+use std::collections::HashMap;
+
+/// A regular-season game as stored, before its teams are resolved. Team ids are `None` for
+/// an opponent that isn't known yet, and scores are `None` until the game is played.
+/// Touchdowns are `None` when the source doesn't record them. A neutral-site game, such as
+/// an international game, still lists one team as home.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScheduledGame {
+    pub game_id: i32,
+    pub season_year: i32,
+    pub week: i32,
+    pub home_team_id: Option<i32>,
+    pub away_team_id: Option<i32>,
+    pub home_score: Option<i32>,
+    pub away_score: Option<i32>,
+    pub neutral_site: bool,
+    pub home_touchdowns: Option<i32>,
+    pub away_touchdowns: Option<i32>,
+}
+
+#[cfg(feature = "postgres")]
+impl ScheduledGame {
+    pub fn new_from_db_row(row: Row) -> Result<ScheduledGame, Error> {
+        Ok(ScheduledGame {
+            game_id: row.try_get(0)?,
+            season_year: row.try_get(1)?,
+            week: row.try_get(2)?,
+            home_team_id: row.try_get(3)?,
+            away_team_id: row.try_get(4)?,
+            home_score: row.try_get(5)?,
+            away_score: row.try_get(6)?,
+            neutral_site: row.try_get(7)?,
+            home_touchdowns: row.try_get(8)?,
+            away_touchdowns: row.try_get(9)?,
+        })
+    }
+}
+
+/// Where `Season::new_from_source` gets a season's teams and schedule. Implemented for a
+/// Postgres `Client` and `Db` pool with the `postgres` feature, for SQLite files with
+/// `SqliteDataSource`, and for flat files with `CsvDataSource`.
+pub trait DataSource {
+    /// Teams playing in `season_year`.
+    fn fetch_teams(&mut self, season_year: i32) -> Result<Vec<Team>, Error>;
+    /// Regular-season games of `season_year`.
+    fn fetch_games(&mut self, season_year: i32) -> Result<Vec<ScheduledGame>, Error>;
+}
+
+#[cfg(feature = "postgres")]
+impl DataSource for Client {
+    fn fetch_teams(&mut self, season_year: i32) -> Result<Vec<Team>, Error> {
+        self.query(
+            "
+            SELECT
+                t.team_id,
+                t.abbreviation,
+                t.name,
+                t.conference,
+                t.division,
+                f.franchise_id
+            FROM nfl.teams t
+            LEFT JOIN nfl.franchises f
+            USING (team_id)
+            WHERE t.team_id in (
+                SELECT DISTINCT home_team_id
+                FROM nfl.games
+                WHERE season=$1
+            )
+            ORDER BY t.division, t.abbreviation;
+        ",
+            &[&season_year],
+        )?
+        .into_iter()
+        .map(Team::new_from_db_row)
+        .collect()
+    }
+
+    fn fetch_games(&mut self, season_year: i32) -> Result<Vec<ScheduledGame>, Error> {
+        self.query(
+            "
+            SELECT
+                game_id,
+                season,
+                week,
+                home_team_id,
+                away_team_id,
+                home_score,
+                away_score,
+                neutral_site,
+                home_touchdowns,
+                away_touchdowns
+            FROM nfl.games
+            WHERE
+                season=$1
+                AND game_type='REG';
+        ",
+            &[&season_year],
+        )?
+        .into_iter()
+        .map(ScheduledGame::new_from_db_row)
+        .collect()
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl DataSource for Db {
+    fn fetch_teams(&mut self, season_year: i32) -> Result<Vec<Team>, Error> {
+        let mut client: PooledClient = self.get()?;
+        client.fetch_teams(season_year)
+    }
+
+    fn fetch_games(&mut self, season_year: i32) -> Result<Vec<ScheduledGame>, Error> {
+        let mut client: PooledClient = self.get()?;
+        client.fetch_games(season_year)
+    }
+}
+
+/// Season data from nflverse-style CSV files. The schedule needs `season`, `week`,
+/// `home_team` and `away_team` columns, with optional `game_type` (only `REG` rows are
+/// loaded), `home_score`, `away_score`, `home_touchdowns`, `away_touchdowns` and `location`
+/// (`Neutral` marks a neutral-site game). Game ids come from a numeric `game_id`, or else
+/// `old_game_id`. The teams file needs `team_abbr`, `team_name`, `team_conf` and
+/// `team_division`; ids come from a numeric `team_id` column when there is one, and are
+/// otherwise numbered by abbreviation. Only teams with a home game in the season are loaded.
+#[derive(Clone, Debug)]
+pub struct CsvDataSource {
+    pub schedule_path: String,
+    pub teams_path: String,
+}
+
+impl CsvDataSource {
+    pub fn new(schedule_path: &str, teams_path: &str) -> CsvDataSource {
+        CsvDataSource {
+            schedule_path: String::from(schedule_path),
+            teams_path: String::from(teams_path),
+        }
+    }
+
+    fn read_teams(&self) -> Result<Vec<Team>, Error> {
+        let mut reader = csv::Reader::from_path(&self.teams_path)?;
+        let headers: StringRecord = reader.headers()?.clone();
+        let abbreviation_column: usize = column(&headers, "team_abbr", &self.teams_path)?;
+        let name_column: usize = column(&headers, "team_name", &self.teams_path)?;
+        let conference_column: usize = column(&headers, "team_conf", &self.teams_path)?;
+        let division_column: usize = column(&headers, "team_division", &self.teams_path)?;
+        let team_id_column: Option<usize> = headers.iter().position(|h| h == "team_id");
+
+        let mut teams: Vec<Team> = Vec::new();
+        for record in reader.records() {
+            let record: StringRecord = record?;
+            let team_id: i32 = match team_id_column {
+                Some(i) => parse_field(&record, i, &self.teams_path)?,
+                None => 0,
+            };
+            teams.push(Team {
+                team_id,
+                abbreviation: String::from(&record[abbreviation_column]),
+                name: String::from(&record[name_column]),
+                conference: String::from(&record[conference_column]),
+                division: String::from(&record[division_column]),
+                franchise_id: None,
+            });
+        }
+        if team_id_column.is_none() {
+            teams.sort_by(|a, b| a.abbreviation.cmp(&b.abbreviation));
+            for (team_id, team) in (1..).zip(teams.iter_mut()) {
+                team.team_id = team_id;
+            }
+        }
+        Ok(teams)
+    }
+
+    /// Regular-season schedule rows for `season_year`, before team ids are resolved.
+    fn read_schedule(&self, season_year: i32) -> Result<Vec<CsvGame>, Error> {
+        let path: &str = &self.schedule_path;
+        let mut reader = csv::Reader::from_path(path)?;
+        let headers: StringRecord = reader.headers()?.clone();
+        let season_column: usize = column(&headers, "season", path)?;
+        let week_column: usize = column(&headers, "week", path)?;
+        let home_team_column: usize = column(&headers, "home_team", path)?;
+        let away_team_column: usize = column(&headers, "away_team", path)?;
+        let game_type_column: Option<usize> = headers.iter().position(|h| h == "game_type");
+        let home_score_column: Option<usize> = headers.iter().position(|h| h == "home_score");
+        let away_score_column: Option<usize> = headers.iter().position(|h| h == "away_score");
+        let home_touchdowns_column: Option<usize> =
+            headers.iter().position(|h| h == "home_touchdowns");
+        let away_touchdowns_column: Option<usize> =
+            headers.iter().position(|h| h == "away_touchdowns");
+        let location_column: Option<usize> = headers.iter().position(|h| h == "location");
+        let game_id_columns: Vec<usize> = ["game_id", "old_game_id"]
+            .iter()
+            .filter_map(|name| headers.iter().position(|h| h == *name))
+            .collect();
+
+        let mut games: Vec<CsvGame> = Vec::new();
+        for record in reader.records() {
+            let record: StringRecord = record?;
+            if parse_field::<i32>(&record, season_column, path)? != season_year {
+                continue;
+            }
+            if game_type_column.is_some_and(|i| &record[i] != "REG") {
+                continue;
+            }
+            let game_id: Option<i32> = game_id_columns
+                .iter()
+                .find_map(|i| record[*i].trim().parse().ok());
+            let game_id: i32 = match game_id {
+                Some(game_id) => game_id,
+                None => return Err(invalid_record(&record, path)),
+            };
+            let home_score: Option<i32> = match home_score_column {
+                Some(i) => parse_optional_field(&record, i, path)?,
+                None => None,
+            };
+            let away_score: Option<i32> = match away_score_column {
+                Some(i) => parse_optional_field(&record, i, path)?,
+                None => None,
+            };
+            let home_touchdowns: Option<i32> = match home_touchdowns_column {
+                Some(i) => parse_optional_field(&record, i, path)?,
+                None => None,
+            };
+            let away_touchdowns: Option<i32> = match away_touchdowns_column {
+                Some(i) => parse_optional_field(&record, i, path)?,
+                None => None,
+            };
+            games.push(CsvGame {
+                scheduled_game: ScheduledGame {
+                    game_id,
+                    season_year,
+                    week: parse_field(&record, week_column, path)?,
+                    home_team_id: None,
+                    away_team_id: None,
+                    home_score,
+                    away_score,
+                    neutral_site: location_column.is_some_and(|i| &record[i] == "Neutral"),
+                    home_touchdowns,
+                    away_touchdowns,
+                },
+                home_team: String::from(&record[home_team_column]),
+                away_team: String::from(&record[away_team_column]),
+            });
+        }
+        Ok(games)
+    }
+}
+
+impl DataSource for CsvDataSource {
+    fn fetch_teams(&mut self, season_year: i32) -> Result<Vec<Team>, Error> {
+        let home_teams: Vec<String> = self
+            .read_schedule(season_year)?
+            .into_iter()
+            .map(|game| game.home_team)
+            .collect();
+        let mut teams: Vec<Team> = self
+            .read_teams()?
+            .into_iter()
+            .filter(|team| home_teams.contains(&team.abbreviation))
+            .collect();
+        teams.sort_by(|a, b| (&a.division, &a.abbreviation).cmp(&(&b.division, &b.abbreviation)));
+        Ok(teams)
+    }
+
+    fn fetch_games(&mut self, season_year: i32) -> Result<Vec<ScheduledGame>, Error> {
+        let team_ids: HashMap<String, i32> = self
+            .read_teams()?
+            .into_iter()
+            .map(|team| (team.abbreviation, team.team_id))
+            .collect();
+        let mut games: Vec<ScheduledGame> = Vec::new();
+        for game in self.read_schedule(season_year)? {
+            let mut scheduled_game: ScheduledGame = game.scheduled_game;
+            scheduled_game.home_team_id = team_ids.get(&game.home_team).copied();
+            scheduled_game.away_team_id = team_ids.get(&game.away_team).copied();
+            games.push(scheduled_game);
+        }
+        Ok(games)
+    }
+}
+
+struct CsvGame {
+    scheduled_game: ScheduledGame,
+    home_team: String,
+    away_team: String,
+}
+
+fn column(headers: &StringRecord, name: &str, path: &str) -> Result<usize, Error> {
+    match headers.iter().position(|h| h == name) {
+        Some(i) => Ok(i),
+        None => Err(Error::InvalidData(format!(
+            "{} has no {} column",
+            path, name
+        ))),
+    }
+}
+
+fn parse_field<T: std::str::FromStr>(
+    record: &StringRecord,
+    i: usize,
+    path: &str,
+) -> Result<T, Error> {
+    match record[i].trim().parse() {
+        Ok(value) => Ok(value),
+        Err(_) => Err(invalid_record(record, path)),
+    }
+}
+
+fn parse_optional_field<T: std::str::FromStr>(
+    record: &StringRecord,
+    i: usize,
+    path: &str,
+) -> Result<Option<T>, Error> {
+    match record[i].trim() {
+        "" | "NA" => Ok(None),
+        _ => Ok(Some(parse_field(record, i, path)?)),
+    }
+}
+
+fn invalid_record(record: &StringRecord, path: &str) -> Error {
+    Error::InvalidData(format!(
+        "invalid row in {}: {}",
+        path,
+        record.iter().collect::<Vec<&str>>().join(",")
+    ))
+}
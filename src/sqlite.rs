@@ -0,0 +1,264 @@
+use crate::{
+    DataSource, Error, ResultRecord, ResultSink, RunInfo, ScheduledGame, SimulationReport, Team,
+};
+use rusqlite::{params, Connection, Row, Transaction};
+
+/// Tables `SqliteResultSink` writes to, matching `migrations/up.sql` apart from types SQLite
+/// doesn't have.
+const RESULT_TABLES: &str = "
+    CREATE TABLE IF NOT EXISTS nfl.simulations (
+        simulation_id INTEGER PRIMARY KEY,
+        simulation_timestamp TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        season INTEGER NOT NULL,
+        simulations_per_game_result INTEGER NOT NULL,
+        baseline_simulations INTEGER NOT NULL,
+        include_decided INTEGER NOT NULL DEFAULT 0
+    );
+    CREATE TABLE IF NOT EXISTS nfl.simulation_results (
+        simulation_result_id INTEGER PRIMARY KEY,
+        simulation_id INTEGER NOT NULL REFERENCES simulations(simulation_id) ON DELETE CASCADE,
+        game_id INTEGER,
+        simulated_game_result TEXT,
+        simulation_team_id INTEGER NOT NULL,
+        result_set TEXT,
+        team_rank INTEGER,
+        simulations_with_rank INTEGER NOT NULL,
+        simulations INTEGER
+    );
+";
+
+/// Opens `path` attached as `nfl`, so tables are named as in Postgres.
+fn open(path: &str) -> Result<Connection, Error> {
+    let connection: Connection = Connection::open_in_memory()?;
+    connection.execute("ATTACH DATABASE ?1 AS nfl", params![path])?;
+    Ok(connection)
+}
+
+/// Season data from a SQLite file with the Postgres `nfl.teams`, `nfl.franchises` and
+/// `nfl.games` tables. `nfl.franchises` and the `neutral_site` column are optional.
+pub struct SqliteDataSource {
+    connection: Connection,
+}
+
+impl SqliteDataSource {
+    pub fn open(path: &str) -> Result<SqliteDataSource, Error> {
+        Ok(SqliteDataSource {
+            connection: open(path)?,
+        })
+    }
+}
+
+/// Whether `nfl.{table}` has `column`.
+fn has_column(connection: &Connection, table: &str, column: &str) -> Result<bool, Error> {
+    let mut statement = connection.prepare(&format!(
+        "SELECT name FROM pragma_table_info('{table}', 'nfl')"
+    ))?;
+    let columns: Vec<String> = statement
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<Vec<String>, rusqlite::Error>>()?;
+    Ok(columns.iter().any(|name| name == column))
+}
+
+impl DataSource for SqliteDataSource {
+    fn fetch_teams(&mut self, season_year: i32) -> Result<Vec<Team>, Error> {
+        let franchise_id: &str = match has_column(&self.connection, "franchises", "franchise_id")? {
+            true => "(SELECT f.franchise_id FROM nfl.franchises f WHERE f.team_id = t.team_id)",
+            false => "NULL",
+        };
+        let mut statement = self.connection.prepare(&format!(
+            "
+            SELECT
+                t.team_id,
+                t.abbreviation,
+                t.name,
+                t.conference,
+                t.division,
+                {franchise_id}
+            FROM nfl.teams t
+            WHERE t.team_id in (
+                SELECT DISTINCT home_team_id
+                FROM nfl.games
+                WHERE season=?1
+            )
+            ORDER BY t.division, t.abbreviation;
+        "
+        ))?;
+        let teams: Vec<Team> = statement
+            .query_map(params![season_year], |row: &Row| {
+                Ok(Team {
+                    team_id: row.get(0)?,
+                    abbreviation: row.get(1)?,
+                    name: row.get(2)?,
+                    conference: row.get(3)?,
+                    division: row.get(4)?,
+                    franchise_id: row.get(5)?,
+                })
+            })?
+            .collect::<Result<Vec<Team>, rusqlite::Error>>()?;
+        Ok(teams)
+    }
+
+    fn fetch_games(&mut self, season_year: i32) -> Result<Vec<ScheduledGame>, Error> {
+        let neutral_site: &str = match has_column(&self.connection, "games", "neutral_site")? {
+            true => "neutral_site",
+            false => "0",
+        };
+        let touchdowns: &str = match has_column(&self.connection, "games", "home_touchdowns")? {
+            true => "home_touchdowns, away_touchdowns",
+            false => "NULL, NULL",
+        };
+        let mut statement = self.connection.prepare(&format!(
+            "
+            SELECT
+                game_id,
+                season,
+                week,
+                home_team_id,
+                away_team_id,
+                home_score,
+                away_score,
+                {neutral_site},
+                {touchdowns}
+            FROM nfl.games
+            WHERE
+                season=?1
+                AND game_type='REG';
+        "
+        ))?;
+        let games: Vec<ScheduledGame> = statement
+            .query_map(params![season_year], |row: &Row| {
+                Ok(ScheduledGame {
+                    game_id: row.get(0)?,
+                    season_year: row.get(1)?,
+                    week: row.get(2)?,
+                    home_team_id: row.get(3)?,
+                    away_team_id: row.get(4)?,
+                    home_score: row.get(5)?,
+                    away_score: row.get(6)?,
+                    neutral_site: row.get(7)?,
+                    home_touchdowns: row.get(8)?,
+                    away_touchdowns: row.get(9)?,
+                })
+            })?
+            .collect::<Result<Vec<ScheduledGame>, rusqlite::Error>>()?;
+        Ok(games)
+    }
+}
+
+/// Stores runs in a SQLite file's `nfl.simulations` and `nfl.simulation_results`, creating
+/// the tables when they don't exist. Can be the same file as a `SqliteDataSource`. Each
+/// scenario is inserted in its own transaction.
+pub struct SqliteResultSink {
+    connection: Connection,
+    simulation_id: Option<i32>,
+}
+
+impl SqliteResultSink {
+    pub fn open(path: &str) -> Result<SqliteResultSink, Error> {
+        let connection: Connection = open(path)?;
+        connection.execute_batch(RESULT_TABLES)?;
+        if !has_column(&connection, "simulation_results", "simulations")? {
+            connection.execute_batch(
+                "ALTER TABLE nfl.simulation_results ADD COLUMN simulations INTEGER;",
+            )?;
+        }
+        Ok(SqliteResultSink {
+            connection,
+            simulation_id: None,
+        })
+    }
+
+    /// Inserts a finished `report` as a new simulation and returns its id. The simulation
+    /// counts are read from the report's baseline and first scenario.
+    pub fn store(&mut self, report: &SimulationReport) -> Result<i32, Error> {
+        let baseline_sims: i32 = report
+            .results
+            .iter()
+            .find(|(lookup, _)| lookup.game_id.is_none())
+            .map_or(0, |(_, results)| results.simulations);
+        let scenario_sims: i32 = report
+            .results
+            .iter()
+            .find(|(lookup, _)| lookup.game_id.is_some())
+            .map_or(0, |(_, results)| results.simulations);
+        let run: RunInfo = RunInfo {
+            season_year: report.season_year,
+            baseline_sims: baseline_sims.unsigned_abs().into(),
+            scenario_sims: scenario_sims.unsigned_abs().into(),
+            include_decided: false,
+        };
+        self.simulation_id = None;
+        let simulation_id: Option<i32> = self.start(&run)?;
+        self.write_scenario(&report.result_records())?;
+        simulation_id.ok_or_else(|| Error::InvalidData(String::from("no simulation id")))
+    }
+}
+
+impl ResultSink for SqliteResultSink {
+    fn start(&mut self, run: &RunInfo) -> Result<Option<i32>, Error> {
+        self.connection.execute(
+            "
+            INSERT INTO nfl.simulations (
+                season,
+                simulations_per_game_result,
+                baseline_simulations,
+                include_decided
+            )
+            VALUES (?1, ?2, ?3, ?4);
+        ",
+            params![
+                run.season_year,
+                run.scenario_sims,
+                run.baseline_sims,
+                run.include_decided
+            ],
+        )?;
+        let simulation_id: i32 = i32::try_from(self.connection.last_insert_rowid())
+            .map_err(|_| Error::InvalidData(String::from("simulation id is out of range")))?;
+        self.simulation_id = Some(simulation_id);
+        Ok(self.simulation_id)
+    }
+
+    fn write_scenario(&mut self, records: &[ResultRecord]) -> Result<(), Error> {
+        let simulation_id: i32 = match self.simulation_id {
+            Some(simulation_id) => simulation_id,
+            None => {
+                return Err(Error::InvalidArgument(String::from(
+                    "SqliteResultSink received results before start",
+                )))
+            }
+        };
+        let transaction: Transaction = self.connection.transaction()?;
+        {
+            let mut statement = transaction.prepare(
+                "
+                INSERT INTO nfl.simulation_results (
+                    simulation_id,
+                    game_id,
+                    simulated_game_result,
+                    simulation_team_id,
+                    result_set,
+                    team_rank,
+                    simulations_with_rank,
+                    simulations
+                )
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8);
+            ",
+            )?;
+            for record in records.iter() {
+                statement.execute(params![
+                    simulation_id,
+                    record.game_id,
+                    record.game_result.as_ref().map(|gr| gr.to_string()),
+                    record.team_id,
+                    record.result_set.to_string(),
+                    record.team_rank,
+                    record.simulations_with_rank,
+                    record.simulations,
+                ])?;
+            }
+        }
+        transaction.commit()?;
+        Ok(())
+    }
+}
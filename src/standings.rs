@@ -0,0 +1,383 @@
+use crate::{
+    CurrentSimulationResult, Error, Game, PoolType, Season, SeedingDetail, SimulationReport,
+    SimulationResultLookup, Team, TeamOdds, TieGroup, TiebreakDecision, TiebreakStep,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+#[derive(Clone, Debug)]
+pub struct Standing {
+    pub team_id: i32,
+    pub abbreviation: String,
+    pub name: String,
+    pub conference: String,
+    pub division: String,
+    pub overall_record: (u16, u16, u16),
+    pub overall_percent: u16,
+    pub division_record: (u16, u16, u16),
+    pub conference_record: (u16, u16, u16),
+    /// Points from decided games with a score.
+    pub points_for: i32,
+    pub points_against: i32,
+    pub playoff_seed: Option<u8>,
+}
+
+/// A team's current standing alongside its baseline odds.
+#[derive(Clone, Debug)]
+pub struct TeamSummary {
+    pub standing: Standing,
+    pub make_playoffs: f64,
+    /// Half-width of the 95% interval around `make_playoffs`.
+    pub make_playoffs_margin: f64,
+    pub win_division: f64,
+    pub first_round_bye: f64,
+    /// Chance of each seed; seeds the team never finished at are left out.
+    pub playoff_seeds: HashMap<u8, f64>,
+    pub expected_wins: f64,
+    pub expected_draft_position: Option<f64>,
+    pub played_strength_of_schedule: Option<f64>,
+    pub remaining_strength_of_schedule: Option<f64>,
+}
+
+impl TeamSummary {
+    pub fn new(standing: Standing, odds: &TeamOdds) -> TeamSummary {
+        TeamSummary {
+            standing,
+            make_playoffs: odds.make_playoffs,
+            make_playoffs_margin: odds.margin_of_error(odds.make_playoffs),
+            win_division: odds.win_division,
+            first_round_bye: odds.first_round_bye,
+            playoff_seeds: odds.playoff_seeds.clone(),
+            expected_wins: odds.expected_wins,
+            expected_draft_position: odds.expected_draft_position,
+            played_strength_of_schedule: odds.played_strength_of_schedule,
+            remaining_strength_of_schedule: odds.remaining_strength_of_schedule,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StandingsTheme {
+    Light,
+    Dark,
+}
+
+/// Standings from decided games with every tiebreaker applied, from `compute_standings` or
+/// `Season::full_standings`.
+#[derive(Clone, Debug)]
+pub struct Standings {
+    /// Every team, ordered by conference and division, then by finish within the division.
+    pub teams: Vec<Standing>,
+    /// Team ids per conference in finish order: the playoff teams by seed, then the rest
+    /// in reverse draft order.
+    pub conferences: HashMap<String, Vec<i32>>,
+    /// Each tiebreaker step that separated teams, in the order it was applied.
+    pub tiebreaks: Vec<TiebreakDecision>,
+    /// Ties only a coin toss could break.
+    pub coin_tosses: Vec<TieGroup>,
+}
+
+impl Standings {
+    pub fn division(&self, division: &str) -> Vec<&Standing> {
+        self.teams
+            .iter()
+            .filter(|standing| standing.division == division)
+            .collect()
+    }
+
+    pub fn conference(&self, conference: &str) -> Vec<&Standing> {
+        let team_ids: &[i32] = match self.conferences.get(conference) {
+            Some(team_ids) => team_ids,
+            None => return Vec::new(),
+        };
+        team_ids
+            .iter()
+            .filter_map(|team_id| {
+                self.teams
+                    .iter()
+                    .find(|standing| standing.team_id == *team_id)
+            })
+            .collect()
+    }
+}
+
+/// Standings from `games` without loading or simulating a season: undecided games are left
+/// out, and ties that come down to a coin toss are broken with a fixed seed, so the same
+/// games always give the same standings. Every team in a game must be in `teams`, and each
+/// conference needs enough teams to fill its playoff seeds.
+pub fn compute_standings(games: &[Game], teams: &[Team]) -> Result<Standings, Error> {
+    let season_year: i32 = games.first().map_or(0, |game| game.season_year);
+    let mut season: Season = Season::empty(season_year);
+    for team in teams {
+        season.teams.insert(team.team_id, Arc::new(team.clone()));
+    }
+    season.load_conference_division_mapping();
+    for game in games
+        .iter()
+        .filter(|game| game.game_result.is_some() && !game.has_tbd_team())
+    {
+        for team_id in [game.home_team.team_id, game.away_team.team_id] {
+            if !season.teams.contains_key(&team_id) {
+                return Err(Error::InvalidArgument(format!(
+                    "game {} has team {}, which is not in teams",
+                    game.game_id, team_id
+                )));
+            }
+        }
+        season.actual_games.insert(game.game_id, game.clone());
+    }
+    season.current_simulation_base_games = season.actual_games.clone();
+    season.set_seed(0);
+    season.full_standings()
+}
+
+impl Season {
+    /// Standings from decided games only, as if the season ended today. Ordered by
+    /// conference and division, then by finish within the division.
+    pub fn standings(&self) -> Result<Vec<Standing>, Error> {
+        Ok(self.full_standings()?.teams)
+    }
+
+    /// `standings()` with each conference's order and the tiebreakers behind them.
+    pub fn full_standings(&self) -> Result<Standings, Error> {
+        let result: CurrentSimulationResult = self.decided_games_result()?;
+        let mut seeds: HashMap<i32, u8> = HashMap::new();
+        for (seed, teams) in result.playoff_seeding.iter() {
+            for team_id in teams.iter() {
+                seeds.insert(*team_id, *seed);
+            }
+        }
+
+        let mut divisions: Vec<&String> = self.division_mapping.keys().collect();
+        divisions.sort_by_key(|division| {
+            let team_id: &i32 = self
+                .division_mapping
+                .get(*division)
+                .unwrap()
+                .first()
+                .unwrap();
+            (
+                self.teams.get(team_id).unwrap().conference.clone(),
+                *division,
+            )
+        });
+
+        let mut standings: Vec<Standing> = Vec::new();
+        for division in divisions {
+            let team_ids: &Vec<i32> = self.division_mapping.get(division).unwrap();
+            for team_id in result.finish_order(team_ids) {
+                let team = self.teams.get(&team_id).unwrap();
+                let record = result.team_records.get(&team_id).unwrap();
+                standings.push(Standing {
+                    team_id,
+                    abbreviation: team.abbreviation.clone(),
+                    name: team.name.clone(),
+                    conference: team.conference.clone(),
+                    division: team.division.clone(),
+                    overall_record: record.overall_record,
+                    overall_percent: record.overall_percent,
+                    division_record: record.division_record,
+                    conference_record: record.conference_record,
+                    points_for: record.points_for,
+                    points_against: record.points_against,
+                    playoff_seed: seeds.get(&team_id).copied(),
+                });
+            }
+        }
+        let mut conferences: HashMap<String, Vec<i32>> = HashMap::new();
+        for (conference, team_ids) in self.conference_mapping.iter() {
+            conferences.insert(conference.clone(), result.finish_order(team_ids));
+        }
+        Ok(Standings {
+            teams: standings,
+            conferences,
+            tiebreaks: result.tiebreaks,
+            coin_tosses: result.random_ties,
+        })
+    }
+
+    /// `team_id`'s standing and its odds from the baseline of the last run. `None` for an
+    /// unknown team, before the season has been simulated, or when the standings can't be
+    /// worked out.
+    pub fn team_summary(&self, team_id: i32) -> Option<TeamSummary> {
+        let lookup = SimulationResultLookup::new(&team_id, None);
+        let odds: TeamOdds =
+            TeamOdds::new_from_results(team_id, self.overall_results.get(&lookup)?);
+        let standing: Standing = self
+            .standings()
+            .ok()?
+            .into_iter()
+            .find(|standing| standing.team_id == team_id)?;
+        Some(TeamSummary::new(standing, &odds))
+    }
+
+    /// Summaries of every team with odds in `report`, such as a stored run from
+    /// `load_results`, in the order of `standings()`.
+    pub fn team_summaries(&self, report: &SimulationReport) -> Result<Vec<TeamSummary>, Error> {
+        Ok(self
+            .standings()?
+            .into_iter()
+            .filter_map(|standing| {
+                let odds: TeamOdds = report.team_odds(standing.team_id)?;
+                Some(TeamSummary::new(standing, &odds))
+            })
+            .collect())
+    }
+
+    /// Team ids per conference in seed order, as if the season ended today.
+    pub fn playoff_field(&self) -> Result<HashMap<String, Vec<i32>>, Error> {
+        let result: CurrentSimulationResult = self.decided_games_result()?;
+        let mut playoff_field: HashMap<String, Vec<i32>> = HashMap::new();
+        for seed in 1..8 {
+            let mut teams: Vec<&i32> = result.playoff_seeding.get(&seed).unwrap().iter().collect();
+            teams.sort();
+            for team_id in teams {
+                let conference: &String = &self.teams.get(team_id).unwrap().conference;
+                playoff_field
+                    .entry(conference.clone())
+                    .or_default()
+                    .push(*team_id);
+            }
+        }
+        Ok(playoff_field)
+    }
+
+    /// Each tiebreaker behind `standings()` after overall percentage, in the order it was
+    /// applied, such as "AFC wildcard spot 2: BUF, MIA, NYJ tied; head-to-head leaves BUF".
+    pub fn tiebreak_explanations(&self) -> Result<Vec<String>, Error> {
+        Ok(self
+            .decided_games_result()?
+            .tiebreaks
+            .iter()
+            .filter(|decision| decision.step != TiebreakStep::OverallPercent)
+            .map(|decision| self.explain_tiebreak(decision))
+            .collect())
+    }
+
+    fn explain_tiebreak(&self, decision: &TiebreakDecision) -> String {
+        let abbreviations = |team_ids: &[i32]| -> String {
+            team_ids
+                .iter()
+                .map(|team_id| self.teams.get(team_id).unwrap().abbreviation.clone())
+                .collect::<Vec<String>>()
+                .join(", ")
+        };
+        let team = self.teams.get(&decision.tied_teams[0]).unwrap();
+        let decided: String = match decision.pool_type {
+            PoolType::Division => format!("{} ranking", team.division),
+            PoolType::Wildcard => format!("{} wildcard spot {}", team.conference, decision.place),
+            PoolType::DivisionWinnerSeeding => {
+                format!("{} seed {}", team.conference, decision.place)
+            }
+            PoolType::DraftOrder => String::from("draft order"),
+        };
+        format!(
+            "{}: {} tied; {} leaves {}",
+            decided,
+            abbreviations(&decision.tied_teams),
+            decision.step,
+            abbreviations(&decision.remaining_teams)
+        )
+    }
+
+    /// Self-contained HTML table of `standings()`. Styling hooks are `standings-*` classes,
+    /// with `standings-light` or `standings-dark` on the table for the theme.
+    pub fn standings_html(&self, theme: StandingsTheme) -> Result<String, Error> {
+        let theme_class: &str = match theme {
+            StandingsTheme::Light => "standings-light",
+            StandingsTheme::Dark => "standings-dark",
+        };
+
+        let mut html: String = format!(
+            "<table class=\"standings {}\">\n<caption>{} Standings</caption>\n",
+            theme_class, self.season_year
+        );
+        let mut current_division: Option<String> = None;
+        for standing in self.standings()? {
+            if current_division.as_ref() != Some(&standing.division) {
+                if current_division.is_some() {
+                    html.push_str("</tbody>\n");
+                }
+                html.push_str(&format!(
+                    "<tbody class=\"standings-division\">\n\
+                     <tr class=\"standings-division-name\"><th colspan=\"11\">{}</th></tr>\n\
+                     <tr class=\"standings-header\"><th>Team</th><th>W</th><th>L</th><th>T</th>\
+                     <th>Pct</th><th>Div</th><th>Conf</th><th>PF</th><th>PA</th><th>Diff</th>\
+                     <th>Seed</th></tr>\n",
+                    escape_html(&standing.division)
+                ));
+                current_division = Some(standing.division.clone());
+            }
+
+            let row_class: &str = match standing.playoff_seed {
+                Some(seed) if seed <= 4 => "standings-team standings-division-leader",
+                Some(_) => "standings-team standings-wildcard",
+                None => "standings-team",
+            };
+            let seed: String = match standing.playoff_seed {
+                Some(seed) => seed.to_string(),
+                None => String::new(),
+            };
+            let (wins, losses, ties) = standing.overall_record;
+            html.push_str(&format!(
+                "<tr class=\"{}\"><td class=\"standings-name\" title=\"{}\">{}</td>\
+                 <td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td>\
+                 <td>{}</td><td>{}</td><td>{:+}</td>\
+                 <td class=\"standings-seed\">{}</td></tr>\n",
+                row_class,
+                escape_html(&standing.name),
+                escape_html(&standing.abbreviation),
+                wins,
+                losses,
+                ties,
+                format_percent(standing.overall_percent),
+                format_record(standing.division_record),
+                format_record(standing.conference_record),
+                standing.points_for,
+                standing.points_against,
+                standing.points_for - standing.points_against,
+                seed,
+            ));
+        }
+        if current_division.is_some() {
+            html.push_str("</tbody>\n");
+        }
+        html.push_str("</table>\n");
+        Ok(html)
+    }
+
+    fn decided_games_result(&self) -> Result<CurrentSimulationResult, Error> {
+        let mut season: Season = self.clone();
+        season.config.seeding_detail = SeedingDetail::FullSeeds;
+        season.current_simulation_result = CurrentSimulationResult::new();
+        season.current_simulation_games = season
+            .current_simulation_base_games
+            .iter()
+            .filter(|(_, game)| game.game_result.is_some())
+            .map(|(game_id, game)| (*game_id, game.clone()))
+            .collect::<HashMap<i32, Game>>();
+        season.evaluate_simulation_results(false)?;
+        Ok(season.current_simulation_result)
+    }
+}
+
+fn format_percent(percent: u16) -> String {
+    match percent {
+        1000 => String::from("1.000"),
+        p => format!(".{:03}", p),
+    }
+}
+
+fn format_record(record: (u16, u16, u16)) -> String {
+    match record {
+        (wins, losses, 0) => format!("{}-{}", wins, losses),
+        (wins, losses, ties) => format!("{}-{}-{}", wins, losses, ties),
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
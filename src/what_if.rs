@@ -0,0 +1,210 @@
+use crate::print_comparison;
+use nfl_schedule_simulator::{Error, Game, GameResult, Season, SimulationReport};
+use std::collections::{BTreeMap, HashMap};
+use std::io::{self, BufRead, Write};
+
+const HELP: &str = "\
+force <game> <home|away|tie>  pin a game's result; <game> is a game id or season_week_away_home
+unforce <game>                undo a forced result
+clear                         undo every forced result
+forced                        list the forced results
+sims <n>                      simulations per set of forced results
+odds [team...]                odds against the baseline, for every team when none are given
+quit";
+
+/// Interactive what-if session over one season. Reports are cached by forced results and
+/// simulation count, so the baseline is simulated once and going back to an earlier set of
+/// forced results doesn't simulate again. Every simulation starts from the same seed.
+pub struct WhatIf {
+    season: Season,
+    seed: u64,
+    sims: u64,
+    forced: BTreeMap<i32, GameResult>,
+    reports: HashMap<(Vec<(i32, GameResult)>, u64), SimulationReport>,
+}
+
+impl WhatIf {
+    pub fn new(season: Season, sims: u64, seed: u64) -> WhatIf {
+        WhatIf {
+            season,
+            seed,
+            sims,
+            forced: BTreeMap::new(),
+            reports: HashMap::new(),
+        }
+    }
+
+    /// Reads commands from standard input until `quit` or end of input.
+    pub fn run(&mut self) -> Result<(), Error> {
+        println!("Type help for commands.");
+        prompt()?;
+        for line in io::stdin().lock().lines() {
+            match self.execute(&line?) {
+                Ok(true) => {}
+                Ok(false) => break,
+                Err(e) => println!("{}", e),
+            }
+            prompt()?;
+        }
+        Ok(())
+    }
+
+    /// Runs one command; `false` once the session should end.
+    fn execute(&mut self, line: &str) -> Result<bool, Error> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words.as_slice() {
+            [] => {}
+            ["force", game, result] => {
+                let game: Game = self.find_game(game)?;
+                let game_result: GameResult = match *result {
+                    "home" => GameResult::HomeWin,
+                    "away" => GameResult::AwayWin,
+                    "tie" => GameResult::Tie,
+                    _ => {
+                        return Err(Error::InvalidArgument(format!(
+                            "unknown result {result}; use home, away or tie"
+                        )))
+                    }
+                };
+                self.forced.insert(game.game_id, game_result);
+                self.print_odds(&[game.away_team.team_id, game.home_team.team_id])?;
+            }
+            ["unforce", game] => {
+                let game: Game = self.find_game(game)?;
+                if self.forced.remove(&game.game_id).is_none() {
+                    return Err(Error::InvalidArgument(format!(
+                        "{} is not forced",
+                        game.game_id
+                    )));
+                }
+                self.print_odds(&[game.away_team.team_id, game.home_team.team_id])?;
+            }
+            ["clear"] => self.forced.clear(),
+            ["forced"] => {
+                for (game_id, game_result) in self.forced.iter() {
+                    let game: &Game = &self.season.actual_games[game_id];
+                    println!(
+                        "{} (week {}, id {}): {}",
+                        matchup(game),
+                        game.week,
+                        game_id,
+                        game_result
+                    );
+                }
+            }
+            ["sims", sims] => match sims.parse::<u64>() {
+                Ok(sims) if sims > 0 => self.sims = sims,
+                _ => {
+                    return Err(Error::InvalidArgument(format!(
+                        "{sims} is not a positive number"
+                    )))
+                }
+            },
+            ["odds", teams @ ..] => {
+                let team_ids: Vec<i32> = match teams.is_empty() {
+                    true => self
+                        .season
+                        .teams_sorted()
+                        .into_iter()
+                        .map(|team| team.team_id)
+                        .collect(),
+                    false => teams
+                        .iter()
+                        .map(|abbreviation| self.find_team(abbreviation))
+                        .collect::<Result<Vec<i32>, Error>>()?,
+                };
+                self.print_odds(&team_ids)?;
+            }
+            ["help"] => println!("{}", HELP),
+            ["quit"] | ["exit"] => return Ok(false),
+            _ => {
+                return Err(Error::InvalidArgument(format!(
+                    "unknown command {line:?}; type help for commands"
+                )))
+            }
+        }
+        Ok(true)
+    }
+
+    fn print_odds(&mut self, team_ids: &[i32]) -> Result<(), Error> {
+        let baseline: SimulationReport = self.report(Vec::new())?;
+        let forced: Vec<(i32, GameResult)> = self
+            .forced
+            .iter()
+            .map(|(game_id, game_result)| (*game_id, game_result.clone()))
+            .collect();
+        let current: SimulationReport = self.report(forced)?;
+        print_comparison(&baseline, &current, team_ids);
+        Ok(())
+    }
+
+    /// The cached report for `forced` at the current simulation count, simulating it first
+    /// if there isn't one.
+    fn report(&mut self, forced: Vec<(i32, GameResult)>) -> Result<SimulationReport, Error> {
+        let key: (Vec<(i32, GameResult)>, u64) = (forced, self.sims);
+        if let Some(report) = self.reports.get(&key) {
+            return Ok(report.clone());
+        }
+        self.season.set_seed(self.seed);
+        let report: SimulationReport = self.season.simulate_with_overrides(key.0.clone(), key.1)?;
+        self.reports.insert(key, report.clone());
+        Ok(report)
+    }
+
+    /// A game by id, or by `season_week_away_home` such as `2024_14_NYJ_MIA`.
+    fn find_game(&self, name: &str) -> Result<Game, Error> {
+        if let Ok(game_id) = name.parse::<i32>() {
+            return match self.season.actual_games.get(&game_id) {
+                Some(game) => Ok(game.clone()),
+                None => Err(Error::InvalidArgument(format!("no game {game_id}"))),
+            };
+        }
+        let parts: Vec<&str> = name.split('_').collect();
+        let (season_year, week, away, home): (i32, i32, &str, &str) = match parts.as_slice() {
+            [season_year, week, away, home] => match (season_year.parse(), week.parse()) {
+                (Ok(season_year), Ok(week)) => (season_year, week, away, home),
+                _ => return Err(Error::InvalidArgument(format!("bad game {name}"))),
+            },
+            _ => {
+                return Err(Error::InvalidArgument(format!(
+                    "bad game {name}; use an id or season_week_away_home"
+                )))
+            }
+        };
+        match self.season.actual_games.values().find(|game| {
+            game.season_year == season_year
+                && game.week == week
+                && game.away_team.abbreviation == away
+                && game.home_team.abbreviation == home
+        }) {
+            Some(game) => Ok(game.clone()),
+            None => Err(Error::InvalidArgument(format!("no game {name}"))),
+        }
+    }
+
+    fn find_team(&self, abbreviation: &str) -> Result<i32, Error> {
+        match self
+            .season
+            .teams
+            .values()
+            .find(|team| team.abbreviation == abbreviation)
+        {
+            Some(team) => Ok(team.team_id),
+            None => Err(Error::InvalidArgument(format!(
+                "unknown team {abbreviation}"
+            ))),
+        }
+    }
+}
+
+fn matchup(game: &Game) -> String {
+    format!(
+        "{} @ {}",
+        game.away_team.abbreviation, game.home_team.abbreviation
+    )
+}
+
+fn prompt() -> io::Result<()> {
+    print!("> ");
+    io::stdout().flush()
+}